@@ -3,6 +3,9 @@ use libc::c_char;
 use libc::c_int;
 use std::ffi::CStr;
 use std::ffi::CString;
+use std::ptr::null_mut;
+use std::sync::atomic::AtomicPtr;
+use std::sync::atomic::Ordering;
 use std::sync::Mutex;
 use std::vec::Vec;
 
@@ -11,6 +14,7 @@ use crate::support::UniqueRef;
 
 extern "C" {
   fn v8__V8__SetFlagsFromCommandLine(argc: *mut c_int, argv: *mut *mut c_char);
+  fn v8__V8__SetFlagsFromString(flags: *const c_char, length: usize);
   fn v8__V8__GetVersion() -> *const c_char;
   fn v8__V8__InitializePlatform(platform: *mut Platform);
   fn v8__V8__Initialize();
@@ -33,6 +37,8 @@ lazy_static! {
     Mutex::new(GlobalState::Uninitialized);
 }
 
+static CURRENT_PLATFORM: AtomicPtr<Platform> = AtomicPtr::new(null_mut());
+
 pub fn assert_initialized() {
   let global_state_guard = GLOBAL_STATE.lock().unwrap();
   assert_eq!(*global_state_guard, Initialized);
@@ -79,6 +85,16 @@ pub fn set_flags_from_command_line(args: Vec<String>) -> Vec<String> {
     .collect()
 }
 
+/// Sets V8 flags from a string, e.g. `"--max-old-space-size=256"`. Multiple
+/// flags can be separated by whitespace. Unlike
+/// `set_flags_from_command_line()`, flags that V8 does not understand are
+/// silently ignored.
+pub fn set_flags_from_string(flags: &str) {
+  unsafe {
+    v8__V8__SetFlagsFromString(flags.as_ptr() as *const c_char, flags.len())
+  }
+}
+
 /// Get the version string.
 pub fn get_version() -> &'static str {
   let version = unsafe { v8__V8__GetVersion() };
@@ -94,10 +110,19 @@ pub fn get_version() -> &'static str {
 pub fn initialize_platform(platform: UniqueRef<Platform>) {
   let mut global_state_guard = GLOBAL_STATE.lock().unwrap();
   assert_eq!(*global_state_guard, Uninitialized);
-  unsafe { v8__V8__InitializePlatform(platform.into_raw()) };
+  let platform = platform.into_raw();
+  CURRENT_PLATFORM.store(platform, Ordering::SeqCst);
+  unsafe { v8__V8__InitializePlatform(platform) };
   *global_state_guard = PlatformInitialized;
 }
 
+/// Returns the platform that was passed to `initialize_platform()`, or `None`
+/// if no platform has been initialized or it has been shut down with
+/// `shutdown_platform()`.
+pub fn get_current_platform() -> Option<&'static Platform> {
+  unsafe { CURRENT_PLATFORM.load(Ordering::SeqCst).as_ref() }
+}
+
 /// Initializes V8. This function needs to be called before the first Isolate
 /// is created. It always returns true.
 pub fn initialize() {
@@ -128,10 +153,12 @@ pub unsafe fn dispose() -> bool {
 }
 
 /// Clears all references to the v8::Platform. This should be invoked after
-/// V8 was disposed.
+/// V8 was disposed with `dispose()`. Once the platform has been shut down, V8
+/// can not be initialized again in the same process.
 pub fn shutdown_platform() {
   let mut global_state_guard = GLOBAL_STATE.lock().unwrap();
   assert_eq!(*global_state_guard, Disposed);
+  CURRENT_PLATFORM.store(null_mut(), Ordering::SeqCst);
   unsafe { v8__V8__ShutdownPlatform() };
   *global_state_guard = PlatformShutdown;
 }
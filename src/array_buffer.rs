@@ -310,4 +310,27 @@ impl ArrayBuffer {
       ))
     }
   }
+
+  /// Returns a new standalone BackingStore that wraps memory owned by the
+  /// embedder. `deleter` is called with `data`, `byte_length` and
+  /// `deleter_data` when V8 releases the backing store, which is when the
+  /// last reference to it (including ones held by array buffers) is dropped.
+  ///
+  /// The result can be later passed to ArrayBuffer::New.
+  ///
+  /// This function is unsafe because the caller must guarantee that `data`
+  /// points to `byte_length` bytes that stay valid until `deleter` is called.
+  pub unsafe fn new_backing_store_from_ptr(
+    data: *mut c_void,
+    byte_length: usize,
+    deleter: BackingStoreDeleterCallback,
+    deleter_data: *mut c_void,
+  ) -> UniqueRef<BackingStore> {
+    UniqueRef::from_raw(v8__ArrayBuffer__NewBackingStore__with_data(
+      data,
+      byte_length,
+      deleter,
+      deleter_data,
+    ))
+  }
 }
@@ -1,5 +1,7 @@
+#include <algorithm>
 #include <cassert>
 #include <cstdint>
+#include <functional>
 #include <iostream>
 
 #include "support.h"
@@ -18,6 +20,10 @@ static_assert(sizeof(two_pointers_t) ==
                   sizeof(std::shared_ptr<v8::BackingStore>),
               "std::shared_ptr<v8::BackingStore> size mismatch");
 
+static_assert(v8::String::kMaxLength ==
+                  (sizeof(void*) == 4 ? (1 << 28) - 16 : (1 << 29) - 24),
+              "String::kMaxLength mismatch");
+
 static_assert(sizeof(v8::ScriptOrigin) == sizeof(size_t) * 7,
               "ScriptOrigin size mismatch");
 
@@ -32,9 +38,15 @@ static_assert(sizeof(v8::PromiseRejectMessage) == sizeof(size_t) * 3,
 
 static_assert(sizeof(v8::Locker) == sizeof(size_t) * 2, "Locker size mismatch");
 
+static_assert(sizeof(v8::Unlocker) == sizeof(size_t) * 1,
+              "Unlocker size mismatch");
+
 static_assert(sizeof(v8::ScriptCompiler::Source) == sizeof(size_t) * 8,
               "Source size mismatch");
 
+static_assert(sizeof(v8::ScriptCompiler::CachedData) == sizeof(size_t) * 3,
+              "CachedData size mismatch");
+
 static_assert(sizeof(v8::FunctionCallbackInfo<v8::Value>) == sizeof(size_t) * 3,
               "FunctionCallbackInfo size mismatch");
 
@@ -50,14 +62,56 @@ static_assert(sizeof(v8::TryCatch) == sizeof(size_t) * 6,
 static_assert(sizeof(v8::Location) == sizeof(size_t) * 1,
               "Location size mismatch");
 
+static_assert(sizeof(v8::AllocationProfile::Allocation) == sizeof(size_t) * 2,
+              "AllocationProfile::Allocation size mismatch");
+
 static_assert(sizeof(v8::SnapshotCreator) == sizeof(size_t) * 1,
               "SnapshotCreator size mismatch");
 
 static_assert(sizeof(three_pointers_t) == sizeof(v8_inspector::StringView),
               "StringView size mismatch");
 
+static_assert(v8::kArrayProto_entries == 0 && v8::kArrayProto_forEach == 1 &&
+                  v8::kArrayProto_keys == 2 && v8::kArrayProto_values == 3 &&
+                  v8::kAsyncIteratorPrototype == 4 &&
+                  v8::kErrorPrototype == 5 && v8::kIteratorPrototype == 6 &&
+                  v8::kObjProto_valueOf == 7,
+              "Intrinsic mismatch");
+
+static_assert(static_cast<int>(v8::MicrotasksPolicy::kExplicit) == 0 &&
+                  static_cast<int>(v8::MicrotasksPolicy::kScoped) == 1 &&
+                  static_cast<int>(v8::MicrotasksPolicy::kAuto) == 2,
+              "MicrotasksPolicy mismatch");
+
+static_assert(v8::Isolate::kMessageLog == 1 << 0 &&
+                  v8::Isolate::kMessageDebug == 1 << 1 &&
+                  v8::Isolate::kMessageInfo == 1 << 2 &&
+                  v8::Isolate::kMessageError == 1 << 3 &&
+                  v8::Isolate::kMessageWarning == 1 << 4,
+              "MessageErrorLevel mismatch");
+
+static_assert(v8::ALL_PROPERTIES == 0 && v8::ONLY_WRITABLE == 1 &&
+                  v8::ONLY_ENUMERABLE == 2 && v8::ONLY_CONFIGURABLE == 4 &&
+                  v8::SKIP_STRINGS == 8 && v8::SKIP_SYMBOLS == 16,
+              "PropertyFilter mismatch");
+
+static_assert(static_cast<int>(v8::KeyCollectionMode::kOwnOnly) == 0 &&
+                  static_cast<int>(v8::KeyCollectionMode::kIncludePrototypes) ==
+                      1,
+              "KeyCollectionMode mismatch");
+
+static_assert(static_cast<int>(v8::IndexFilter::kIncludeIndices) == 0 &&
+                  static_cast<int>(v8::IndexFilter::kSkipIndices) == 1,
+              "IndexFilter mismatch");
+
+static_assert(static_cast<int>(v8::KeyConversionMode::kConvertToString) == 0 &&
+                  static_cast<int>(v8::KeyConversionMode::kKeepNumbers) == 1 &&
+                  static_cast<int>(v8::KeyConversionMode::kNoNumbers) == 2,
+              "KeyConversionMode mismatch");
+
 enum InternalSlots {
   kSlotDynamicImport = 0,
+  kSlotPrepareStackTrace,
   kNumInternalSlots,
 };
 #define SLOT_NUM_EXTERNAL(isolate) \
@@ -86,11 +140,35 @@ v8::MaybeLocal<v8::Promise> HostImportModuleDynamicallyCallback(
   }
 }
 
+// v8::PrepareStackTraceCallback
+typedef v8::Value* (*v8__PrepareStackTraceCallback)(
+    v8::Local<v8::Context> context, v8::Local<v8::Value> error,
+    v8::Local<v8::Array> sites);
+
+v8::MaybeLocal<v8::Value> PrepareStackTraceCallback(
+    v8::Local<v8::Context> context, v8::Local<v8::Value> error,
+    v8::Local<v8::Array> sites) {
+  auto* isolate = context->GetIsolate();
+  void* d = isolate->GetData(SLOT_INTERNAL(isolate, kSlotPrepareStackTrace));
+  auto* callback = reinterpret_cast<v8__PrepareStackTraceCallback>(d);
+  assert(callback != nullptr);
+  auto* value_ptr = callback(context, error, sites);
+  if (value_ptr == nullptr) {
+    return v8::MaybeLocal<v8::Value>();
+  } else {
+    return v8::MaybeLocal<v8::Value>(ptr_to_local(value_ptr));
+  }
+}
+
 extern "C" {
 void v8__V8__SetFlagsFromCommandLine(int* argc, char** argv) {
   v8::V8::SetFlagsFromCommandLine(argc, argv, true);
 }
 
+void v8__V8__SetFlagsFromString(const char* flags, size_t length) {
+  v8::V8::SetFlagsFromString(flags, length);
+}
+
 const char* v8__V8__GetVersion() { return v8::V8::GetVersion(); }
 
 void v8__V8__InitializePlatform(v8::Platform* platform) {
@@ -113,6 +191,31 @@ void v8__Isolate__Enter(v8::Isolate* isolate) { isolate->Enter(); }
 
 void v8__Isolate__Exit(v8::Isolate* isolate) { isolate->Exit(); }
 
+v8::Isolate* v8__Isolate__GetCurrent() { return v8::Isolate::GetCurrent(); }
+
+bool v8__Isolate__IsInUse(v8::Isolate* isolate) { return isolate->IsInUse(); }
+
+void v8__Isolate__LowMemoryNotification(v8::Isolate* isolate) {
+  isolate->LowMemoryNotification();
+}
+
+int64_t v8__Isolate__AdjustAmountOfExternalAllocatedMemory(
+    v8::Isolate* isolate, int64_t change_in_bytes) {
+  return isolate->AdjustAmountOfExternalAllocatedMemory(change_in_bytes);
+}
+
+void v8__Isolate__IncreaseHeapLimitForDebugging(v8::Isolate* isolate) {
+  isolate->IncreaseHeapLimitForDebugging();
+}
+
+void v8__Isolate__RestoreOriginalHeapLimit(v8::Isolate* isolate) {
+  isolate->RestoreOriginalHeapLimit();
+}
+
+void v8__Isolate__SetStackLimit(v8::Isolate* isolate, uintptr_t stack_limit) {
+  isolate->SetStackLimit(stack_limit);
+}
+
 const v8::Context* v8__Isolate__GetCurrentContext(v8::Isolate* isolate) {
   return local_to_ptr(isolate->GetCurrentContext());
 }
@@ -138,11 +241,69 @@ void v8__Isolate__RunMicrotasks(v8::Isolate* isolate) {
   isolate->RunMicrotasks();
 }
 
+void v8__Isolate__PerformMicrotaskCheckpoint(v8::Isolate* isolate) {
+  isolate->PerformMicrotaskCheckpoint();
+}
+
+void v8__Isolate__SetMicrotasksPolicy(v8::Isolate* isolate,
+                                      v8::MicrotasksPolicy policy) {
+  isolate->SetMicrotasksPolicy(policy);
+}
+
+v8::MicrotasksPolicy v8__Isolate__GetMicrotasksPolicy(
+    const v8::Isolate* isolate) {
+  return isolate->GetMicrotasksPolicy();
+}
+
+v8::MicrotaskQueue* v8__MicrotaskQueue__New(v8::Isolate* isolate,
+                                            v8::MicrotasksPolicy policy) {
+  return v8::MicrotaskQueue::New(isolate, policy).release();
+}
+
+void v8__MicrotaskQueue__DELETE(v8::MicrotaskQueue* self) { delete self; }
+
+void v8__MicrotaskQueue__EnqueueMicrotask(v8::MicrotaskQueue* self,
+                                          v8::Isolate* isolate,
+                                          const v8::Function& microtask) {
+  self->EnqueueMicrotask(isolate, ptr_to_local(&microtask));
+}
+
+void v8__MicrotaskQueue__PerformCheckpoint(v8::MicrotaskQueue* self,
+                                           v8::Isolate* isolate) {
+  self->PerformCheckpoint(isolate);
+}
+
+bool v8__MicrotaskQueue__IsRunningMicrotasks(const v8::MicrotaskQueue& self) {
+  return self.IsRunningMicrotasks();
+}
+
+int v8__MicrotaskQueue__GetMicrotasksScopeDepth(
+    const v8::MicrotaskQueue& self) {
+  return self.GetMicrotasksScopeDepth();
+}
+
 void v8__Isolate__EnqueueMicrotask(v8::Isolate* isolate,
                                    const v8::Function& function) {
   isolate->EnqueueMicrotask(ptr_to_local(&function));
 }
 
+const v8::Data* v8__Isolate__GetDataFromSnapshotOnce(v8::Isolate* isolate,
+                                                     size_t index) {
+  return maybe_local_to_ptr(isolate->GetDataFromSnapshotOnce<v8::Data>(index));
+}
+
+void v8__Isolate__AddNearHeapLimitCallback(v8::Isolate* isolate,
+                                           v8::NearHeapLimitCallback callback,
+                                           void* data) {
+  isolate->AddNearHeapLimitCallback(callback, data);
+}
+
+void v8__Isolate__RemoveNearHeapLimitCallback(
+    v8::Isolate* isolate, v8::NearHeapLimitCallback callback,
+    size_t heap_limit) {
+  isolate->RemoveNearHeapLimitCallback(callback, heap_limit);
+}
+
 void v8__Isolate__RequestInterrupt(v8::Isolate* isolate,
                                    v8::InterruptCallback callback, void* data) {
   isolate->RequestInterrupt(callback, data);
@@ -171,11 +332,29 @@ void v8__Isolate__SetHostImportModuleDynamicallyCallback(
       HostImportModuleDynamicallyCallback);
 }
 
+void v8__Isolate__SetPrepareStackTraceCallback(
+    v8::Isolate* isolate, v8__PrepareStackTraceCallback callback) {
+  isolate->SetData(SLOT_INTERNAL(isolate, kSlotPrepareStackTrace),
+                   reinterpret_cast<void*>(callback));
+  isolate->SetPrepareStackTraceCallback(PrepareStackTraceCallback);
+}
+
+void v8__Isolate__SetFatalErrorHandler(v8::Isolate* isolate,
+                                       v8::FatalErrorCallback callback) {
+  isolate->SetFatalErrorHandler(callback);
+}
+
 bool v8__Isolate__AddMessageListener(v8::Isolate* isolate,
                                      v8::MessageCallback callback) {
   return isolate->AddMessageListener(callback);
 }
 
+bool v8__Isolate__AddMessageListenerWithErrorLevel(v8::Isolate* isolate,
+                                                   v8::MessageCallback callback,
+                                                   int message_levels) {
+  return isolate->AddMessageListenerWithErrorLevel(callback, message_levels);
+}
+
 const v8::Value* v8__Isolate__ThrowException(v8::Isolate* isolate,
                                              const v8::Value& exception) {
   return local_to_ptr(isolate->ThrowException(ptr_to_local(&exception)));
@@ -202,6 +381,30 @@ size_t v8__Isolate__CreateParams__SIZEOF() {
   return sizeof(v8::Isolate::CreateParams);
 }
 
+void v8__ResourceConstraints__ConfigureDefaultsFromHeapSize(
+    v8::ResourceConstraints* constraints, size_t initial_heap_size_in_bytes,
+    size_t maximum_heap_size_in_bytes) {
+  constraints->ConfigureDefaultsFromHeapSize(initial_heap_size_in_bytes,
+                                             maximum_heap_size_in_bytes);
+}
+
+void v8__Locker__CONSTRUCT(uninit_t<v8::Locker>* buf, v8::Isolate* isolate) {
+  construct_in_place<v8::Locker>(buf, isolate);
+}
+
+void v8__Locker__DESTRUCT(v8::Locker* self) { self->~Locker(); }
+
+bool v8__Locker__IsLocked(v8::Isolate* isolate) {
+  return v8::Locker::IsLocked(isolate);
+}
+
+void v8__Unlocker__CONSTRUCT(uninit_t<v8::Unlocker>* buf,
+                             v8::Isolate* isolate) {
+  construct_in_place<v8::Unlocker>(buf, isolate);
+}
+
+void v8__Unlocker__DESTRUCT(v8::Unlocker* self) { self->~Unlocker(); }
+
 void v8__HandleScope__CONSTRUCT(uninit_t<v8::HandleScope>* buf,
                                 v8::Isolate* isolate) {
   construct_in_place<v8::HandleScope>(buf, isolate);
@@ -256,17 +459,74 @@ void v8__Global__Reset__2(const v8::Data*& self, v8::Isolate* isolate,
   self = make_pod<v8::Data*>(std::move(global));
 }
 
+void v8__Global__SetWeak(const v8::Data*& self) {
+  // V8 resets a weak handle by writing to the location of the handle itself,
+  // so the Rust-owned handle must not be copied into a temporary here.
+  reinterpret_cast<v8::Global<v8::Data>&>(self).SetWeak();
+}
+
+void v8__Value__SetWeakFinalizer(v8::Isolate* isolate, const v8::Value& self,
+                                 void (*finalizer)(void*), void* data) {
+  struct WeakFinalizer {
+    v8::Global<v8::Value> handle;
+    void (*finalizer)(void*);
+    void* data;
+  };
+  auto holder = new WeakFinalizer{
+      v8::Global<v8::Value>(isolate, ptr_to_local(&self)), finalizer, data};
+  // The first-pass callback may only reset the handle. The finalizer runs
+  // Rust code, so it is deferred to the second pass.
+  holder->handle.SetWeak(
+      holder,
+      [](const v8::WeakCallbackInfo<WeakFinalizer>& info) {
+        info.GetParameter()->handle.Reset();
+        info.SetSecondPassCallback(
+            [](const v8::WeakCallbackInfo<WeakFinalizer>& info) {
+              auto holder = info.GetParameter();
+              holder->finalizer(holder->data);
+              delete holder;
+            });
+      },
+      v8::WeakCallbackType::kParameter);
+}
+
 void v8__ScriptCompiler__Source__CONSTRUCT(
     uninit_t<v8::ScriptCompiler::Source>* buf, const v8::String& source_string,
-    const v8::ScriptOrigin& origin) {
+    const v8::ScriptOrigin& origin,
+    v8::ScriptCompiler::CachedData* cached_data) {
   construct_in_place<v8::ScriptCompiler::Source>(
-      buf, ptr_to_local(&source_string), origin);
+      buf, ptr_to_local(&source_string), origin, cached_data);
 }
 
 void v8__ScriptCompiler__Source__DESTRUCT(v8::ScriptCompiler::Source* self) {
   self->~Source();
 }
 
+const v8::ScriptCompiler::CachedData*
+v8__ScriptCompiler__Source__GetCachedData(
+    const v8::ScriptCompiler::Source& self) {
+  return self.GetCachedData();
+}
+
+v8::ScriptCompiler::CachedData* v8__ScriptCompiler__CachedData__NEW(
+    const uint8_t* data, int length) {
+  uint8_t* copy = new uint8_t[length];
+  std::copy(data, data + length, copy);
+  return new v8::ScriptCompiler::CachedData(
+      copy, length, v8::ScriptCompiler::CachedData::BufferOwned);
+}
+
+void v8__ScriptCompiler__CachedData__DELETE(
+    v8::ScriptCompiler::CachedData* self) {
+  delete self;
+}
+
+v8::ScriptCompiler::CachedData* v8__UnboundModuleScript__CreateCodeCache(
+    const v8::UnboundModuleScript& unbound_module_script) {
+  return v8::ScriptCompiler::CreateCodeCache(
+      ptr_to_local(&unbound_module_script));
+}
+
 const v8::Module* v8__ScriptCompiler__CompileModule(
     v8::Isolate* isolate, v8::ScriptCompiler::Source* source,
     v8::ScriptCompiler::CompileOptions options,
@@ -474,6 +734,46 @@ const v8::Int32* v8__Value__ToInt32(const v8::Value& self,
   return maybe_local_to_ptr(self.ToInt32(ptr_to_local(&context)));
 }
 
+const v8::Uint32* v8__Value__ToArrayIndex(const v8::Value& self,
+                                          const v8::Context& context) {
+  return maybe_local_to_ptr(self.ToArrayIndex(ptr_to_local(&context)));
+}
+
+uint32_t v8__Value__GetHash(const v8::Value& self) {
+  auto value = ptr_to_local(&self);
+  if (value->IsObject()) {
+    return static_cast<uint32_t>(value.As<v8::Object>()->GetIdentityHash());
+  } else if (value->IsName()) {
+    return static_cast<uint32_t>(value.As<v8::Name>()->GetIdentityHash());
+  } else if (value->IsNumber()) {
+    // std::hash<double> maps 0.0 and -0.0 to the same hash.
+    double number = value.As<v8::Number>()->Value();
+    return static_cast<uint32_t>(std::hash<double>()(number));
+  } else if (value->IsBigInt()) {
+    int64_t bigint = value.As<v8::BigInt>()->Int64Value();
+    return static_cast<uint32_t>(std::hash<int64_t>()(bigint));
+  } else if (value->IsTrue()) {
+    return 1;
+  } else if (value->IsFalse()) {
+    return 2;
+  } else if (value->IsNull()) {
+    return 3;
+  } else {
+    return 4;
+  }
+}
+
+uint32_t v8__Data__GetHash(const v8::Data& self) {
+  if (self.IsValue()) {
+    return v8__Value__GetHash(static_cast<const v8::Value&>(self));
+  } else if (self.IsModule()) {
+    return static_cast<uint32_t>(
+        static_cast<const v8::Module&>(self).GetIdentityHash());
+  } else {
+    return 0;
+  }
+}
+
 const v8::Integer* v8__Value__ToInteger(const v8::Value& self,
                                         const v8::Context& context) {
   return maybe_local_to_ptr(self.ToInteger(ptr_to_local(&context)));
@@ -504,6 +804,68 @@ const v8::Object* v8__Value__ToObject(const v8::Value& self,
   return maybe_local_to_ptr(self.ToObject(ptr_to_local(&context)));
 }
 
+// Implements the ToPrimitive abstract operation (ECMA-262 7.1.1), which is
+// not exposed by the V8 API. The hint is 0 for "default", 1 for "number" and
+// 2 for "string".
+const v8::Value* v8__Value__ToPrimitive(const v8::Value& self,
+                                        const v8::Context& context, int hint) {
+  auto value = ptr_to_local(&self);
+  if (!value->IsObject()) return local_to_ptr(value);
+  auto ctx = ptr_to_local(&context);
+  auto isolate = ctx->GetIsolate();
+  auto object = value.As<v8::Object>();
+  auto throw_type_error = [isolate]() {
+    auto message = v8::String::NewFromUtf8(
+                       isolate, "Cannot convert object to primitive value")
+                       .ToLocalChecked();
+    isolate->ThrowException(v8::Exception::TypeError(message));
+  };
+
+  v8::Local<v8::Value> exotic_to_prim;
+  if (!object->Get(ctx, v8::Symbol::GetToPrimitive(isolate))
+           .ToLocal(&exotic_to_prim)) {
+    return nullptr;
+  }
+  if (!exotic_to_prim->IsNullOrUndefined()) {
+    if (!exotic_to_prim->IsFunction()) {
+      throw_type_error();
+      return nullptr;
+    }
+    static const char* const hint_names[] = {"default", "number", "string"};
+    v8::Local<v8::Value> argv[] = {
+        v8::String::NewFromUtf8(isolate, hint_names[hint]).ToLocalChecked()};
+    v8::Local<v8::Value> result;
+    if (!exotic_to_prim.As<v8::Function>()
+             ->Call(ctx, object, 1, argv)
+             .ToLocal(&result)) {
+      return nullptr;
+    }
+    if (result->IsObject()) {
+      throw_type_error();
+      return nullptr;
+    }
+    return local_to_ptr(result);
+  }
+
+  // OrdinaryToPrimitive (ECMA-262 7.1.1.1).
+  const char* method_names[] = {"valueOf", "toString"};
+  if (hint == 2) std::swap(method_names[0], method_names[1]);
+  for (auto method_name : method_names) {
+    auto key = v8::String::NewFromUtf8(isolate, method_name).ToLocalChecked();
+    v8::Local<v8::Value> method;
+    if (!object->Get(ctx, key).ToLocal(&method)) return nullptr;
+    if (!method->IsFunction()) continue;
+    v8::Local<v8::Value> result;
+    if (!method.As<v8::Function>()->Call(ctx, object, 0, nullptr).ToLocal(
+            &result)) {
+      return nullptr;
+    }
+    if (!result->IsObject()) return local_to_ptr(result);
+  }
+  throw_type_error();
+  return nullptr;
+}
+
 void v8__Value__NumberValue(const v8::Value& self, const v8::Context& context,
                             v8::Maybe<double>* out) {
   *out = self.NumberValue(ptr_to_local(&context));
@@ -524,6 +886,12 @@ void v8__Value__Int32Value(const v8::Value& self, const v8::Context& context,
   *out = self.Int32Value(ptr_to_local(&context));
 }
 
+const v8::External* v8__External__New(v8::Isolate* isolate, void* value) {
+  return local_to_ptr(v8::External::New(isolate, value));
+}
+
+void* v8__External__Value(const v8::External& self) { return self.Value(); }
+
 const v8::Primitive* v8__Null(v8::Isolate* isolate) {
   return local_to_ptr(v8::Null(isolate));
 }
@@ -536,6 +904,8 @@ const v8::Boolean* v8__Boolean__New(v8::Isolate* isolate, bool value) {
   return local_to_ptr(v8::Boolean::New(isolate, value));
 }
 
+bool v8__Boolean__Value(const v8::Boolean& self) { return self.Value(); }
+
 const v8::PrimitiveArray* v8__PrimitiveArray__New(v8::Isolate* isolate,
                                                   int length) {
   return local_to_ptr(v8::PrimitiveArray::New(isolate, length));
@@ -671,6 +1041,14 @@ void v8__Template__Set(const v8::Template& self, const v8::Name& key,
   ptr_to_local(&self)->Set(ptr_to_local(&key), ptr_to_local(&value), attr);
 }
 
+void v8__Template__SetIntrinsicDataProperty(const v8::Template& self,
+                                            const v8::Name& key,
+                                            v8::Intrinsic intrinsic,
+                                            v8::PropertyAttribute attr) {
+  ptr_to_local(&self)->SetIntrinsicDataProperty(ptr_to_local(&key), intrinsic,
+                                                attr);
+}
+
 const v8::ObjectTemplate* v8__ObjectTemplate__New(
     v8::Isolate* isolate, const v8::FunctionTemplate& templ) {
   return local_to_ptr(v8::ObjectTemplate::New(isolate, ptr_to_local(&templ)));
@@ -682,6 +1060,58 @@ const v8::Object* v8__ObjectTemplate__NewInstance(
       ptr_to_local(&self)->NewInstance(ptr_to_local(&context)));
 }
 
+int v8__ObjectTemplate__InternalFieldCount(const v8::ObjectTemplate& self) {
+  return ptr_to_local(&self)->InternalFieldCount();
+}
+
+void v8__ObjectTemplate__SetInternalFieldCount(const v8::ObjectTemplate& self,
+                                               int value) {
+  ptr_to_local(&self)->SetInternalFieldCount(value);
+}
+
+void v8__ObjectTemplate__SetImmutableProto(const v8::ObjectTemplate& self) {
+  ptr_to_local(&self)->SetImmutableProto();
+}
+
+void v8__ObjectTemplate__SetAccessCheckCallback(
+    const v8::ObjectTemplate& self, v8::AccessCheckCallback callback) {
+  ptr_to_local(&self)->SetAccessCheckCallback(callback);
+}
+
+void v8__ObjectTemplate__SetAccessor(const v8::ObjectTemplate& self,
+                                     const v8::Name& key,
+                                     v8::AccessorNameGetterCallback getter) {
+  ptr_to_local(&self)->SetAccessor(ptr_to_local(&key), getter);
+}
+
+void v8__ObjectTemplate__SetAccessorWithData(
+    const v8::ObjectTemplate& self, const v8::Name& key,
+    v8::AccessorNameGetterCallback getter, const v8::Value& data) {
+  ptr_to_local(&self)->SetAccessor(ptr_to_local(&key), getter, nullptr,
+                                   ptr_to_local(&data));
+}
+
+void v8__ObjectTemplate__SetNamedPropertyHandler(
+    const v8::ObjectTemplate& self,
+    v8::GenericNamedPropertyGetterCallback getter,
+    v8::GenericNamedPropertySetterCallback setter,
+    v8::GenericNamedPropertyQueryCallback query,
+    v8::GenericNamedPropertyDeleterCallback deleter,
+    v8::GenericNamedPropertyEnumeratorCallback enumerator) {
+  ptr_to_local(&self)->SetHandler(v8::NamedPropertyHandlerConfiguration(
+      getter, setter, query, deleter, enumerator));
+}
+
+void v8__ObjectTemplate__SetIndexedPropertyHandler(
+    const v8::ObjectTemplate& self, v8::IndexedPropertyGetterCallback getter,
+    v8::IndexedPropertySetterCallback setter,
+    v8::IndexedPropertyQueryCallback query,
+    v8::IndexedPropertyDeleterCallback deleter,
+    v8::IndexedPropertyEnumeratorCallback enumerator) {
+  ptr_to_local(&self)->SetHandler(v8::IndexedPropertyHandlerConfiguration(
+      getter, setter, query, deleter, enumerator));
+}
+
 const v8::Object* v8__Object__New(v8::Isolate* isolate) {
   return local_to_ptr(v8::Object::New(isolate));
 }
@@ -751,6 +1181,13 @@ MaybeBool v8__Object__DefineOwnProperty(const v8::Object& self,
       ptr_to_local(&context), ptr_to_local(&key), ptr_to_local(&value), attr));
 }
 
+MaybeBool v8__Object__HasOwnProperty(const v8::Object& self,
+                                     const v8::Context& context,
+                                     const v8::Name& key) {
+  return maybe_to_maybe_bool(ptr_to_local(&self)->HasOwnProperty(
+      ptr_to_local(&context), ptr_to_local(&key)));
+}
+
 MaybeBool v8__Object__SetAccessor(const v8::Object& self,
                                   const v8::Context& context,
                                   const v8::Name& key,
@@ -759,6 +1196,39 @@ MaybeBool v8__Object__SetAccessor(const v8::Object& self,
       ptr_to_local(&context), ptr_to_local(&key), getter));
 }
 
+MaybeBool v8__Object__SetAccessor__with_configuration(
+    const v8::Object& self, const v8::Context& context, const v8::Name& key,
+    v8::AccessorNameGetterCallback getter,
+    v8::AccessorNameSetterCallback setter, const v8::Value* data,
+    v8::AccessControl settings, v8::PropertyAttribute attr) {
+  return maybe_to_maybe_bool(ptr_to_local(&self)->SetAccessor(
+      ptr_to_local(&context), ptr_to_local(&key), getter, setter,
+      ptr_to_maybe_local(data), settings, attr));
+}
+
+MaybeBool v8__Object__SetNativeDataProperty(
+    const v8::Object& self, const v8::Context& context, const v8::Name& key,
+    v8::AccessorNameGetterCallback getter) {
+  return maybe_to_maybe_bool(ptr_to_local(&self)->SetNativeDataProperty(
+      ptr_to_local(&context), ptr_to_local(&key), getter));
+}
+
+MaybeBool v8__Object__SetLazyDataProperty(
+    const v8::Object& self, const v8::Context& context, const v8::Name& key,
+    v8::AccessorNameGetterCallback getter) {
+  return maybe_to_maybe_bool(ptr_to_local(&self)->SetLazyDataProperty(
+      ptr_to_local(&context), ptr_to_local(&key), getter));
+}
+
+void v8__Object__SetAccessorProperty(const v8::Object& self,
+                                     const v8::Name& key,
+                                     const v8::Function& getter,
+                                     const v8::Function* setter,
+                                     v8::PropertyAttribute attr) {
+  ptr_to_local(&self)->SetAccessorProperty(
+      ptr_to_local(&key), ptr_to_local(&getter), ptr_to_local(setter), attr);
+}
+
 v8::Isolate* v8__Object__GetIsolate(const v8::Object& self) {
   return ptr_to_local(&self)->GetIsolate();
 }
@@ -767,10 +1237,30 @@ int v8__Object__GetIdentityHash(const v8::Object& self) {
   return ptr_to_local(&self)->GetIdentityHash();
 }
 
+const v8::String* v8__Object__GetConstructorName(const v8::Object& self) {
+  return local_to_ptr(ptr_to_local(&self)->GetConstructorName());
+}
+
 const v8::Context* v8__Object__CreationContext(const v8::Object& self) {
   return local_to_ptr(ptr_to_local(&self)->CreationContext());
 }
 
+const v8::Value* v8__Object__CallAsFunction(const v8::Object& self,
+                                            const v8::Context& context,
+                                            const v8::Value& recv, int argc,
+                                            const v8::Value* const argv[]) {
+  return maybe_local_to_ptr(ptr_to_local(&self)->CallAsFunction(
+      ptr_to_local(&context), ptr_to_local(&recv), argc,
+      const_ptr_array_to_local_array(argv)));
+}
+
+const v8::Value* v8__Object__CallAsConstructor(
+    const v8::Object& self, const v8::Context& context, int argc,
+    const v8::Value* const argv[]) {
+  return maybe_local_to_ptr(ptr_to_local(&self)->CallAsConstructor(
+      ptr_to_local(&context), argc, const_ptr_array_to_local_array(argv)));
+}
+
 const v8::Array* v8__Object__GetOwnPropertyNames(const v8::Object* self,
                                                  const v8::Context* context) {
   return maybe_local_to_ptr(
@@ -783,6 +1273,44 @@ const v8::Array* v8__Object__GetPropertyNames(const v8::Object* self,
       ptr_to_local(self)->GetPropertyNames(ptr_to_local(context)));
 }
 
+const v8::Array* v8__Object__GetPropertyNamesWithArgs(
+    const v8::Object& self, const v8::Context& context,
+    v8::KeyCollectionMode mode, v8::PropertyFilter property_filter,
+    v8::IndexFilter index_filter, v8::KeyConversionMode key_conversion) {
+  return maybe_local_to_ptr(ptr_to_local(&self)->GetPropertyNames(
+      ptr_to_local(&context), mode, property_filter, index_filter,
+      key_conversion));
+}
+
+const v8::Array* v8__Object__PreviewEntries(const v8::Object& self,
+                                            bool* is_key_value) {
+  return maybe_local_to_ptr(ptr_to_local(&self)->PreviewEntries(is_key_value));
+}
+
+int v8__Object__InternalFieldCount(const v8::Object& self) {
+  return ptr_to_local(&self)->InternalFieldCount();
+}
+
+const v8::Value* v8__Object__GetInternalField(const v8::Object& self,
+                                              int index) {
+  return local_to_ptr(ptr_to_local(&self)->GetInternalField(index));
+}
+
+void v8__Object__SetInternalField(const v8::Object& self, int index,
+                                  const v8::Value& value) {
+  ptr_to_local(&self)->SetInternalField(index, ptr_to_local(&value));
+}
+
+void* v8__Object__GetAlignedPointerFromInternalField(const v8::Object& self,
+                                                     int index) {
+  return ptr_to_local(&self)->GetAlignedPointerFromInternalField(index);
+}
+
+void v8__Object__SetAlignedPointerInInternalField(const v8::Object& self,
+                                                  int index, void* value) {
+  ptr_to_local(&self)->SetAlignedPointerInInternalField(index, value);
+}
+
 const v8::Array* v8__Array__New(v8::Isolate* isolate, int length) {
   return local_to_ptr(v8::Array::New(isolate, length));
 }
@@ -881,12 +1409,38 @@ void DeserializeInternalFields(v8::Local<v8::Object> holder, int index,
 
 const v8::Context* v8__Context__New(v8::Isolate* isolate,
                                     const v8::ObjectTemplate* templ,
-                                    const v8::Value* global_object) {
-  return local_to_ptr(
-      v8::Context::New(isolate, nullptr, ptr_to_maybe_local(templ),
-                       ptr_to_maybe_local(global_object),
-                       v8::DeserializeInternalFieldsCallback(
-                           DeserializeInternalFields, nullptr)));
+                                    const v8::Value* global_object,
+                                    v8::MicrotaskQueue* microtask_queue,
+                                    v8::DeserializeInternalFieldsCallback::
+                                        CallbackFunction deserializer) {
+  if (deserializer == nullptr) deserializer = DeserializeInternalFields;
+  return local_to_ptr(v8::Context::New(
+      isolate, nullptr, ptr_to_maybe_local(templ),
+      ptr_to_maybe_local(global_object),
+      v8::DeserializeInternalFieldsCallback(deserializer, nullptr),
+      microtask_queue));
+}
+
+const v8::Context* v8__Context__FromSnapshot(v8::Isolate* isolate,
+                                             size_t context_snapshot_index) {
+  return maybe_local_to_ptr(v8::Context::FromSnapshot(
+      isolate, context_snapshot_index,
+      v8::DeserializeInternalFieldsCallback(DeserializeInternalFields,
+                                            nullptr)));
+}
+
+const v8::Context* v8__Context__FromSnapshot__with_deserializer(
+    v8::Isolate* isolate, size_t context_snapshot_index,
+    v8::DeserializeInternalFieldsCallback::CallbackFunction deserializer) {
+  return maybe_local_to_ptr(v8::Context::FromSnapshot(
+      isolate, context_snapshot_index,
+      v8::DeserializeInternalFieldsCallback(deserializer, nullptr)));
+}
+
+const v8::Data* v8__Context__GetDataFromSnapshotOnce(const v8::Context& self,
+                                                     size_t index) {
+  return maybe_local_to_ptr(
+      ptr_to_local(&self)->GetDataFromSnapshotOnce<v8::Data>(index));
 }
 
 void v8__Context__Enter(const v8::Context& self) {
@@ -903,6 +1457,26 @@ const v8::Object* v8__Context__Global(const v8::Context& self) {
   return local_to_ptr(ptr_to_local(&self)->Global());
 }
 
+void v8__Context__DetachGlobal(const v8::Context& self) {
+  ptr_to_local(&self)->DetachGlobal();
+}
+
+void v8__Context__AllowCodeGenerationFromStrings(const v8::Context& self,
+                                                 bool allow) {
+  ptr_to_local(&self)->AllowCodeGenerationFromStrings(allow);
+}
+
+bool v8__Context__IsCodeGenerationFromStringsAllowed(
+    const v8::Context& self) {
+  return ptr_to_local(&self)->IsCodeGenerationFromStringsAllowed();
+}
+
+void v8__Context__SetErrorMessageForCodeGenerationFromStrings(
+    const v8::Context& self, const v8::String& message) {
+  ptr_to_local(&self)->SetErrorMessageForCodeGenerationFromStrings(
+      ptr_to_local(&message));
+}
+
 const v8::String* v8__Message__Get(const v8::Message& self) {
   return local_to_ptr(self.Get());
 }
@@ -960,6 +1534,11 @@ bool v8__Message__IsSharedCrossOrigin(const v8::Message& self) {
 
 bool v8__Message__IsOpaque(const v8::Message& self) { return self.IsOpaque(); }
 
+void v8__Message__GetScriptOrigin(const v8::Message& self,
+                                  uninit_t<v8::ScriptOrigin>* buf) {
+  construct_in_place<v8::ScriptOrigin>(buf, self.GetScriptOrigin());
+}
+
 v8::Isolate* v8__Message__GetIsolate(const v8::Message& self) {
   return self.GetIsolate();
 }
@@ -1016,6 +1595,18 @@ const v8::Value* v8__Function__Call(const v8::Function& self,
                                 argc, const_ptr_array_to_local_array(argv)));
 }
 
+const v8::Value* v8__Function__GetInferredName(const v8::Function& self) {
+  return local_to_ptr(ptr_to_local(&self)->GetInferredName());
+}
+
+const v8::Value* v8__Function__GetDebugName(const v8::Function& self) {
+  return local_to_ptr(ptr_to_local(&self)->GetDebugName());
+}
+
+const v8::Value* v8__Function__GetBoundFunction(const v8::Function& self) {
+  return local_to_ptr(ptr_to_local(&self)->GetBoundFunction());
+}
+
 const v8::FunctionTemplate* v8__FunctionTemplate__New(
     v8::Isolate* isolate, v8::FunctionCallback callback = nullptr) {
   return local_to_ptr(v8::FunctionTemplate::New(isolate, callback));
@@ -1032,6 +1623,35 @@ void v8__FunctionTemplate__SetClassName(const v8::FunctionTemplate& self,
   ptr_to_local(&self)->SetClassName(ptr_to_local(&name));
 }
 
+void v8__FunctionTemplate__Inherit(const v8::FunctionTemplate& self,
+                                   const v8::FunctionTemplate& parent) {
+  ptr_to_local(&self)->Inherit(ptr_to_local(&parent));
+}
+
+void v8__FunctionTemplate__SetLength(const v8::FunctionTemplate& self,
+                                     int length) {
+  ptr_to_local(&self)->SetLength(length);
+}
+
+void v8__FunctionTemplate__ReadOnlyPrototype(const v8::FunctionTemplate& self) {
+  ptr_to_local(&self)->ReadOnlyPrototype();
+}
+
+const v8::ObjectTemplate* v8__FunctionTemplate__PrototypeTemplate(
+    const v8::FunctionTemplate& self) {
+  return local_to_ptr(ptr_to_local(&self)->PrototypeTemplate());
+}
+
+const v8::ObjectTemplate* v8__FunctionTemplate__InstanceTemplate(
+    const v8::FunctionTemplate& self) {
+  return local_to_ptr(ptr_to_local(&self)->InstanceTemplate());
+}
+
+const v8::Object* v8__FunctionTemplate__NewRemoteInstance(
+    const v8::FunctionTemplate& self) {
+  return maybe_local_to_ptr(ptr_to_local(&self)->NewRemoteInstance());
+}
+
 v8::Isolate* v8__FunctionCallbackInfo__GetIsolate(
     const v8::FunctionCallbackInfo<v8::Value>& self) {
   return self.GetIsolate();
@@ -1062,15 +1682,56 @@ const v8::Value* v8__FunctionCallbackInfo__Data(
   return local_to_ptr(self.Data());
 }
 
+const v8::Value* v8__FunctionCallbackInfo__NewTarget(
+    const v8::FunctionCallbackInfo<v8::Value>& self) {
+  return local_to_ptr(self.NewTarget());
+}
+
 void v8__ReturnValue__Set(v8::ReturnValue<v8::Value>* self,
                           const v8::Value& value) {
   self->Set(ptr_to_local(&value));
 }
 
+void v8__ReturnValue__Set__Bool(v8::ReturnValue<v8::Value>* self, bool value) {
+  self->Set(value);
+}
+
+void v8__ReturnValue__Set__Int32(v8::ReturnValue<v8::Value>* self,
+                                 int32_t value) {
+  self->Set(value);
+}
+
+void v8__ReturnValue__Set__Uint32(v8::ReturnValue<v8::Value>* self,
+                                  uint32_t value) {
+  self->Set(value);
+}
+
+void v8__ReturnValue__Set__Double(v8::ReturnValue<v8::Value>* self,
+                                  double value) {
+  self->Set(value);
+}
+
+void v8__ReturnValue__SetNull(v8::ReturnValue<v8::Value>* self) {
+  self->SetNull();
+}
+
+void v8__ReturnValue__SetUndefined(v8::ReturnValue<v8::Value>* self) {
+  self->SetUndefined();
+}
+
+void v8__ReturnValue__SetEmptyString(v8::ReturnValue<v8::Value>* self) {
+  self->SetEmptyString();
+}
+
 const v8::Value* v8__ReturnValue__Get(const v8::ReturnValue<v8::Value>& self) {
   return local_to_ptr(self.Get());
 }
 
+const v8::StackTrace* v8__StackTrace__CurrentStackTrace(v8::Isolate* isolate,
+                                                       int frame_limit) {
+  return local_to_ptr(v8::StackTrace::CurrentStackTrace(isolate, frame_limit));
+}
+
 int v8__StackTrace__GetFrameCount(const v8::StackTrace& self) {
   return self.GetFrameCount();
 }
@@ -1198,13 +1859,41 @@ void v8__ScriptOrigin__CONSTRUCT(
     const v8::Boolean& resource_is_shared_cross_origin,
     const v8::Integer& script_id, const v8::Value& source_map_url,
     const v8::Boolean& resource_is_opaque, const v8::Boolean& is_wasm,
-    const v8::Boolean& is_module) {
+    const v8::Boolean& is_module,
+    const v8::PrimitiveArray* host_defined_options) {
   construct_in_place<v8::ScriptOrigin>(
       buf, ptr_to_local(&resource_name), ptr_to_local(&resource_line_offset),
       ptr_to_local(&resource_column_offset),
       ptr_to_local(&resource_is_shared_cross_origin), ptr_to_local(&script_id),
       ptr_to_local(&source_map_url), ptr_to_local(&resource_is_opaque),
-      ptr_to_local(&is_wasm), ptr_to_local(&is_module));
+      ptr_to_local(&is_wasm), ptr_to_local(&is_module),
+      ptr_to_local(host_defined_options));
+}
+
+const v8::Value* v8__ScriptOrigin__ResourceName(const v8::ScriptOrigin& self) {
+  return local_to_ptr(self.ResourceName());
+}
+
+int v8__ScriptOrigin__ScriptID(const v8::ScriptOrigin& self) {
+  auto script_id = self.ScriptID();
+  return script_id.IsEmpty() ? v8::Message::kNoScriptIdInfo
+                             : static_cast<int>(script_id->Value());
+}
+
+bool v8__ScriptOrigin__IsSharedCrossOrigin(const v8::ScriptOrigin& self) {
+  return self.Options().IsSharedCrossOrigin();
+}
+
+bool v8__ScriptOrigin__IsOpaque(const v8::ScriptOrigin& self) {
+  return self.Options().IsOpaque();
+}
+
+bool v8__ScriptOrigin__IsWasm(const v8::ScriptOrigin& self) {
+  return self.Options().IsWasm();
+}
+
+bool v8__ScriptOrigin__IsModule(const v8::ScriptOrigin& self) {
+  return self.Options().IsModule();
 }
 
 const v8::Value* v8__ScriptOrModule__GetResourceName(
@@ -1296,6 +1985,10 @@ bool v8__Promise__HasHandler(const v8::Promise& self) {
   return ptr_to_local(&self)->HasHandler();
 }
 
+void v8__Promise__MarkAsHandled(const v8::Promise& self) {
+  ptr_to_local(&self)->MarkAsHandled();
+}
+
 const v8::Value* v8__Promise__Result(const v8::Promise& self) {
   return local_to_ptr(ptr_to_local(&self)->Result());
 }
@@ -1353,6 +2046,21 @@ const v8::Object* v8__PropertyCallbackInfo__This(
   return local_to_ptr(self.This());
 }
 
+const v8::Object* v8__PropertyCallbackInfo__Holder(
+    const v8::PropertyCallbackInfo<v8::Value>& self) {
+  return local_to_ptr(self.Holder());
+}
+
+const v8::Value* v8__PropertyCallbackInfo__Data(
+    const v8::PropertyCallbackInfo<v8::Value>& self) {
+  return local_to_ptr(self.Data());
+}
+
+bool v8__PropertyCallbackInfo__ShouldThrowOnError(
+    const v8::PropertyCallbackInfo<v8::Value>& self) {
+  return self.ShouldThrowOnError();
+}
+
 const v8::Proxy* v8__Proxy__New(const v8::Context& context,
                                 const v8::Object& target,
                                 const v8::Object& handler) {
@@ -1374,9 +2082,35 @@ bool v8__Proxy__IsRevoked(const v8::Proxy& self) {
 
 void v8__Proxy__Revoke(const v8::Proxy& self) { ptr_to_local(&self)->Revoke(); }
 
-void v8__SnapshotCreator__CONSTRUCT(uninit_t<v8::SnapshotCreator>* buf,
-                                    const intptr_t* external_references) {
-  construct_in_place<v8::SnapshotCreator>(buf, external_references);
+v8::CompiledWasmModule* v8__WasmModuleObject__GetCompiledModule(
+    const v8::WasmModuleObject& self) {
+  return new v8::CompiledWasmModule(ptr_to_local(&self)->GetCompiledModule());
+}
+
+const v8::WasmModuleObject* v8__WasmModuleObject__FromCompiledModule(
+    v8::Isolate* isolate, const v8::CompiledWasmModule& compiled_module) {
+  return maybe_local_to_ptr(
+      v8::WasmModuleObject::FromCompiledModule(isolate, compiled_module));
+}
+
+const uint8_t* v8__CompiledWasmModule__GetWireBytesRef(
+    const v8::CompiledWasmModule& self, size_t* length) {
+  v8::MemorySpan<const uint8_t> span =
+      const_cast<v8::CompiledWasmModule&>(self).GetWireBytesRef();
+  *length = span.size();
+  return span.data();
+}
+
+void v8__CompiledWasmModule__DELETE(v8::CompiledWasmModule* self) {
+  delete self;
+}
+
+void v8__SnapshotCreator__CONSTRUCT(
+    uninit_t<v8::SnapshotCreator>* buf, const intptr_t* external_references,
+    const v8::StartupData* existing_snapshot_blob) {
+  construct_in_place<v8::SnapshotCreator>(
+      buf, external_references,
+      const_cast<v8::StartupData*>(existing_snapshot_blob));
 }
 
 void v8__SnapshotCreator__DESTRUCT(v8::SnapshotCreator* self) {
@@ -1385,6 +2119,13 @@ void v8__SnapshotCreator__DESTRUCT(v8::SnapshotCreator* self) {
 
 void v8__StartupData__DESTRUCT(v8::StartupData* self) { delete[] self->data; }
 
+v8::StartupData v8__StartupData__CopyFrom(const char* data, int raw_size) {
+  if (raw_size == 0) return {nullptr, 0};
+  char* copy = new char[raw_size];
+  memcpy(copy, data, raw_size);
+  return {copy, raw_size};
+}
+
 v8::Isolate* v8__SnapshotCreator__GetIsolate(v8::SnapshotCreator* self) {
   return self->GetIsolate();
 }
@@ -1407,6 +2148,36 @@ void v8__SnapshotCreator__SetDefaultContext(v8::SnapshotCreator* self,
   self->SetDefaultContext(ptr_to_local(&context), SerializeInternalFields);
 }
 
+void v8__SnapshotCreator__SetDefaultContext__with_serializer(
+    v8::SnapshotCreator* self, const v8::Context& context,
+    v8::SerializeInternalFieldsCallback::CallbackFunction serializer) {
+  self->SetDefaultContext(ptr_to_local(&context),
+                          v8::SerializeInternalFieldsCallback(serializer));
+}
+
+size_t v8__SnapshotCreator__AddContext(v8::SnapshotCreator* self,
+                                      const v8::Context& context) {
+  return self->AddContext(ptr_to_local(&context), SerializeInternalFields);
+}
+
+size_t v8__SnapshotCreator__AddContext__with_serializer(
+    v8::SnapshotCreator* self, const v8::Context& context,
+    v8::SerializeInternalFieldsCallback::CallbackFunction serializer) {
+  return self->AddContext(ptr_to_local(&context),
+                          v8::SerializeInternalFieldsCallback(serializer));
+}
+
+size_t v8__SnapshotCreator__AddData_to_isolate(v8::SnapshotCreator* self,
+                                              const v8::Data& data) {
+  return self->AddData(ptr_to_local(&data));
+}
+
+size_t v8__SnapshotCreator__AddData_to_context(v8::SnapshotCreator* self,
+                                              const v8::Context& context,
+                                              const v8::Data& data) {
+  return self->AddData(ptr_to_local(&context), ptr_to_local(&data));
+}
+
 v8::StartupData v8__SnapshotCreator__CreateBlob(
     v8::SnapshotCreator* self,
     v8::SnapshotCreator::FunctionCodeHandling function_code_handling) {
@@ -1418,6 +2189,28 @@ v8::Platform* v8__platform__NewDefaultPlatform() {
   return v8::platform::NewDefaultPlatform().release();
 }
 
+v8::Platform* v8__platform__NewDefaultPlatform__with(int thread_pool_size,
+                                                     bool idle_task_support) {
+  return v8::platform::NewDefaultPlatform(
+             thread_pool_size,
+             idle_task_support ? v8::platform::IdleTaskSupport::kEnabled
+                               : v8::platform::IdleTaskSupport::kDisabled)
+      .release();
+}
+
+bool v8__platform__PumpMessageLoop(v8::Platform* platform,
+                                   v8::Isolate* isolate, bool wait_for_work) {
+  return v8::platform::PumpMessageLoop(
+      platform, isolate,
+      wait_for_work ? v8::platform::MessageLoopBehavior::kWaitForWork
+                    : v8::platform::MessageLoopBehavior::kDoNotWait);
+}
+
+void v8__platform__RunIdleTasks(v8::Platform* platform, v8::Isolate* isolate,
+                                double idle_time_in_seconds) {
+  v8::platform::RunIdleTasks(platform, isolate, idle_time_in_seconds);
+}
+
 void v8__Platform__DELETE(v8::Platform* self) { delete self; }
 void v8__Task__BASE__DELETE(v8::Task* self);
 void v8__Task__BASE__Run(v8::Task* self);
@@ -1472,6 +2265,16 @@ void v8_inspector__V8Inspector__contextCreated(
       ptr_to_local(&context), contextGroupId, humanReadableName));
 }
 
+void v8_inspector__V8Inspector__contextDestroyed(
+    v8_inspector::V8Inspector* self, const v8::Context& context) {
+  self->contextDestroyed(ptr_to_local(&context));
+}
+
+void v8_inspector__V8Inspector__resetContextGroup(
+    v8_inspector::V8Inspector* self, int contextGroupId) {
+  self->resetContextGroup(contextGroupId);
+}
+
 void v8_inspector__V8InspectorSession__DELETE(
     v8_inspector::V8InspectorSession* self) {
   delete self;
@@ -1654,6 +2457,11 @@ int v8__Module__GetIdentityHash(const v8::Module& self) {
   return self.GetIdentityHash();
 }
 
+const v8::UnboundModuleScript* v8__Module__GetUnboundModuleScript(
+    const v8::Module& self) {
+  return local_to_ptr(ptr_to_local(&self)->GetUnboundModuleScript());
+}
+
 MaybeBool v8__Module__InstantiateModule(const v8::Module& self,
                                         const v8::Context& context,
                                         v8::Module::ResolveCallback cb) {
@@ -1701,6 +2509,165 @@ void v8__HeapProfiler__TakeHeapSnapshot(v8::Isolate* isolate,
   const_cast<v8::HeapSnapshot*>(snapshot)->Delete();
 }
 
+bool v8__HeapProfiler__StartSamplingHeapProfiler(v8::Isolate* isolate,
+                                                 uint64_t sample_interval,
+                                                 int stack_depth) {
+  return isolate->GetHeapProfiler()->StartSamplingHeapProfiler(
+      sample_interval, stack_depth);
+}
+
+void v8__HeapProfiler__StopSamplingHeapProfiler(v8::Isolate* isolate) {
+  isolate->GetHeapProfiler()->StopSamplingHeapProfiler();
+}
+
+v8::AllocationProfile* v8__HeapProfiler__GetAllocationProfile(
+    v8::Isolate* isolate) {
+  return isolate->GetHeapProfiler()->GetAllocationProfile();
+}
+
+void v8__AllocationProfile__DELETE(v8::AllocationProfile* self) {
+  delete self;
+}
+
+const v8::AllocationProfile::Node* v8__AllocationProfile__GetRootNode(
+    v8::AllocationProfile* self) {
+  return self->GetRootNode();
+}
+
+const v8::String* v8__AllocationProfile__Node__GetName(
+    const v8::AllocationProfile::Node& self) {
+  return local_to_ptr(self.name);
+}
+
+const v8::String* v8__AllocationProfile__Node__GetScriptName(
+    const v8::AllocationProfile::Node& self) {
+  return local_to_ptr(self.script_name);
+}
+
+int v8__AllocationProfile__Node__GetScriptId(
+    const v8::AllocationProfile::Node& self) {
+  return self.script_id;
+}
+
+int v8__AllocationProfile__Node__GetStartPosition(
+    const v8::AllocationProfile::Node& self) {
+  return self.start_position;
+}
+
+int v8__AllocationProfile__Node__GetLineNumber(
+    const v8::AllocationProfile::Node& self) {
+  return self.line_number;
+}
+
+int v8__AllocationProfile__Node__GetColumnNumber(
+    const v8::AllocationProfile::Node& self) {
+  return self.column_number;
+}
+
+uint32_t v8__AllocationProfile__Node__GetNodeId(
+    const v8::AllocationProfile::Node& self) {
+  return self.node_id;
+}
+
+size_t v8__AllocationProfile__Node__GetChildrenCount(
+    const v8::AllocationProfile::Node& self) {
+  return self.children.size();
+}
+
+const v8::AllocationProfile::Node* v8__AllocationProfile__Node__GetChild(
+    const v8::AllocationProfile::Node& self, size_t index) {
+  return self.children[index];
+}
+
+size_t v8__AllocationProfile__Node__GetAllocationsCount(
+    const v8::AllocationProfile::Node& self) {
+  return self.allocations.size();
+}
+
+const v8::AllocationProfile::Allocation*
+v8__AllocationProfile__Node__GetAllocation(
+    const v8::AllocationProfile::Node& self, size_t index) {
+  return &self.allocations[index];
+}
+
+v8::CpuProfiler* v8__CpuProfiler__New(v8::Isolate* isolate) {
+  return v8::CpuProfiler::New(isolate);
+}
+
+void v8__CpuProfiler__Dispose(v8::CpuProfiler* self) { self->Dispose(); }
+
+void v8__CpuProfiler__SetSamplingInterval(v8::CpuProfiler* self, int us) {
+  self->SetSamplingInterval(us);
+}
+
+void v8__CpuProfiler__StartProfiling(v8::CpuProfiler* self,
+                                     const v8::String& title) {
+  self->StartProfiling(ptr_to_local(&title), true);
+}
+
+v8::CpuProfile* v8__CpuProfiler__StopProfiling(v8::CpuProfiler* self,
+                                               const v8::String& title) {
+  return self->StopProfiling(ptr_to_local(&title));
+}
+
+void v8__CpuProfile__Delete(v8::CpuProfile* self) { self->Delete(); }
+
+const v8::CpuProfileNode* v8__CpuProfile__GetTopDownRoot(
+    const v8::CpuProfile& self) {
+  return self.GetTopDownRoot();
+}
+
+int v8__CpuProfile__GetSamplesCount(const v8::CpuProfile& self) {
+  return self.GetSamplesCount();
+}
+
+int64_t v8__CpuProfile__GetStartTime(const v8::CpuProfile& self) {
+  return self.GetStartTime();
+}
+
+int64_t v8__CpuProfile__GetEndTime(const v8::CpuProfile& self) {
+  return self.GetEndTime();
+}
+
+const char* v8__CpuProfileNode__GetFunctionNameStr(
+    const v8::CpuProfileNode& self) {
+  return self.GetFunctionNameStr();
+}
+
+const char* v8__CpuProfileNode__GetScriptResourceNameStr(
+    const v8::CpuProfileNode& self) {
+  return self.GetScriptResourceNameStr();
+}
+
+int v8__CpuProfileNode__GetScriptId(const v8::CpuProfileNode& self) {
+  return self.GetScriptId();
+}
+
+int v8__CpuProfileNode__GetLineNumber(const v8::CpuProfileNode& self) {
+  return self.GetLineNumber();
+}
+
+int v8__CpuProfileNode__GetColumnNumber(const v8::CpuProfileNode& self) {
+  return self.GetColumnNumber();
+}
+
+unsigned v8__CpuProfileNode__GetHitCount(const v8::CpuProfileNode& self) {
+  return self.GetHitCount();
+}
+
+unsigned v8__CpuProfileNode__GetNodeId(const v8::CpuProfileNode& self) {
+  return self.GetNodeId();
+}
+
+int v8__CpuProfileNode__GetChildrenCount(const v8::CpuProfileNode& self) {
+  return self.GetChildrenCount();
+}
+
+const v8::CpuProfileNode* v8__CpuProfileNode__GetChild(
+    const v8::CpuProfileNode& self, int index) {
+  return self.GetChild(index);
+}
+
 // This is necessary for v8__internal__GetIsolateFromHeapObject() to be
 // reliable enough for our purposes.
 #if !(defined V8_SHARED_RO_HEAP or defined V8_COMPRESS_POINTERS)
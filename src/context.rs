@@ -1,42 +1,182 @@
 // Copyright 2019-2020 the Deno authors. All rights reserved. MIT license.
 use crate::isolate::Isolate;
+use crate::support::MapFnTo;
 use crate::Context;
+use crate::Data;
+use crate::DeserializeInternalFieldsCallback;
 use crate::Local;
+use crate::MicrotaskQueue;
 use crate::Object;
 use crate::ObjectTemplate;
+use crate::String;
 use crate::ToLocal;
 use crate::Value;
 use std::ptr::null;
+use std::ptr::null_mut;
 
 extern "C" {
   fn v8__Context__New(
     isolate: *mut Isolate,
     templ: *const ObjectTemplate,
     global_object: *const Value,
+    microtask_queue: *mut MicrotaskQueue,
+    deserializer: Option<DeserializeInternalFieldsCallback>,
   ) -> *const Context;
+  fn v8__Context__FromSnapshot(
+    isolate: *mut Isolate,
+    context_snapshot_index: usize,
+  ) -> *const Context;
+  fn v8__Context__FromSnapshot__with_deserializer(
+    isolate: *mut Isolate,
+    context_snapshot_index: usize,
+    deserializer: DeserializeInternalFieldsCallback,
+  ) -> *const Context;
+  fn v8__Context__GetDataFromSnapshotOnce(
+    this: *const Context,
+    index: usize,
+  ) -> *const Data;
   fn v8__Context__Enter(this: *const Context);
   fn v8__Context__Exit(this: *const Context);
   fn v8__Context__Global(this: *const Context) -> *const Object;
+  fn v8__Context__DetachGlobal(this: *const Context);
+  fn v8__Context__AllowCodeGenerationFromStrings(
+    this: *const Context,
+    allow: bool,
+  );
+  fn v8__Context__IsCodeGenerationFromStringsAllowed(
+    this: *const Context,
+  ) -> bool;
+  fn v8__Context__SetErrorMessageForCodeGenerationFromStrings(
+    this: *const Context,
+    message: *const String,
+  );
+}
+
+/// Options for `Context::new_with_options()`. Options that are not set use
+/// the same defaults as `Context::new()`.
+#[derive(Default)]
+pub struct ContextOptions<'a> {
+  /// The template from which the global object of the new context is created.
+  pub global_template: Option<Local<'a, ObjectTemplate>>,
+  /// A global proxy object that was previously detached from another context
+  /// with `Context::detach_global()`, to be reused by the new context.
+  pub global_object: Option<Local<'a, Value>>,
+  /// The MicrotaskQueue to use instead of the isolate's default one. See
+  /// `Context::new_with_options()` for how long it must be kept alive.
+  pub microtask_queue: Option<&'a mut MicrotaskQueue>,
+  /// The callback that restores the internal fields of objects when the
+  /// context is deserialized from the default context of a snapshot. It
+  /// should match the serializer that was passed to
+  /// `SnapshotCreator::set_default_context_with_serializer()`.
+  pub internal_fields_deserializer:
+    Option<DeserializeInternalFieldsCallback<'a>>,
 }
 
 impl Context {
   /// Creates a new context.
   pub fn new<'sc>(scope: &mut impl ToLocal<'sc>) -> Local<'sc, Context> {
     // TODO: optional arguments;
-    let ptr = unsafe { v8__Context__New(scope.isolate(), null(), null()) };
+    let ptr = unsafe {
+      v8__Context__New(scope.isolate(), null(), null(), null_mut(), None)
+    };
     unsafe { scope.to_local(ptr) }.unwrap()
   }
 
+  /// Creates a new context that uses the given MicrotaskQueue instead of the
+  /// isolate's default one.
+  ///
+  /// # Safety
+  ///
+  /// The context keeps a pointer to the MicrotaskQueue. The queue must
+  /// therefore outlive every use of the context: once it has been dropped, the
+  /// context must not be entered and no code may run in it.
+  pub unsafe fn new_with_microtask_queue<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+    microtask_queue: &mut MicrotaskQueue,
+  ) -> Local<'sc, Context> {
+    let ptr =
+      v8__Context__New(scope.isolate(), null(), null(), microtask_queue, None);
+    scope.to_local(ptr).unwrap()
+  }
+
   /// Creates a new context using the object template as the template for
   /// the global object.
   pub fn new_from_template<'sc>(
     scope: &mut impl ToLocal<'sc>,
     templ: Local<ObjectTemplate>,
   ) -> Local<'sc, Context> {
-    let ptr = unsafe { v8__Context__New(scope.isolate(), &*templ, null()) };
+    let ptr = unsafe {
+      v8__Context__New(scope.isolate(), &*templ, null(), null_mut(), None)
+    };
     unsafe { scope.to_local(ptr) }.unwrap()
   }
 
+  /// Creates a new context, allowing all of the parameters of `v8::Context::New`
+  /// that this crate supports to be specified.
+  ///
+  /// # Safety
+  ///
+  /// If `options.microtask_queue` is set, the context keeps a pointer to the
+  /// MicrotaskQueue. The queue must therefore outlive every use of the
+  /// context: once it has been dropped, the context must not be entered and
+  /// no code may run in it.
+  pub unsafe fn new_with_options<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+    options: ContextOptions,
+  ) -> Local<'sc, Context> {
+    let ptr = v8__Context__New(
+      scope.isolate(),
+      options
+        .global_template
+        .map(|t| &*t as *const _)
+        .unwrap_or(null()),
+      options
+        .global_object
+        .map(|g| &*g as *const _)
+        .unwrap_or(null()),
+      options
+        .microtask_queue
+        .map(|q| q as *mut _)
+        .unwrap_or(null_mut()),
+      options.internal_fields_deserializer,
+    );
+    scope.to_local(ptr).unwrap()
+  }
+
+  /// Create a new context from a (non-default) context snapshot. There
+  /// is no way to provide a global object template since we do not create
+  /// a new global object from template, but we can reuse a global object.
+  ///
+  /// The index is the value that was returned by
+  /// `SnapshotCreator::add_context()` when the snapshot was created.
+  pub fn from_snapshot<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+    context_snapshot_index: usize,
+  ) -> Option<Local<'sc, Context>> {
+    let ptr = unsafe {
+      v8__Context__FromSnapshot(scope.isolate(), context_snapshot_index)
+    };
+    unsafe { scope.to_local(ptr) }
+  }
+
+  /// Like `from_snapshot()`, but the internal fields of objects in the
+  /// context are restored with `deserializer`. The context must have been
+  /// added with `SnapshotCreator::add_context_with_serializer()`.
+  pub fn from_snapshot_with_deserializer<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+    context_snapshot_index: usize,
+    deserializer: impl for<'s> MapFnTo<DeserializeInternalFieldsCallback<'s>>,
+  ) -> Option<Local<'sc, Context>> {
+    let ptr = unsafe {
+      v8__Context__FromSnapshot__with_deserializer(
+        scope.isolate(),
+        context_snapshot_index,
+        deserializer.map_fn_to(),
+      )
+    };
+    unsafe { scope.to_local(ptr) }
+  }
+
   /// Returns the global proxy object.
   ///
   /// Global proxy object is a thin wrapper whose prototype points to actual
@@ -54,6 +194,58 @@ impl Context {
     unsafe { scope.to_local(v8__Context__Global(self)) }.unwrap()
   }
 
+  /// Detaches the global object from its context before the global object can
+  /// be reused to create a new context.
+  pub fn detach_global(&self) {
+    unsafe { v8__Context__DetachGlobal(self) }
+  }
+
+  /// Control whether code generation from strings is allowed. Calling this
+  /// method with false will disable 'eval' and the 'Function' constructor for
+  /// code running in this context. If 'eval' or the 'Function' constructor
+  /// are used an exception will be thrown.
+  pub fn allow_code_generation_from_strings(&self, allow: bool) {
+    unsafe { v8__Context__AllowCodeGenerationFromStrings(self, allow) }
+  }
+
+  /// Returns true if code generation from strings is allowed for the context.
+  /// For more details see `allow_code_generation_from_strings()`.
+  pub fn is_code_generation_from_strings_allowed(&self) -> bool {
+    unsafe { v8__Context__IsCodeGenerationFromStringsAllowed(self) }
+  }
+
+  /// Sets the error description for the exception that is thrown when
+  /// code generation from strings is not allowed and 'eval' or the 'Function'
+  /// constructor are called.
+  pub fn set_error_message_for_code_gen_from_strings(
+    &self,
+    message: Local<String>,
+  ) {
+    unsafe {
+      v8__Context__SetErrorMessageForCodeGenerationFromStrings(self, &*message)
+    }
+  }
+
+  /// Return data that was previously attached to the context snapshot via
+  /// `SnapshotCreator::add_context_data()`, or `None` if it has already been
+  /// retrieved or if the index is invalid. The data is cleared after the first
+  /// retrieval.
+  ///
+  /// This function is unsafe because the cast to `T` is unchecked; `T` must be
+  /// the type of the data that was attached.
+  pub unsafe fn get_data_from_snapshot_once<'sc, T>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+    index: usize,
+  ) -> Option<Local<'sc, T>>
+  where
+    Local<'sc, Data>: From<Local<'sc, T>>,
+  {
+    let data =
+      scope.to_local(v8__Context__GetDataFromSnapshotOnce(self, index));
+    data.map(|data| Local::cast(data))
+  }
+
   /// Enter this context.  After entering a context, all code compiled
   /// and run is compiled and run in this context.  If another context
   /// is already entered, this old context is saved so it can be
@@ -0,0 +1,203 @@
+// Copyright 2019-2020 the Deno authors. All rights reserved. MIT license.
+use std::borrow::Cow;
+use std::convert::TryInto;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+use crate::isolate::Isolate;
+use crate::support::int;
+use crate::support::Opaque;
+use crate::support::UniqueRef;
+use crate::Local;
+use crate::String;
+
+extern "C" {
+  fn v8__CpuProfiler__New(isolate: *mut Isolate) -> *mut CpuProfiler;
+  fn v8__CpuProfiler__Dispose(this: *mut CpuProfiler);
+  fn v8__CpuProfiler__SetSamplingInterval(this: *mut CpuProfiler, us: int);
+  fn v8__CpuProfiler__StartProfiling(
+    this: *mut CpuProfiler,
+    title: *const String,
+  );
+  fn v8__CpuProfiler__StopProfiling(
+    this: *mut CpuProfiler,
+    title: *const String,
+  ) -> *mut CpuProfile;
+
+  fn v8__CpuProfile__Delete(this: *mut CpuProfile);
+  fn v8__CpuProfile__GetTopDownRoot(
+    this: *const CpuProfile,
+  ) -> *const CpuProfileNode;
+  fn v8__CpuProfile__GetSamplesCount(this: *const CpuProfile) -> int;
+  fn v8__CpuProfile__GetStartTime(this: *const CpuProfile) -> i64;
+  fn v8__CpuProfile__GetEndTime(this: *const CpuProfile) -> i64;
+
+  fn v8__CpuProfileNode__GetFunctionNameStr(
+    this: *const CpuProfileNode,
+  ) -> *const c_char;
+  fn v8__CpuProfileNode__GetScriptResourceNameStr(
+    this: *const CpuProfileNode,
+  ) -> *const c_char;
+  fn v8__CpuProfileNode__GetScriptId(this: *const CpuProfileNode) -> int;
+  fn v8__CpuProfileNode__GetLineNumber(this: *const CpuProfileNode) -> int;
+  fn v8__CpuProfileNode__GetColumnNumber(this: *const CpuProfileNode) -> int;
+  fn v8__CpuProfileNode__GetHitCount(this: *const CpuProfileNode) -> u32;
+  fn v8__CpuProfileNode__GetNodeId(this: *const CpuProfileNode) -> u32;
+  fn v8__CpuProfileNode__GetChildrenCount(this: *const CpuProfileNode) -> int;
+  fn v8__CpuProfileNode__GetChild(
+    this: *const CpuProfileNode,
+    index: int,
+  ) -> *const CpuProfileNode;
+}
+
+/// Interface for controlling CPU profiling. Instance of the profiler can be
+/// created using `CpuProfiler::new()`.
+#[repr(C)]
+pub struct CpuProfiler(Opaque);
+
+impl CpuProfiler {
+  /// Creates a new CPU profiler for the isolate.
+  pub fn new(isolate: &mut Isolate) -> UniqueRef<Self> {
+    unsafe { UniqueRef::from_raw(v8__CpuProfiler__New(isolate)) }
+  }
+
+  /// Changes default CPU profiler sampling interval to the specified number
+  /// of microseconds. Default interval is 1000us. This method must be called
+  /// when there are no profiles being recorded.
+  pub fn set_sampling_interval(&mut self, us: usize) {
+    let us = us.try_into().unwrap_or(int::max_value());
+    unsafe { v8__CpuProfiler__SetSamplingInterval(self, us) }
+  }
+
+  /// Starts collecting a CPU profile. Title may be an empty string. Several
+  /// profiles may be collected at once. Attempts to start collecting several
+  /// profiles with the same title are silently ignored. Individual samples are
+  /// always recorded, so that `CpuProfile::get_samples_count()` reflects the
+  /// number of samples taken.
+  pub fn start_profiling(&mut self, title: Local<String>) {
+    unsafe { v8__CpuProfiler__StartProfiling(self, &*title) }
+  }
+
+  /// Stops collecting the CPU profile with the given title and returns it.
+  /// Returns `None` if no profile with that title is being collected.
+  pub fn stop_profiling(
+    &mut self,
+    title: Local<String>,
+  ) -> Option<UniqueRef<CpuProfile>> {
+    let ptr = unsafe { v8__CpuProfiler__StopProfiling(self, &*title) };
+    if ptr.is_null() {
+      None
+    } else {
+      Some(unsafe { UniqueRef::from_raw(ptr) })
+    }
+  }
+}
+
+impl Drop for CpuProfiler {
+  fn drop(&mut self) {
+    unsafe { v8__CpuProfiler__Dispose(self) }
+  }
+}
+
+/// CpuProfile contains a CPU profile in a form of top-down call tree
+/// (from main() down to functions that do all the work).
+#[repr(C)]
+pub struct CpuProfile(Opaque);
+
+impl CpuProfile {
+  /// Returns the root node of the top down call tree.
+  pub fn get_top_down_root(&self) -> &CpuProfileNode {
+    unsafe { &*v8__CpuProfile__GetTopDownRoot(self) }
+  }
+
+  /// Returns number of samples recorded.
+  pub fn get_samples_count(&self) -> usize {
+    unsafe { v8__CpuProfile__GetSamplesCount(self) as usize }
+  }
+
+  /// Returns time when the profile recording was started (in microseconds)
+  /// since some unspecified starting point.
+  pub fn get_start_time(&self) -> i64 {
+    unsafe { v8__CpuProfile__GetStartTime(self) }
+  }
+
+  /// Returns time when the profile recording was stopped (in microseconds)
+  /// since some unspecified starting point.
+  pub fn get_end_time(&self) -> i64 {
+    unsafe { v8__CpuProfile__GetEndTime(self) }
+  }
+}
+
+impl Drop for CpuProfile {
+  fn drop(&mut self) {
+    unsafe { v8__CpuProfile__Delete(self) }
+  }
+}
+
+/// CpuProfileNode represents a node in a call graph.
+#[repr(C)]
+pub struct CpuProfileNode(Opaque);
+
+impl CpuProfileNode {
+  /// Returns function name (empty string for anonymous functions.)
+  pub fn get_function_name(&self) -> Cow<str> {
+    unsafe {
+      CStr::from_ptr(v8__CpuProfileNode__GetFunctionNameStr(self))
+        .to_string_lossy()
+    }
+  }
+
+  /// Returns resource name for script from where the function originates.
+  pub fn get_script_resource_name(&self) -> Cow<str> {
+    unsafe {
+      CStr::from_ptr(v8__CpuProfileNode__GetScriptResourceNameStr(self))
+        .to_string_lossy()
+    }
+  }
+
+  /// Returns id of the script where function is located.
+  pub fn get_script_id(&self) -> int {
+    unsafe { v8__CpuProfileNode__GetScriptId(self) }
+  }
+
+  /// Returns the number, 1-based, of the line where the function originates.
+  /// Returns `None` if no line number information is available.
+  pub fn get_line_number(&self) -> Option<usize> {
+    match unsafe { v8__CpuProfileNode__GetLineNumber(self) } {
+      0 => None,
+      n => Some(n as usize),
+    }
+  }
+
+  /// Returns 1-based number of the column where the function originates.
+  /// Returns `None` if no column number information is available.
+  pub fn get_column_number(&self) -> Option<usize> {
+    match unsafe { v8__CpuProfileNode__GetColumnNumber(self) } {
+      0 => None,
+      n => Some(n as usize),
+    }
+  }
+
+  /// Returns count of samples where the function was currently executing.
+  pub fn get_hit_count(&self) -> u32 {
+    unsafe { v8__CpuProfileNode__GetHitCount(self) }
+  }
+
+  /// Returns id of the node. The id is unique within the tree.
+  pub fn get_node_id(&self) -> u32 {
+    unsafe { v8__CpuProfileNode__GetNodeId(self) }
+  }
+
+  /// Returns child nodes count of the node.
+  pub fn get_children_count(&self) -> usize {
+    unsafe { v8__CpuProfileNode__GetChildrenCount(self) as usize }
+  }
+
+  /// Retrieves a child node by index.
+  pub fn get_child(&self, index: usize) -> Option<&CpuProfileNode> {
+    if index >= self.get_children_count() {
+      return None;
+    }
+    unsafe { Some(&*v8__CpuProfileNode__GetChild(self, index as int)) }
+  }
+}
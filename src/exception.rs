@@ -1,57 +1,16 @@
 #![allow(non_snake_case)]
 
+use std::convert::TryInto;
+
 use crate::isolate::Isolate;
-use crate::support::int;
-use crate::Context;
 use crate::Local;
 use crate::Message;
-use crate::StackFrame;
-use crate::StackTrace;
+use crate::Object;
 use crate::String;
 use crate::ToLocal;
 use crate::Value;
 
 extern "C" {
-  fn v8__Message__Get(this: *const Message) -> *const String;
-  fn v8__Message__GetSourceLine(
-    this: *const Message,
-    context: *const Context,
-  ) -> *const String;
-  fn v8__Message__GetScriptResourceName(this: *const Message) -> *const Value;
-  fn v8__Message__GetLineNumber(
-    this: *const Message,
-    context: *const Context,
-  ) -> int;
-  fn v8__Message__GetStartPosition(this: *const Message) -> int;
-  fn v8__Message__GetEndPosition(this: *const Message) -> int;
-  fn v8__Message__GetWasmFunctionIndex(this: *const Message) -> int;
-  fn v8__Message__ErrorLevel(this: *const Message) -> int;
-  fn v8__Message__GetStartColumn(this: *const Message) -> int;
-  fn v8__Message__GetEndColumn(this: *const Message) -> int;
-  fn v8__Message__IsSharedCrossOrigin(this: *const Message) -> bool;
-  fn v8__Message__IsOpaque(this: *const Message) -> bool;
-  fn v8__Message__GetStackTrace(this: *const Message) -> *const StackTrace;
-
-  fn v8__StackTrace__GetFrameCount(this: *const StackTrace) -> int;
-  fn v8__StackTrace__GetFrame(
-    this: *const StackTrace,
-    isolate: *mut Isolate,
-    index: u32,
-  ) -> *const StackFrame;
-
-  fn v8__StackFrame__GetLineNumber(this: *const StackFrame) -> int;
-  fn v8__StackFrame__GetColumn(this: *const StackFrame) -> int;
-  fn v8__StackFrame__GetScriptId(this: *const StackFrame) -> int;
-  fn v8__StackFrame__GetScriptName(this: *const StackFrame) -> *const String;
-  fn v8__StackFrame__GetScriptNameOrSourceURL(
-    this: *const StackFrame,
-  ) -> *const String;
-  fn v8__StackFrame__GetFunctionName(this: *const StackFrame) -> *const String;
-  fn v8__StackFrame__IsEval(this: *const StackFrame) -> bool;
-  fn v8__StackFrame__IsConstructor(this: *const StackFrame) -> bool;
-  fn v8__StackFrame__IsWasm(this: *const StackFrame) -> bool;
-  fn v8__StackFrame__IsUserJavaScript(this: *const StackFrame) -> bool;
-
   fn v8__Exception__Error(message: *const String) -> *const Value;
   fn v8__Exception__RangeError(message: *const String) -> *const Value;
   fn v8__Exception__ReferenceError(message: *const String) -> *const Value;
@@ -66,189 +25,15 @@ extern "C" {
     -> *const StackTrace;
 }
 
-impl StackTrace {
-  /// Returns the number of StackFrames.
-  pub fn get_frame_count(&self) -> usize {
-    unsafe { v8__StackTrace__GetFrameCount(self) as usize }
-  }
-
-  /// Returns a StackFrame at a particular index.
-  pub fn get_frame<'sc>(
-    &self,
-    scope: &mut impl ToLocal<'sc>,
-    index: usize,
-  ) -> Option<Local<'sc, StackFrame>> {
-    let isolate = scope.isolate();
-    unsafe {
-      let ptr = v8__StackTrace__GetFrame(self, isolate, index as u32);
-      scope.to_local(ptr)
-    }
-  }
-}
-
-impl StackFrame {
-  /// Returns the number, 1-based, of the line for the associate function call.
-  /// This method will return Message::kNoLineNumberInfo if it is unable to
-  /// retrieve the line number, or if kLineNumber was not passed as an option
-  /// when capturing the StackTrace.
-  pub fn get_line_number(&self) -> usize {
-    unsafe { v8__StackFrame__GetLineNumber(self) as usize }
-  }
-
-  /// Returns the 1-based column offset on the line for the associated function
-  /// call.
-  /// This method will return Message::kNoColumnInfo if it is unable to retrieve
-  /// the column number, or if kColumnOffset was not passed as an option when
-  /// capturing the StackTrace.
-  pub fn get_column(&self) -> usize {
-    unsafe { v8__StackFrame__GetColumn(self) as usize }
-  }
-
-  /// Returns the id of the script for the function for this StackFrame.
-  /// This method will return Message::kNoScriptIdInfo if it is unable to
-  /// retrieve the script id, or if kScriptId was not passed as an option when
-  /// capturing the StackTrace.
-  pub fn get_script_id(&self) -> usize {
-    unsafe { v8__StackFrame__GetScriptId(self) as usize }
-  }
-
-  /// Returns the name of the resource that contains the script for the
-  /// function for this StackFrame.
-  pub fn get_script_name<'sc>(
-    &self,
-    scope: &mut impl ToLocal<'sc>,
-  ) -> Option<Local<'sc, String>> {
-    unsafe { scope.to_local(v8__StackFrame__GetScriptName(self)) }
-  }
-
-  /// Returns the name of the resource that contains the script for the
-  /// function for this StackFrame or sourceURL value if the script name
-  /// is undefined and its source ends with //# sourceURL=... string or
-  /// deprecated //@ sourceURL=... string.
-  pub fn get_script_name_or_source_url<'sc>(
-    &self,
-    scope: &mut impl ToLocal<'sc>,
-  ) -> Option<Local<'sc, String>> {
-    unsafe { scope.to_local(v8__StackFrame__GetScriptNameOrSourceURL(self)) }
-  }
-
-  /// Returns the name of the function associated with this stack frame.
-  pub fn get_function_name<'sc>(
-    &self,
-    scope: &mut impl ToLocal<'sc>,
-  ) -> Option<Local<'sc, String>> {
-    unsafe { scope.to_local(v8__StackFrame__GetFunctionName(self)) }
-  }
-
-  /// Returns whether or not the associated function is compiled via a call to
-  /// eval().
-  pub fn is_eval(&self) -> bool {
-    unsafe { v8__StackFrame__IsEval(self) }
-  }
-
-  /// Returns whether or not the associated function is called as a
-  /// constructor via "new".
-  pub fn is_constructor(&self) -> bool {
-    unsafe { v8__StackFrame__IsConstructor(self) }
-  }
-
-  /// Returns whether or not the associated functions is defined in wasm.
-  pub fn is_wasm(&self) -> bool {
-    unsafe { v8__StackFrame__IsWasm(self) }
-  }
-
-  /// Returns whether or not the associated function is defined by the user.
-  pub fn is_user_javascript(&self) -> bool {
-    unsafe { v8__StackFrame__IsUserJavaScript(self) }
-  }
-}
-
-impl Message {
-  pub fn get<'sc>(&self, scope: &mut impl ToLocal<'sc>) -> Local<'sc, String> {
-    unsafe { scope.to_local(v8__Message__Get(self)) }.unwrap()
-  }
-
-  /// Exception stack trace. By default stack traces are not captured for
-  /// uncaught exceptions. SetCaptureStackTraceForUncaughtExceptions allows
-  /// to change this option.
-  pub fn get_stack_trace<'sc>(
-    &self,
-    scope: &mut impl ToLocal<'sc>,
-  ) -> Option<Local<'sc, StackTrace>> {
-    unsafe { scope.to_local(v8__Message__GetStackTrace(self)) }
-  }
-
-  pub fn get_source_line<'s>(
-    &self,
-    scope: &mut impl ToLocal<'s>,
-    context: Local<Context>,
-  ) -> Option<Local<'s, String>> {
-    unsafe { scope.to_local(v8__Message__GetSourceLine(self, &*context)) }
-  }
-
-  /// Returns the resource name for the script from where the function causing
-  /// the error originates.
-  pub fn get_script_resource_name<'s>(
-    &self,
-    scope: &mut impl ToLocal<'s>,
-  ) -> Option<Local<'s, Value>> {
-    unsafe { scope.to_local(v8__Message__GetScriptResourceName(self)) }
-  }
-
-  /// Returns the number, 1-based, of the line where the error occurred.
-  pub fn get_line_number(&self, context: Local<Context>) -> Option<usize> {
-    let i = unsafe { v8__Message__GetLineNumber(self, &*context) };
-    if i < 0 {
-      None
-    } else {
-      Some(i as usize)
-    }
-  }
-
-  /// Returns the index within the script of the first character where
-  /// the error occurred.
-  pub fn get_start_position(&self) -> int {
-    unsafe { v8__Message__GetStartPosition(self) }
-  }
-
-  /// Returns the index within the script of the last character where
-  /// the error occurred.
-  pub fn get_end_position(&self) -> int {
-    unsafe { v8__Message__GetEndPosition(self) }
-  }
-
-  /// Returns the Wasm function index where the error occurred. Returns -1 if
-  /// message is not from a Wasm script.
-  pub fn get_wasm_function_index(&self) -> int {
-    unsafe { v8__Message__GetWasmFunctionIndex(self) }
-  }
-
-  /// Returns the error level of the message.
-  pub fn error_level(&self) -> int {
-    unsafe { v8__Message__ErrorLevel(self) }
-  }
-
-  /// Returns the index within the line of the first character where
-  /// the error occurred.
-  pub fn get_start_column(&self) -> usize {
-    unsafe { v8__Message__GetStartColumn(self) as usize }
-  }
-
-  /// Returns the index within the line of the last character where
-  /// the error occurred.
-  pub fn get_end_column(&self) -> usize {
-    unsafe { v8__Message__GetEndColumn(self) as usize }
-  }
-
-  /// Passes on the value set by the embedder when it fed the script from which
-  /// this Message was generated to V8.
-  pub fn is_shared_cross_origin(&self) -> bool {
-    unsafe { v8__Message__IsSharedCrossOrigin(self) }
-  }
-
-  pub fn is_opaque(&self) -> bool {
-    unsafe { v8__Message__IsOpaque(self) }
-  }
+/// The kind of a native error, as determined by `Exception::get_error_type()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NativeErrorKind {
+  Error,
+  EvalError,
+  RangeError,
+  ReferenceError,
+  SyntaxError,
+  TypeError,
 }
 
 /// Create new error objects by calling the corresponding error object
@@ -323,4 +108,40 @@ impl Exception {
   ) -> Option<Local<'sc, StackTrace>> {
     unsafe { scope.to_local(v8__Exception__GetStackTrace(&*exception)) }
   }
+
+  /// Returns the kind of a native error, or `None` if `exception` isn't a
+  /// native error. The kind is determined by the nearest constructor on the
+  /// prototype chain whose name is one of the built-in error names, so
+  /// instances of user defined subclasses of e.g. `TypeError` are classified
+  /// as `TypeError`.
+  ///
+  /// Constructors are matched by name, not by identity. A native error whose
+  /// prototype chain contains a user defined `class TypeError {}`, e.g. after
+  /// `Object.setPrototypeOf()`, is therefore also reported as `TypeError`.
+  // Convenience function not present in the original V8 API.
+  pub fn get_error_type<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+    exception: Local<Value>,
+  ) -> Option<NativeErrorKind> {
+    if !exception.is_native_error() {
+      return None;
+    }
+    let mut object: Local<Object> = exception.try_into().ok()?;
+    loop {
+      let name = object.get_constructor_name(scope);
+      let kind = match name.to_rust_string_lossy(scope).as_str() {
+        "Error" => NativeErrorKind::Error,
+        "EvalError" => NativeErrorKind::EvalError,
+        "RangeError" => NativeErrorKind::RangeError,
+        "ReferenceError" => NativeErrorKind::ReferenceError,
+        "SyntaxError" => NativeErrorKind::SyntaxError,
+        "TypeError" => NativeErrorKind::TypeError,
+        _ => {
+          object = object.get_prototype(scope)?.try_into().ok()?;
+          continue;
+        }
+      };
+      return Some(kind);
+    }
+  }
 }
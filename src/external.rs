@@ -0,0 +1,31 @@
+use std::ffi::c_void;
+
+use crate::isolate::Isolate;
+use crate::External;
+use crate::Local;
+use crate::ToLocal;
+
+extern "C" {
+  fn v8__External__New(
+    isolate: *mut Isolate,
+    value: *mut c_void,
+  ) -> *const External;
+  fn v8__External__Value(this: *const External) -> *mut c_void;
+}
+
+impl External {
+  pub fn new<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+    value: *mut c_void,
+  ) -> Local<'sc, External> {
+    unsafe {
+      let ptr = v8__External__New(scope.isolate(), value);
+      scope.to_local(ptr)
+    }
+    .unwrap()
+  }
+
+  pub fn value(&self) -> *mut c_void {
+    unsafe { v8__External__Value(self) }
+  }
+}
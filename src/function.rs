@@ -1,7 +1,10 @@
 use std::convert::TryFrom;
 use std::marker::PhantomData;
+use std::ptr::NonNull;
 
+use crate::isolate::Isolate;
 use crate::scope::ScopeDefinition;
+use crate::scope_traits::internal::GetRawIsolate;
 use crate::support::MapFnFrom;
 use crate::support::MapFnTo;
 use crate::support::ToCFn;
@@ -34,6 +37,9 @@ extern "C" {
     argc: int,
     argv: *const *const Value,
   ) -> *const Value;
+  fn v8__Function__GetInferredName(this: *const Function) -> *const Value;
+  fn v8__Function__GetDebugName(this: *const Function) -> *const Value;
+  fn v8__Function__GetBoundFunction(this: *const Function) -> *const Value;
 
   fn v8__FunctionCallbackInfo__GetReturnValue(
     info: *const FunctionCallbackInfo,
@@ -50,6 +56,9 @@ extern "C" {
   fn v8__FunctionCallbackInfo__Data(
     this: *const FunctionCallbackInfo,
   ) -> *const Value;
+  fn v8__FunctionCallbackInfo__NewTarget(
+    this: *const FunctionCallbackInfo,
+  ) -> *const Value;
 
   fn v8__PropertyCallbackInfo__GetReturnValue(
     this: *const PropertyCallbackInfo,
@@ -57,8 +66,24 @@ extern "C" {
   fn v8__PropertyCallbackInfo__This(
     this: *const PropertyCallbackInfo,
   ) -> *const Object;
+  fn v8__PropertyCallbackInfo__Holder(
+    this: *const PropertyCallbackInfo,
+  ) -> *const Object;
+  fn v8__PropertyCallbackInfo__Data(
+    this: *const PropertyCallbackInfo,
+  ) -> *const Value;
+  fn v8__PropertyCallbackInfo__ShouldThrowOnError(
+    this: *const PropertyCallbackInfo,
+  ) -> bool;
 
   fn v8__ReturnValue__Set(this: *mut ReturnValue, value: *const Value);
+  fn v8__ReturnValue__Set__Bool(this: *mut ReturnValue, value: bool);
+  fn v8__ReturnValue__Set__Int32(this: *mut ReturnValue, value: i32);
+  fn v8__ReturnValue__Set__Uint32(this: *mut ReturnValue, value: u32);
+  fn v8__ReturnValue__Set__Double(this: *mut ReturnValue, value: f64);
+  fn v8__ReturnValue__SetNull(this: *mut ReturnValue);
+  fn v8__ReturnValue__SetUndefined(this: *mut ReturnValue);
+  fn v8__ReturnValue__SetEmptyString(this: *mut ReturnValue);
   fn v8__ReturnValue__Get(this: *const ReturnValue) -> *const Value;
 }
 
@@ -89,6 +114,42 @@ impl<'cb> ReturnValue<'cb> {
     unsafe { v8__ReturnValue__Set(&mut *self, &*value) }
   }
 
+  /// Sets the return value to a boolean, without the need to create a
+  /// `Local<Boolean>` first.
+  pub fn set_bool(&mut self, value: bool) {
+    unsafe { v8__ReturnValue__Set__Bool(&mut *self, value) }
+  }
+
+  /// Sets the return value to a 32-bit signed integer.
+  pub fn set_int32(&mut self, value: i32) {
+    unsafe { v8__ReturnValue__Set__Int32(&mut *self, value) }
+  }
+
+  /// Sets the return value to a 32-bit unsigned integer.
+  pub fn set_uint32(&mut self, value: u32) {
+    unsafe { v8__ReturnValue__Set__Uint32(&mut *self, value) }
+  }
+
+  /// Sets the return value to a number.
+  pub fn set_double(&mut self, value: f64) {
+    unsafe { v8__ReturnValue__Set__Double(&mut *self, value) }
+  }
+
+  /// Sets the return value to `null`.
+  pub fn set_null(&mut self) {
+    unsafe { v8__ReturnValue__SetNull(&mut *self) }
+  }
+
+  /// Sets the return value to `undefined`.
+  pub fn set_undefined(&mut self) {
+    unsafe { v8__ReturnValue__SetUndefined(&mut *self) }
+  }
+
+  /// Sets the return value to the empty string.
+  pub fn set_empty_string(&mut self) {
+    unsafe { v8__ReturnValue__SetEmptyString(&mut *self) }
+  }
+
   /// Getter. Creates a new Local<> so it comes with a certain performance
   /// hit. If the ReturnValue was not yet set, this will return the undefined
   /// value.
@@ -157,6 +218,22 @@ impl<'s> FunctionCallbackArguments<'s> {
     unsafe { Local::from_raw(v8__FunctionCallbackInfo__Data(self.info)) }
   }
 
+  /// For construct calls, this returns the "new.target" value. Returns
+  /// `undefined` when the function was called without `new`.
+  pub fn new_target(&self) -> Local<'s, Value> {
+    unsafe {
+      Local::from_raw(v8__FunctionCallbackInfo__NewTarget(self.info)).unwrap()
+    }
+  }
+
+  /// Returns the isolate of the function call.
+  ///
+  /// The returned pointer must not be dereferenced while the callback scope,
+  /// or any other `&mut Isolate` reference to the same isolate, is alive.
+  pub fn get_isolate(&self) -> NonNull<Isolate> {
+    NonNull::new(unsafe { (*self.info).get_raw_isolate() }).unwrap()
+  }
+
   /// The number of available arguments.
   pub fn length(&self) -> int {
     unsafe {
@@ -233,6 +310,40 @@ impl<'s> PropertyCallbackArguments<'s> {
       Local::from_raw(v8__PropertyCallbackInfo__This(self.info)).unwrap()
     }
   }
+
+  /// Returns the object in the prototype chain of the receiver that has the
+  /// interceptor or accessor. Suppose you have `x` and its prototype is `y`,
+  /// and `y` has an interceptor. Then `this()` is `x` and `holder()` is `y`.
+  pub fn holder(&self) -> Local<'s, Object> {
+    unsafe {
+      Local::from_raw(v8__PropertyCallbackInfo__Holder(self.info)).unwrap()
+    }
+  }
+
+  /// Returns the data set in the configuration, i.e., in
+  /// `NamedPropertyHandlerConfiguration` or `set_accessor_with_data()`.
+  pub fn data(&self) -> Local<'s, Value> {
+    unsafe {
+      Local::from_raw(v8__PropertyCallbackInfo__Data(self.info)).unwrap()
+    }
+  }
+
+  /// Returns `true` if the intercepted function should throw if an error
+  /// occurs. Usually, `true` corresponds to `'use strict'`.
+  ///
+  /// Always `false` when intercepting `Reflect.set()` independent of the
+  /// language mode.
+  pub fn should_throw_on_error(&self) -> bool {
+    unsafe { v8__PropertyCallbackInfo__ShouldThrowOnError(self.info) }
+  }
+
+  /// Returns the isolate of the property access.
+  ///
+  /// The returned pointer must not be dereferenced while the callback scope,
+  /// or any other `&mut Isolate` reference to the same isolate, is alive.
+  pub fn get_isolate(&self) -> NonNull<Isolate> {
+    NonNull::new(unsafe { (*self.info).get_raw_isolate() }).unwrap()
+  }
 }
 
 pub type FunctionCallback = extern "C" fn(*const FunctionCallbackInfo);
@@ -281,6 +392,150 @@ where
   }
 }
 
+/// AccessorNameSetterCallback is used as callback functions when setting a
+/// particular property. See `Object::set_accessor_with_configuration()`. The
+/// return value passed to the callback must not be used.
+pub type AccessorNameSetterCallback<'s> =
+  GenericNamedPropertySetterCallback<'s>;
+
+/// Interceptor for get requests on an object. Use `rv.set()` to set the
+/// return value of the intercepted get request. If the property does not
+/// exist the callback should not set the result and must not produce side
+/// effects.
+pub type GenericNamedPropertyGetterCallback<'s> =
+  AccessorNameGetterCallback<'s>;
+
+/// Interceptor for set requests on an object. Use `rv.set()` to indicate
+/// whether the request was intercepted or not. If the setter successfully
+/// intercepts the request, i.e., if the request should not be further
+/// executed, call `rv.set()` with the value that was set. If the setter did not
+/// intercept the request, i.e., if the request should be handled as if no
+/// interceptor is present, do not call `rv.set()` and do not produce side
+/// effects.
+pub type GenericNamedPropertySetterCallback<'s> =
+  extern "C" fn(Local<'s, Name>, Local<'s, Value>, *const PropertyCallbackInfo);
+
+impl<F> MapFnFrom<F> for GenericNamedPropertySetterCallback<'_>
+where
+  F: UnitType
+    + Fn(
+      PropertyCallbackScope,
+      Local<Name>,
+      Local<Value>,
+      PropertyCallbackArguments,
+      ReturnValue,
+    ),
+{
+  fn mapping() -> Self {
+    let f = |key: Local<Name>,
+             value: Local<Value>,
+             info: *const PropertyCallbackInfo| {
+      let scope: PropertyCallbackScope =
+        &mut crate::scope::Entered::new_root(info as *mut PropertyCallbackInfo);
+      let args = PropertyCallbackArguments::from_property_callback_info(info);
+      let rv = ReturnValue::from_property_callback_info(info);
+      (F::get())(scope, key, value, args, rv);
+    };
+    f.to_c_fn()
+  }
+}
+
+/// Intercepts all requests that query the attributes of the property, e.g.,
+/// getOwnPropertyDescriptor(), propertyIsEnumerable(), and defineProperty().
+/// Use `rv.set()` with an Integer holding the property attributes to indicate
+/// that the property exists.
+pub type GenericNamedPropertyQueryCallback<'s> = AccessorNameGetterCallback<'s>;
+
+/// Interceptor for delete requests on an object. Use `rv.set()` with a Boolean
+/// to indicate whether the request was intercepted or not. If the deleter
+/// successfully intercepts the request, i.e., if the request should not be
+/// further executed, call `rv.set()` with a boolean value. The value is used
+/// as the return value of `delete`.
+pub type GenericNamedPropertyDeleterCallback<'s> =
+  AccessorNameGetterCallback<'s>;
+
+/// Returns an array containing the names of the properties the named property
+/// getter intercepts. Use `rv.set()` with an Array to set the result.
+pub type GenericNamedPropertyEnumeratorCallback =
+  extern "C" fn(*const PropertyCallbackInfo);
+
+impl<F> MapFnFrom<F> for GenericNamedPropertyEnumeratorCallback
+where
+  F: UnitType
+    + Fn(PropertyCallbackScope, PropertyCallbackArguments, ReturnValue),
+{
+  fn mapping() -> Self {
+    let f = |info: *const PropertyCallbackInfo| {
+      let scope: PropertyCallbackScope =
+        &mut crate::scope::Entered::new_root(info as *mut PropertyCallbackInfo);
+      let args = PropertyCallbackArguments::from_property_callback_info(info);
+      let rv = ReturnValue::from_property_callback_info(info);
+      (F::get())(scope, args, rv);
+    };
+    f.to_c_fn()
+  }
+}
+
+/// See `GenericNamedPropertyGetterCallback`.
+pub type IndexedPropertyGetterCallback =
+  extern "C" fn(u32, *const PropertyCallbackInfo);
+
+impl<F> MapFnFrom<F> for IndexedPropertyGetterCallback
+where
+  F: UnitType
+    + Fn(PropertyCallbackScope, u32, PropertyCallbackArguments, ReturnValue),
+{
+  fn mapping() -> Self {
+    let f = |index: u32, info: *const PropertyCallbackInfo| {
+      let scope: PropertyCallbackScope =
+        &mut crate::scope::Entered::new_root(info as *mut PropertyCallbackInfo);
+      let args = PropertyCallbackArguments::from_property_callback_info(info);
+      let rv = ReturnValue::from_property_callback_info(info);
+      (F::get())(scope, index, args, rv);
+    };
+    f.to_c_fn()
+  }
+}
+
+/// See `GenericNamedPropertySetterCallback`.
+pub type IndexedPropertySetterCallback<'s> =
+  extern "C" fn(u32, Local<'s, Value>, *const PropertyCallbackInfo);
+
+impl<F> MapFnFrom<F> for IndexedPropertySetterCallback<'_>
+where
+  F: UnitType
+    + Fn(
+      PropertyCallbackScope,
+      u32,
+      Local<Value>,
+      PropertyCallbackArguments,
+      ReturnValue,
+    ),
+{
+  fn mapping() -> Self {
+    let f = |index: u32,
+             value: Local<Value>,
+             info: *const PropertyCallbackInfo| {
+      let scope: PropertyCallbackScope =
+        &mut crate::scope::Entered::new_root(info as *mut PropertyCallbackInfo);
+      let args = PropertyCallbackArguments::from_property_callback_info(info);
+      let rv = ReturnValue::from_property_callback_info(info);
+      (F::get())(scope, index, value, args, rv);
+    };
+    f.to_c_fn()
+  }
+}
+
+/// See `GenericNamedPropertyQueryCallback`.
+pub type IndexedPropertyQueryCallback = IndexedPropertyGetterCallback;
+
+/// See `GenericNamedPropertyDeleterCallback`.
+pub type IndexedPropertyDeleterCallback = IndexedPropertyGetterCallback;
+
+/// See `GenericNamedPropertyEnumeratorCallback`.
+pub type IndexedPropertyEnumeratorCallback =
+  GenericNamedPropertyEnumeratorCallback;
+
 impl Function {
   // TODO: add remaining arguments from C++
   /// Create a function in the current execution context
@@ -326,4 +581,33 @@ impl Function {
       scope.to_local(v8__Function__Call(self, &*context, &*recv, argc, argv))
     }
   }
+
+  /// Name inferred from variable or property assignment of this function.
+  /// Used to facilitate debugging and profiling of JavaScript code written
+  /// in an OO style, where many functions are anonymous but are assigned
+  /// to object properties.
+  pub fn get_inferred_name<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Local<'sc, Value> {
+    unsafe { scope.to_local(v8__Function__GetInferredName(self)) }.unwrap()
+  }
+
+  /// displayName if it is set, otherwise name if it is configured, otherwise
+  /// function name, otherwise inferred name.
+  pub fn get_debug_name<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Local<'sc, Value> {
+    unsafe { scope.to_local(v8__Function__GetDebugName(self)) }.unwrap()
+  }
+
+  /// Returns the target function of a bound function, or `undefined` if this
+  /// function is not bound.
+  pub fn get_bound_function<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Local<'sc, Value> {
+    unsafe { scope.to_local(v8__Function__GetBoundFunction(self)) }.unwrap()
+  }
 }
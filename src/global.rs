@@ -1,3 +1,5 @@
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::mem::transmute;
 use std::ptr::NonNull;
 
@@ -20,6 +22,12 @@ extern "C" {
     isolate: *mut Isolate,
     other: *const *const Data,
   );
+
+  fn v8__Global__SetWeak(this: *mut *const Data);
+
+  fn v8__Data__EQ(this: *const Data, other: *const Data) -> bool;
+
+  fn v8__Data__GetHash(this: *const Data) -> u32;
 }
 
 /// An object reference that is independent of any handle scope. Where
@@ -36,6 +44,8 @@ extern "C" {
 pub struct Global<T> {
   value: Option<NonNull<T>>,
   isolate_handle: Option<IsolateHandle>,
+  weak: bool,
+  hash: u32,
 }
 
 impl<T> Global<T> {
@@ -44,6 +54,8 @@ impl<T> Global<T> {
     Self {
       value: None,
       isolate_handle: None,
+      weak: false,
+      hash: 0,
     }
   }
 
@@ -60,6 +72,10 @@ impl<T> Global<T> {
       value: other_value
         .map(|v| unsafe { transmute(v8__Global__New(isolate, transmute(v))) }),
       isolate_handle: other_value.map(|_| isolate.thread_safe_handle()),
+      weak: false,
+      hash: other_value.map_or(0, |v| unsafe {
+        v8__Data__GetHash(v.as_ptr() as *const Data)
+      }),
     }
   }
 
@@ -105,6 +121,10 @@ impl<T> Global<T> {
       },
     }
     self.isolate_handle = other_value.map(|_| isolate.thread_safe_handle());
+    self.weak = false;
+    self.hash = other_value.map_or(0, |v| unsafe {
+      v8__Data__GetHash(v.as_ptr() as *const Data)
+    });
   }
 
   /// If non-empty, destroy the underlying storage cell
@@ -113,8 +133,30 @@ impl<T> Global<T> {
     self.set(scope, None);
   }
 
+  /// Turns this handle into a weak phantom handle. When the garbage collector
+  /// detects that the object is no longer reachable, the underlying storage
+  /// cell is destroyed and this Global becomes empty, i.e. `is_empty()` will
+  /// return true afterwards.
+  ///
+  /// # Safety
+  ///
+  /// This Global must not be moved for as long as it is weak, because V8
+  /// clears it by writing to its current memory location. Boxing it is the
+  /// easiest way to guarantee that.
+  pub unsafe fn set_weak(&mut self) {
+    if self.value.is_some() {
+      v8__Global__SetWeak(
+        &mut *(&mut self.value as *mut Option<NonNull<T>> as *mut *const Data),
+      );
+      self.weak = true;
+    }
+  }
+
   fn check_isolate(&self, isolate: &mut Isolate) {
     match self.value {
+      // A weak handle may have been cleared by the garbage collector, in
+      // which case the isolate handle is still set.
+      None if self.weak => {}
       None => assert!(self.isolate_handle.is_none()),
       Some(_) => assert_eq!(
         unsafe { self.isolate_handle.as_ref().unwrap().get_isolate_ptr() },
@@ -130,9 +172,38 @@ impl<T> Default for Global<T> {
   }
 }
 
+/// Two globals are equal if they are both empty, or if they refer to the same
+/// object. Note that this is object identity, not JavaScript value equality;
+/// use `Value::strict_equals()` or `Value::same_value()` for the latter.
+impl<T> PartialEq for Global<T> {
+  fn eq(&self, other: &Self) -> bool {
+    match (self.value, other.value) {
+      (None, None) => true,
+      (Some(a), Some(b)) => unsafe {
+        v8__Data__EQ(a.as_ptr() as *const Data, b.as_ptr() as *const Data)
+      },
+      _ => false,
+    }
+  }
+}
+
+impl<T> Eq for Global<T> {}
+
+/// Globals are hashed by a hash of the value they refer to, which is computed
+/// when the value is assigned, so hashing does not require a `HandleScope`.
+/// Objects are hashed by identity, see `Value::get_hash()`.
+impl<T> Hash for Global<T> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.value.map(|_| self.hash).hash(state)
+  }
+}
+
 impl<T> Drop for Global<T> {
   fn drop(&mut self) {
     match &mut self.value {
+      None if self.weak => {
+        // This weak global handle has been cleared by the garbage collector.
+      }
       None => {
         // This global handle is empty.
         assert!(self.isolate_handle.is_none())
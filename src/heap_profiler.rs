@@ -0,0 +1,200 @@
+// Copyright 2019-2020 the Deno authors. All rights reserved. MIT license.
+use std::convert::TryInto;
+
+use crate::isolate::Isolate;
+use crate::support::int;
+use crate::support::Opaque;
+use crate::CallbackScope;
+use crate::HandleScope;
+use crate::InIsolate;
+use crate::Local;
+use crate::String;
+use crate::ToLocal;
+
+extern "C" {
+  fn v8__HeapProfiler__StartSamplingHeapProfiler(
+    isolate: *mut Isolate,
+    sample_interval: u64,
+    stack_depth: int,
+  ) -> bool;
+  fn v8__HeapProfiler__StopSamplingHeapProfiler(isolate: *mut Isolate);
+  fn v8__HeapProfiler__GetAllocationProfile(
+    isolate: *mut Isolate,
+  ) -> *mut RawAllocationProfile;
+
+  fn v8__AllocationProfile__DELETE(this: *mut RawAllocationProfile);
+  fn v8__AllocationProfile__GetRootNode(
+    this: *mut RawAllocationProfile,
+  ) -> *const RawAllocationNode;
+
+  fn v8__AllocationProfile__Node__GetName(
+    this: *const RawAllocationNode,
+  ) -> *const String;
+  fn v8__AllocationProfile__Node__GetScriptName(
+    this: *const RawAllocationNode,
+  ) -> *const String;
+  fn v8__AllocationProfile__Node__GetScriptId(
+    this: *const RawAllocationNode,
+  ) -> int;
+  fn v8__AllocationProfile__Node__GetStartPosition(
+    this: *const RawAllocationNode,
+  ) -> int;
+  fn v8__AllocationProfile__Node__GetLineNumber(
+    this: *const RawAllocationNode,
+  ) -> int;
+  fn v8__AllocationProfile__Node__GetColumnNumber(
+    this: *const RawAllocationNode,
+  ) -> int;
+  fn v8__AllocationProfile__Node__GetNodeId(
+    this: *const RawAllocationNode,
+  ) -> u32;
+  fn v8__AllocationProfile__Node__GetChildrenCount(
+    this: *const RawAllocationNode,
+  ) -> usize;
+  fn v8__AllocationProfile__Node__GetChild(
+    this: *const RawAllocationNode,
+    index: usize,
+  ) -> *const RawAllocationNode;
+  fn v8__AllocationProfile__Node__GetAllocationsCount(
+    this: *const RawAllocationNode,
+  ) -> usize;
+  fn v8__AllocationProfile__Node__GetAllocation(
+    this: *const RawAllocationNode,
+    index: usize,
+  ) -> *const Allocation;
+}
+
+#[repr(C)]
+struct RawAllocationProfile(Opaque);
+
+#[repr(C)]
+struct RawAllocationNode(Opaque);
+
+/// Represents a number of allocations of objects of a particular size.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Allocation {
+  /// Size in bytes of the objects in this allocation.
+  pub size: usize,
+  /// The number of objects of this size that were allocated.
+  pub count: u32,
+}
+
+/// A node in the call-graph of an allocation profile. Each node corresponds to
+/// a function that was on the stack when an allocation was sampled.
+#[derive(Debug, Clone)]
+pub struct AllocationNode {
+  /// Name of the function. May be empty for anonymous functions or if the
+  /// script corresponding to this function has been unloaded.
+  pub name: std::string::String,
+  /// Name of the script containing the function. May be empty if the script
+  /// name is not available, or if the script has been unloaded.
+  pub script_name: std::string::String,
+  /// The id of the script containing the function. Zero if the script has
+  /// been unloaded.
+  pub script_id: int,
+  /// Start position of the function in the script.
+  pub start_position: int,
+  /// 1-indexed line number where the function starts. Zero if no line number
+  /// information is available.
+  pub line_number: int,
+  /// 1-indexed column number where the function starts. Zero if no column
+  /// number information is available.
+  pub column_number: int,
+  /// Unique id of the node.
+  pub node_id: u32,
+  /// List of callees called from this node for which we have sampled
+  /// allocations.
+  pub children: Vec<AllocationNode>,
+  /// List of self allocations done by this node in the call-graph.
+  pub allocations: Vec<Allocation>,
+}
+
+impl AllocationNode {
+  fn from_raw<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+    node: *const RawAllocationNode,
+  ) -> Self {
+    unsafe {
+      let name: Local<String> = scope
+        .to_local(v8__AllocationProfile__Node__GetName(node))
+        .unwrap();
+      let script_name: Local<String> = scope
+        .to_local(v8__AllocationProfile__Node__GetScriptName(node))
+        .unwrap();
+      let children = (0..v8__AllocationProfile__Node__GetChildrenCount(node))
+        .map(|i| {
+          let child = v8__AllocationProfile__Node__GetChild(node, i);
+          Self::from_raw(scope, child)
+        })
+        .collect();
+      let allocations =
+        (0..v8__AllocationProfile__Node__GetAllocationsCount(node))
+          .map(|i| *v8__AllocationProfile__Node__GetAllocation(node, i))
+          .collect();
+      Self {
+        name: name.to_rust_string_lossy(scope),
+        script_name: script_name.to_rust_string_lossy(scope),
+        script_id: v8__AllocationProfile__Node__GetScriptId(node),
+        start_position: v8__AllocationProfile__Node__GetStartPosition(node),
+        line_number: v8__AllocationProfile__Node__GetLineNumber(node),
+        column_number: v8__AllocationProfile__Node__GetColumnNumber(node),
+        node_id: v8__AllocationProfile__Node__GetNodeId(node),
+        children,
+        allocations,
+      }
+    }
+  }
+}
+
+impl Isolate {
+  /// Starts gathering a sampling heap profile. A sampling heap profile is
+  /// similar to tcmalloc's heap profiler and Go's mprof. It samples object
+  /// allocations and builds an online 'sampling' heap profile. At any point in
+  /// time, this profile is expected to be a representative sample of objects
+  /// currently live in the system. Each sampled object will be accompanied by
+  /// the stack trace at the time of allocation.
+  ///
+  /// `sample_interval` is the average interval in bytes between samples, and
+  /// `stack_depth` is the maximum number of stack frames captured for each
+  /// sample. Returns false if the sampling heap profiler was already running.
+  pub fn start_sampling_heap_profiler(
+    &mut self,
+    sample_interval: u64,
+    stack_depth: usize,
+  ) -> bool {
+    let stack_depth = stack_depth.try_into().unwrap_or(int::max_value());
+    unsafe {
+      v8__HeapProfiler__StartSamplingHeapProfiler(
+        self,
+        sample_interval,
+        stack_depth,
+      )
+    }
+  }
+
+  /// Stops the sampling heap profile and discards the current profile.
+  pub fn stop_sampling_heap_profiler(&mut self) {
+    unsafe { v8__HeapProfiler__StopSamplingHeapProfiler(self) }
+  }
+
+  /// Returns the sampled profile of allocations allocated (and still live)
+  /// since `start_sampling_heap_profiler()` was called, as a tree rooted at
+  /// the returned node. Returns `None` if the sampling heap profiler is not
+  /// running.
+  pub fn get_allocation_profile(&mut self) -> Option<AllocationNode> {
+    let mut cbs = CallbackScope::new(self);
+    let mut hs = HandleScope::new(cbs.enter());
+    let scope = hs.enter();
+    unsafe {
+      let profile = v8__HeapProfiler__GetAllocationProfile(scope.isolate());
+      if profile.is_null() {
+        return None;
+      }
+      let root = v8__AllocationProfile__GetRootNode(profile);
+      let node = AllocationNode::from_raw(scope, root);
+      v8__AllocationProfile__DELETE(profile);
+      Some(node)
+    }
+  }
+}
@@ -24,6 +24,9 @@ use crate::support::UniqueRef;
 use crate::Context;
 use crate::Isolate;
 use crate::Local;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Mutex;
 
 extern "C" {
   fn v8_inspector__V8Inspector__Channel__BASE__CONSTRUCT(
@@ -103,6 +106,14 @@ extern "C" {
     contextGroupId: int,
     humanReadableName: StringView,
   );
+  fn v8_inspector__V8Inspector__contextDestroyed(
+    this: *mut V8Inspector,
+    context: *const Context,
+  );
+  fn v8_inspector__V8Inspector__resetContextGroup(
+    this: *mut V8Inspector,
+    contextGroupId: int,
+  );
 }
 
 #[no_mangle]
@@ -564,6 +575,44 @@ impl V8InspectorSession {
     }
   }
 
+  /// Dispatches a protocol command with the given method name and params,
+  /// which must be a serialized JSON object, e.g. `{"expression":"1+1"}`.
+  /// Returns the id assigned to the command. The `call_id` that is passed to
+  /// `ChannelImpl::send_response()` along with the response to the command
+  /// is equal to this id. Ids are assigned in increasing order, starting at 1
+  /// for every session.
+  ///
+  /// Returns `None` without dispatching anything if `params` is not a valid
+  /// JSON object.
+  // Convenience function not present in the original V8 API.
+  pub fn post_message(&mut self, method: &str, params: &str) -> Option<u32> {
+    if !is_json_object(params) {
+      return None;
+    }
+    let id = {
+      let mut next_ids = SESSION_NEXT_IDS.lock().unwrap();
+      let next_id = next_ids.entry(self as *const Self as usize).or_insert(1);
+      let id = *next_id;
+      *next_id += 1;
+      id
+    };
+    let mut message = format!(r#"{{"id":{},"method":""#, id);
+    for c in method.chars() {
+      match c {
+        '"' => message.push_str(r#"\""#),
+        '\\' => message.push_str(r"\\"),
+        c if c.is_control() => write!(message, "\\u{:04x}", c as u32).unwrap(),
+        c => message.push(c),
+      }
+    }
+    write!(message, r#"","params":{}}}"#, params).unwrap();
+    // An 8-bit StringView is Latin-1, so non-ASCII text must be sent as
+    // UTF-16.
+    let message = message.encode_utf16().collect::<Vec<u16>>();
+    self.dispatch_protocol_message(StringView::from(&message[..]));
+    Some(id)
+  }
+
   pub fn schedule_pause_on_next_statement(
     &mut self,
     reason: StringView,
@@ -579,10 +628,200 @@ impl V8InspectorSession {
 
 impl Drop for V8InspectorSession {
   fn drop(&mut self) {
+    SESSION_NEXT_IDS
+      .lock()
+      .unwrap()
+      .remove(&(self as *const Self as usize));
     unsafe { v8_inspector__V8InspectorSession__DELETE(self) };
   }
 }
 
+lazy_static! {
+  /// The next id that `V8InspectorSession::post_message()` assigns, keyed by
+  /// the address of the session. V8 owns the session object, so the counter
+  /// can't be stored in it.
+  static ref SESSION_NEXT_IDS: Mutex<HashMap<usize, u32>> =
+    Mutex::new(HashMap::new());
+}
+
+/// Returns true if `text` is a single JSON object, optionally surrounded by
+/// whitespace.
+fn is_json_object(text: &str) -> bool {
+  let mut parser = JsonValidator {
+    bytes: text.as_bytes(),
+    pos: 0,
+  };
+  parser.skip_whitespace();
+  if parser.peek() != Some(b'{') || !parser.value(0) {
+    return false;
+  }
+  parser.skip_whitespace();
+  parser.pos == parser.bytes.len()
+}
+
+/// A minimal JSON syntax checker, used to validate the params that are passed
+/// to `V8InspectorSession::post_message()`.
+struct JsonValidator<'a> {
+  bytes: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> JsonValidator<'a> {
+  /// Nesting limit that keeps deeply nested input from overflowing the stack.
+  const MAX_DEPTH: usize = 1000;
+
+  fn peek(&self) -> Option<u8> {
+    self.bytes.get(self.pos).copied()
+  }
+
+  fn eat(&mut self, byte: u8) -> bool {
+    let found = self.peek() == Some(byte);
+    if found {
+      self.pos += 1;
+    }
+    found
+  }
+
+  fn eat_literal(&mut self, literal: &[u8]) -> bool {
+    let found = self.bytes[self.pos..].starts_with(literal);
+    if found {
+      self.pos += literal.len();
+    }
+    found
+  }
+
+  fn eat_digits(&mut self) -> bool {
+    let start = self.pos;
+    while let Some(b'0'..=b'9') = self.peek() {
+      self.pos += 1;
+    }
+    self.pos > start
+  }
+
+  fn skip_whitespace(&mut self) {
+    while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek()
+    {
+      self.pos += 1;
+    }
+  }
+
+  fn value(&mut self, depth: usize) -> bool {
+    if depth > Self::MAX_DEPTH {
+      return false;
+    }
+    match self.peek() {
+      Some(b'{') => self.object(depth),
+      Some(b'[') => self.array(depth),
+      Some(b'"') => self.string(),
+      Some(b't') => self.eat_literal(b"true"),
+      Some(b'f') => self.eat_literal(b"false"),
+      Some(b'n') => self.eat_literal(b"null"),
+      Some(b'-') | Some(b'0'..=b'9') => self.number(),
+      _ => false,
+    }
+  }
+
+  fn object(&mut self, depth: usize) -> bool {
+    self.pos += 1;
+    self.skip_whitespace();
+    if self.eat(b'}') {
+      return true;
+    }
+    loop {
+      self.skip_whitespace();
+      if self.peek() != Some(b'"') || !self.string() {
+        return false;
+      }
+      self.skip_whitespace();
+      if !self.eat(b':') {
+        return false;
+      }
+      self.skip_whitespace();
+      if !self.value(depth + 1) {
+        return false;
+      }
+      self.skip_whitespace();
+      if self.eat(b'}') {
+        return true;
+      }
+      if !self.eat(b',') {
+        return false;
+      }
+    }
+  }
+
+  fn array(&mut self, depth: usize) -> bool {
+    self.pos += 1;
+    self.skip_whitespace();
+    if self.eat(b']') {
+      return true;
+    }
+    loop {
+      self.skip_whitespace();
+      if !self.value(depth + 1) {
+        return false;
+      }
+      self.skip_whitespace();
+      if self.eat(b']') {
+        return true;
+      }
+      if !self.eat(b',') {
+        return false;
+      }
+    }
+  }
+
+  fn string(&mut self) -> bool {
+    self.pos += 1;
+    loop {
+      match self.peek() {
+        Some(b'"') => {
+          self.pos += 1;
+          return true;
+        }
+        Some(b'\\') => {
+          self.pos += 1;
+          match self.peek() {
+            Some(b'"') | Some(b'\\') | Some(b'/') | Some(b'b') | Some(b'f')
+            | Some(b'n') | Some(b'r') | Some(b't') => self.pos += 1,
+            Some(b'u') => {
+              self.pos += 1;
+              for _ in 0..4 {
+                match self.peek() {
+                  Some(b) if b.is_ascii_hexdigit() => self.pos += 1,
+                  _ => return false,
+                }
+              }
+            }
+            _ => return false,
+          }
+        }
+        Some(b) if b >= 0x20 => self.pos += 1,
+        _ => return false,
+      }
+    }
+  }
+
+  fn number(&mut self) -> bool {
+    self.eat(b'-');
+    if !self.eat(b'0') && !self.eat_digits() {
+      return false;
+    }
+    if self.eat(b'.') && !self.eat_digits() {
+      return false;
+    }
+    if self.eat(b'e') || self.eat(b'E') {
+      if !self.eat(b'+') {
+        self.eat(b'-');
+      }
+      if !self.eat_digits() {
+        return false;
+      }
+    }
+    true
+  }
+}
+
 // TODO: in C++, this class is intended to be user-extensible, just like
 // like `Task`, `Client`, `Channel`. In Rust this would ideally also be the
 // case, but currently to obtain a `UniquePtr<StringBuffer>` is by making a
@@ -607,6 +846,14 @@ impl StringBuffer {
   pub fn create(source: StringView) -> UniquePtr<StringBuffer> {
     unsafe { v8_inspector__StringBuffer__create(source) }
   }
+
+  /// Creates a `StringBuffer` from a Rust string. The contents are transcoded
+  /// to UTF-16, so non-ASCII characters are preserved.
+  // Convenience function not present in the original V8 API.
+  pub fn from_utf8(source: &str) -> UniquePtr<StringBuffer> {
+    let source = source.encode_utf16().collect::<Vec<u16>>();
+    Self::create(StringView::from(&source[..]))
+  }
 }
 
 impl Drop for StringBuffer {
@@ -884,6 +1131,20 @@ impl V8Inspector {
       )
     }
   }
+
+  /// Notifies the inspector that the context is about to be destroyed. Must
+  /// be called for every context that was passed to `context_created()`.
+  pub fn context_destroyed(&mut self, context: Local<Context>) {
+    unsafe { v8_inspector__V8Inspector__contextDestroyed(self, &*context) }
+  }
+
+  /// Discards the inspector state (e.g. evaluated scripts and console
+  /// messages) of all contexts in the given context group.
+  pub fn reset_context_group(&mut self, context_group_id: i32) {
+    unsafe {
+      v8_inspector__V8Inspector__resetContextGroup(self, context_group_id)
+    }
+  }
 }
 
 impl Drop for V8Inspector {
@@ -3,18 +3,23 @@ use crate::isolate_create_params::raw;
 use crate::isolate_create_params::CreateParams;
 use crate::promise::PromiseRejectMessage;
 use crate::support::Opaque;
+use crate::Array;
 use crate::Context;
+use crate::Data;
 use crate::Function;
 use crate::InIsolate;
 use crate::Local;
 use crate::Message;
+use crate::MessageErrorLevel;
 use crate::Module;
 use crate::Object;
 use crate::Promise;
 use crate::ScriptOrModule;
 use crate::String;
+use crate::ToLocal;
 use crate::Value;
 
+use libc::c_char;
 use std::any::Any;
 use std::any::TypeId;
 use std::cell::{Ref, RefCell, RefMut};
@@ -27,6 +32,20 @@ use std::ptr::NonNull;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+/// Policy for running microtasks:
+///   - explicit: microtasks are invoked with the
+///     Isolate::perform_microtask_checkpoint() method;
+///   - scoped: microtasks invocation is controlled by MicrotasksScope objects;
+///   - auto: microtasks are invoked when the script call depth decrements
+///     to zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum MicrotasksPolicy {
+  Explicit = 0,
+  Scoped = 1,
+  Auto = 2,
+}
+
 pub type MessageCallback = extern "C" fn(Local<Message>, Local<Value>);
 
 pub type PromiseRejectCallback = extern "C" fn(PromiseRejectMessage);
@@ -60,15 +79,46 @@ pub type HostInitializeImportMetaObjectCallback =
 /// this promise with the exception. If the promise creation itself
 /// fails (e.g. due to stack overflow), the embedder must propagate
 /// that exception by returning an empty MaybeLocal.
+// TODO: V8 8.5 has no support for import assertions. The
+// HostImportModuleDynamicallyWithImportAssertionsCallback variant, which also
+// receives a FixedArray of assertions, can only be added once we're on a V8
+// version that has it (9.1 or newer).
 pub type HostImportModuleDynamicallyCallback = extern "C" fn(
   Local<Context>,
   Local<ScriptOrModule>,
   Local<String>,
 ) -> *mut Promise;
 
+/// PrepareStackTraceCallback is called when the stack property of an error is
+/// first accessed. The return value will be used as the stack value. If this
+/// callback is registered, the |Error.prepareStackTrace| API will be disabled.
+/// |sites| is an array of call sites, specified in
+/// https://v8.dev/docs/stack-trace-api
+///
+/// Return a null pointer to signal that an exception was thrown; the embedder
+/// must have scheduled that exception with `Isolate::throw_exception()`.
+pub type PrepareStackTraceCallback =
+  extern "C" fn(Local<Context>, Local<Value>, Local<Array>) -> *mut Value;
+
 pub type InterruptCallback =
   extern "C" fn(isolate: &mut Isolate, data: *mut c_void);
 
+/// This callback is invoked when the heap size is close to the heap limit and
+/// V8 is likely to abort with out-of-memory error.
+/// The callback can extend the heap limit by returning a value that is greater
+/// than the current_heap_limit. The initial heap limit is the limit that was
+/// set after heap setup.
+pub type NearHeapLimitCallback = extern "C" fn(
+  data: *mut c_void,
+  current_heap_limit: usize,
+  initial_heap_limit: usize,
+) -> usize;
+
+/// This callback is invoked when V8 encounters a fatal error, right before the
+/// process is aborted. `location` and `message` are null-terminated C strings.
+pub type FatalErrorCallback =
+  extern "C" fn(location: *const c_char, message: *const c_char);
+
 extern "C" {
   fn v8__Isolate__New(params: *const raw::CreateParams) -> *mut Isolate;
   fn v8__Isolate__Dispose(this: *mut Isolate);
@@ -77,6 +127,16 @@ extern "C" {
   fn v8__Isolate__GetNumberOfDataSlots(this: *const Isolate) -> u32;
   fn v8__Isolate__Enter(this: *mut Isolate);
   fn v8__Isolate__Exit(this: *mut Isolate);
+  fn v8__Isolate__GetCurrent() -> *mut Isolate;
+  fn v8__Isolate__IsInUse(this: *const Isolate) -> bool;
+  fn v8__Isolate__LowMemoryNotification(this: *mut Isolate);
+  fn v8__Isolate__AdjustAmountOfExternalAllocatedMemory(
+    this: *mut Isolate,
+    change_in_bytes: i64,
+  ) -> i64;
+  fn v8__Isolate__SetStackLimit(this: *mut Isolate, stack_limit: usize);
+  fn v8__Isolate__IncreaseHeapLimitForDebugging(this: *mut Isolate);
+  fn v8__Isolate__RestoreOriginalHeapLimit(this: *mut Isolate);
   fn v8__Isolate__SetCaptureStackTraceForUncaughtExceptions(
     this: *mut Isolate,
     caputre: bool,
@@ -86,6 +146,11 @@ extern "C" {
     isolate: *mut Isolate,
     callback: MessageCallback,
   ) -> bool;
+  fn v8__Isolate__AddMessageListenerWithErrorLevel(
+    isolate: *mut Isolate,
+    callback: MessageCallback,
+    message_levels: MessageErrorLevel,
+  ) -> bool;
   fn v8__Isolate__SetPromiseRejectCallback(
     isolate: *mut Isolate,
     callback: PromiseRejectCallback,
@@ -98,6 +163,24 @@ extern "C" {
     isolate: *mut Isolate,
     callback: HostImportModuleDynamicallyCallback,
   );
+  fn v8__Isolate__SetPrepareStackTraceCallback(
+    isolate: *mut Isolate,
+    callback: PrepareStackTraceCallback,
+  );
+  fn v8__Isolate__SetFatalErrorHandler(
+    isolate: *mut Isolate,
+    callback: FatalErrorCallback,
+  );
+  fn v8__Isolate__AddNearHeapLimitCallback(
+    isolate: *mut Isolate,
+    callback: NearHeapLimitCallback,
+    data: *mut c_void,
+  );
+  fn v8__Isolate__RemoveNearHeapLimitCallback(
+    isolate: *mut Isolate,
+    callback: NearHeapLimitCallback,
+    heap_limit: usize,
+  );
   fn v8__Isolate__RequestInterrupt(
     isolate: *const Isolate,
     callback: InterruptCallback,
@@ -111,11 +194,24 @@ extern "C" {
   fn v8__Isolate__IsExecutionTerminating(isolate: *const Isolate) -> bool;
   fn v8__Isolate__CancelTerminateExecution(isolate: *const Isolate);
   fn v8__Isolate__RunMicrotasks(isolate: *mut Isolate);
+  fn v8__Isolate__PerformMicrotaskCheckpoint(isolate: *mut Isolate);
+  fn v8__Isolate__SetMicrotasksPolicy(
+    isolate: *mut Isolate,
+    policy: MicrotasksPolicy,
+  );
+  fn v8__Isolate__GetMicrotasksPolicy(
+    isolate: *const Isolate,
+  ) -> MicrotasksPolicy;
   fn v8__Isolate__EnqueueMicrotask(
     isolate: *mut Isolate,
     function: *const Function,
   );
 
+  fn v8__Isolate__GetDataFromSnapshotOnce(
+    isolate: *mut Isolate,
+    index: usize,
+  ) -> *const Data;
+
   fn v8__HeapProfiler__TakeHeapSnapshot(
     isolate: *mut Isolate,
     callback: extern "C" fn(*mut c_void, *const u8, usize) -> bool,
@@ -155,6 +251,8 @@ impl Isolate {
     CreateParams::default()
   }
 
+  /// Returns a handle that can be used to interact with this isolate from other
+  /// threads. The handle can be cloned and sent to as many threads as needed.
   pub fn thread_safe_handle(&mut self) -> IsolateHandle {
     IsolateHandle::new(self)
   }
@@ -230,18 +328,17 @@ impl Isolate {
   ///
   /// This method gives ownership of value to the Isolate. Exactly one object of
   /// each type can be associated with an Isolate. If called more than once with
-  /// an object of the same type, the earlier version will be dropped and
-  /// replaced.
+  /// an object of the same type, the earlier version will be replaced.
   ///
-  /// Returns true if value was set without replacing an existing value.
+  /// Returns the value that was replaced, if any.
   ///
   /// The value will be dropped when the isolate is dropped.
-  pub fn set_slot<T: 'static>(&mut self, value: T) -> bool {
+  pub fn set_slot<T: 'static>(&mut self, value: T) -> Option<T> {
     self
       .get_annex_mut()
       .slots
-      .insert(Any::type_id(&value), RefCell::new(Box::new(value)))
-      .is_none()
+      .insert(TypeId::of::<T>(), RefCell::new(Box::new(value)))
+      .map(|cell| *cell.into_inner().downcast::<T>().unwrap())
   }
 
   /// Sets this isolate as the entered one for the current thread.
@@ -257,9 +354,106 @@ impl Isolate {
   ///
   /// Requires: self == Isolate::GetCurrent().
   pub(crate) fn exit(&mut self) {
+    debug_assert_eq!(
+      Self::get_current().map(NonNull::as_ptr),
+      Some(self as *mut Self),
+      "exiting an isolate that is not the current isolate of this thread"
+    );
     unsafe { v8__Isolate__Exit(self) }
   }
 
+  /// Returns the entered isolate for the current thread, or `None` in case
+  /// there is no current isolate.
+  ///
+  /// The returned pointer is only valid for as long as the isolate stays
+  /// entered, and it must not be dereferenced while another `&mut Isolate`
+  /// reference to the same isolate is alive (e.g. one held by a scope further
+  /// up the stack).
+  pub fn get_current() -> Option<NonNull<Isolate>> {
+    NonNull::new(unsafe { v8__Isolate__GetCurrent() })
+  }
+
+  // Isolates that are only used by the thread that owns them are never
+  // entered by this crate, so an isolate that is not entered at all is
+  // accepted as well. What must not happen is using it while another isolate
+  // is entered on this thread, or while another thread has entered it.
+  fn debug_assert_entered(&mut self) {
+    debug_assert!(
+      match Self::get_current() {
+        Some(current) => current.as_ptr() == self as *mut Self,
+        None => !self.is_in_use(),
+      },
+      "the isolate is not entered by the current thread"
+    );
+  }
+
+  /// Returns true if this isolate is entered by at least one thread.
+  pub fn is_in_use(&self) -> bool {
+    unsafe { v8__Isolate__IsInUse(self) }
+  }
+
+  /// Adds a callback to notify the embedder when the heap size is close to the
+  /// heap limit. The callback can extend the heap limit to give the embedder
+  /// a chance to handle the situation gracefully instead of crashing with an
+  /// out-of-memory error.
+  ///
+  /// Multiple callbacks can be added. They are invoked in LIFO order.
+  pub fn add_near_heap_limit_callback(
+    &mut self,
+    callback: NearHeapLimitCallback,
+    data: *mut c_void,
+  ) {
+    unsafe { v8__Isolate__AddNearHeapLimitCallback(self, callback, data) }
+  }
+
+  /// Removes the given callback and restores the heap limit to the given limit.
+  /// If the given limit is zero, then it is ignored. If the current heap size
+  /// is greater than the given limit, then the heap limit is restored to the
+  /// minimal limit that is possible for the current heap size.
+  pub fn remove_near_heap_limit_callback(
+    &mut self,
+    callback: NearHeapLimitCallback,
+    heap_limit: usize,
+  ) {
+    unsafe {
+      v8__Isolate__RemoveNearHeapLimitCallback(self, callback, heap_limit)
+    }
+  }
+
+  /// Optional notification that the system is running low on memory.
+  /// V8 uses these notifications to attempt to free memory.
+  pub fn low_memory_notification(&mut self) {
+    unsafe { v8__Isolate__LowMemoryNotification(self) }
+  }
+
+  /// Adjusts the amount of registered external memory. Used to give V8 an
+  /// indication of the amount of externally allocated memory that is kept
+  /// alive by JavaScript objects. V8 uses this to decide when to perform
+  /// global garbage collections. Registering externally allocated memory will
+  /// trigger global garbage collections more often than it would otherwise in
+  /// an application that only uses V8.
+  ///
+  /// Returns the adjusted value.
+  pub fn adjust_amount_of_external_allocated_memory(
+    &mut self,
+    change_in_bytes: i64,
+  ) -> i64 {
+    unsafe {
+      v8__Isolate__AdjustAmountOfExternalAllocatedMemory(self, change_in_bytes)
+    }
+  }
+
+  /// Set the address beyond which the JavaScript stack may not grow. The stack
+  /// grows downwards, so `stack_limit` is the lowest address that V8 is
+  /// allowed to use. Exceeding the limit results in a RangeError being thrown
+  /// instead of a crash.
+  ///
+  /// This is needed when V8 runs on a thread whose stack size differs from
+  /// the default, e.g. a thread spawned with a custom stack size.
+  pub fn set_stack_limit(&mut self, stack_limit: usize) {
+    unsafe { v8__Isolate__SetStackLimit(self, stack_limit) }
+  }
+
   /// Tells V8 to capture current stack trace when uncaught exception occurs
   /// and report it to the message listeners. The option is off by default.
   pub fn set_capture_stack_trace_for_uncaught_exceptions(
@@ -286,6 +480,23 @@ impl Isolate {
     unsafe { v8__Isolate__AddMessageListener(self, callback) }
   }
 
+  /// Adds a message listener for the specified message levels. Unlike
+  /// `add_message_listener()`, which only reports errors, this can be used to
+  /// receive e.g. warnings as well.
+  pub fn add_message_listener_with_error_level(
+    &mut self,
+    callback: MessageCallback,
+    message_levels: MessageErrorLevel,
+  ) -> bool {
+    unsafe {
+      v8__Isolate__AddMessageListenerWithErrorLevel(
+        self,
+        callback,
+        message_levels,
+      )
+    }
+  }
+
   /// Set callback to notify about promise reject with no handler, or
   /// revocation of such a previous notification once the handler is added.
   pub fn set_promise_reject_callback(
@@ -316,6 +527,20 @@ impl Isolate {
     }
   }
 
+  /// This specifies the callback called when the stack property of Error
+  /// is accessed.
+  pub fn set_prepare_stack_trace_callback(
+    &mut self,
+    callback: PrepareStackTraceCallback,
+  ) {
+    unsafe { v8__Isolate__SetPrepareStackTraceCallback(self, callback) }
+  }
+
+  /// Set the callback to invoke in case of fatal errors.
+  pub fn set_fatal_error_handler(&mut self, callback: FatalErrorCallback) {
+    unsafe { v8__Isolate__SetFatalErrorHandler(self, callback) }
+  }
+
   /// Schedules an exception to be thrown when returning to JavaScript. When an
   /// exception has been scheduled it is illegal to invoke any JavaScript
   /// operation; the caller must return immediately and only after the exception
@@ -337,17 +562,62 @@ impl Isolate {
   /// Runs the default MicrotaskQueue until it gets empty.
   /// Any exceptions thrown by microtask callbacks are swallowed.
   pub fn run_microtasks(&mut self) {
+    self.debug_assert_entered();
     unsafe { v8__Isolate__RunMicrotasks(self) }
   }
 
   /// Enqueues the callback to the default MicrotaskQueue
   pub fn enqueue_microtask(&mut self, microtask: Local<Function>) {
+    self.debug_assert_entered();
     unsafe { v8__Isolate__EnqueueMicrotask(self, &*microtask) }
   }
 
+  /// Runs the default MicrotaskQueue until it gets empty and perform other
+  /// microtask checkpoint steps, such as calling ClearKeptObjects. Asserts
+  /// that the MicrotasksPolicy is not kScoped. Any exceptions thrown by
+  /// microtask callbacks are swallowed.
+  pub fn perform_microtask_checkpoint(&mut self) {
+    self.debug_assert_entered();
+    unsafe { v8__Isolate__PerformMicrotaskCheckpoint(self) }
+  }
+
+  /// Controls how microtasks are run for the default MicrotaskQueue. See
+  /// `MicrotasksPolicy` for details.
+  pub fn set_microtasks_policy(&mut self, policy: MicrotasksPolicy) {
+    unsafe { v8__Isolate__SetMicrotasksPolicy(self, policy) }
+  }
+
+  /// Returns the policy controlling how microtasks are run for the default
+  /// MicrotaskQueue.
+  pub fn get_microtasks_policy(&self) -> MicrotasksPolicy {
+    unsafe { v8__Isolate__GetMicrotasksPolicy(self) }
+  }
+
+  /// Return data that was previously attached to the isolate snapshot via
+  /// `SnapshotCreator::add_isolate_data()`, or `None` if it has already been
+  /// retrieved or if the index is invalid. The data is cleared after the first
+  /// retrieval.
+  ///
+  /// This function is unsafe because the cast to `T` is unchecked; `T` must be
+  /// the type of the data that was attached.
+  pub unsafe fn get_data_from_snapshot_once<'sc, T>(
+    scope: &mut impl ToLocal<'sc>,
+    index: usize,
+  ) -> Option<Local<'sc, T>>
+  where
+    Local<'sc, Data>: From<Local<'sc, T>>,
+  {
+    let ptr = v8__Isolate__GetDataFromSnapshotOnce(scope.isolate(), index);
+    scope.to_local(ptr).map(|data| Local::cast(data))
+  }
+
   /// Disposes the isolate.  The isolate must not be entered by any
   /// thread to be disposable.
   unsafe fn dispose(&mut self) {
+    debug_assert!(
+      !self.is_in_use(),
+      "cannot dispose an isolate that is still entered by a thread"
+    );
     let annex = self.get_annex_mut();
 
     // Set the `isolate` pointer inside the annex struct to null, so any
@@ -510,6 +780,33 @@ impl IsolateHandle {
     }
   }
 
+  /// Optional notification to tell V8 the current isolate is used for
+  /// debugging and requires higher heap limit.
+  ///
+  /// Returns false if Isolate was already destroyed.
+  pub fn increase_heap_limit_for_debugging(&self) -> bool {
+    let _lock = self.0.isolate_mutex.lock().unwrap();
+    if self.0.isolate.is_null() {
+      false
+    } else {
+      unsafe { v8__Isolate__IncreaseHeapLimitForDebugging(self.0.isolate) };
+      true
+    }
+  }
+
+  /// Restores the original heap limit after IncreaseHeapLimitForDebugging().
+  ///
+  /// Returns false if Isolate was already destroyed.
+  pub fn restore_original_heap_limit(&self) -> bool {
+    let _lock = self.0.isolate_mutex.lock().unwrap();
+    if self.0.isolate.is_null() {
+      false
+    } else {
+      unsafe { v8__Isolate__RestoreOriginalHeapLimit(self.0.isolate) };
+      true
+    }
+  }
+
   /// Request V8 to interrupt long running JavaScript code and invoke
   /// the given |callback| passing the given |data| to it. After |callback|
   /// returns control will be returned to the JavaScript code.
@@ -546,6 +843,10 @@ impl OwnedIsolate {
     let cxx_isolate = NonNull::new(cxx_isolate).unwrap();
     Self { cxx_isolate }
   }
+
+  pub(crate) fn as_ptr(&self) -> *mut Isolate {
+    self.cxx_isolate.as_ptr()
+  }
 }
 
 impl InIsolate for OwnedIsolate {
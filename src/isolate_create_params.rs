@@ -88,6 +88,23 @@ impl CreateParams {
     self
   }
 
+  /// Configures the constraints with reasonable default values based on the
+  /// provided heap size limit. The heap size includes both the young and
+  /// the old generation.
+  ///
+  /// When the heap size approaches `max`, V8 will perform series of garbage
+  /// collections and invoke the near-heap-limit callbacks, see
+  /// `Isolate::add_near_heap_limit_callback()`. If the garbage collections do
+  /// not help and no callback increases the limit, then V8 will crash with an
+  /// out-of-memory error.
+  pub fn heap_limits(mut self, initial: usize, max: usize) -> Self {
+    self
+      .raw
+      .constraints
+      .configure_defaults_from_heap_size(initial, max);
+    self
+  }
+
   /// Whether calling Atomics.wait (a function that may block) is allowed in
   /// this isolate. This can also be configured via SetAllowAtomicsWait.
   pub fn allow_atomics_wait(mut self, value: bool) -> Self {
@@ -164,6 +181,12 @@ pub(crate) mod raw {
       buf: *mut MaybeUninit<CreateParams>,
     );
     fn v8__Isolate__CreateParams__SIZEOF() -> usize;
+
+    fn v8__ResourceConstraints__ConfigureDefaultsFromHeapSize(
+      constraints: *mut ResourceConstraints,
+      initial_heap_size_in_bytes: usize,
+      maximum_heap_size_in_bytes: usize,
+    );
   }
 
   impl Default for CreateParams {
@@ -183,7 +206,7 @@ pub(crate) mod raw {
   }
 
   impl StartupData {
-    pub(super) fn boxed_header(data: &Allocation<[u8]>) -> Box<Self> {
+    pub(crate) fn boxed_header(data: &Allocation<[u8]>) -> Box<Self> {
       Box::new(Self {
         data: &data[0] as *const _ as *const char,
         raw_size: int::try_from(data.len()).unwrap(),
@@ -201,4 +224,20 @@ pub(crate) mod raw {
     initial_young_generation_size_: usize,
     stack_limit_: *mut u32,
   }
+
+  impl ResourceConstraints {
+    pub fn configure_defaults_from_heap_size(
+      &mut self,
+      initial_heap_size_in_bytes: usize,
+      maximum_heap_size_in_bytes: usize,
+    ) {
+      unsafe {
+        v8__ResourceConstraints__ConfigureDefaultsFromHeapSize(
+          self,
+          initial_heap_size_in_bytes,
+          maximum_heap_size_in_bytes,
+        )
+      };
+    }
+  }
 }
@@ -19,6 +19,9 @@ extern "C" {
 
 /// Tries to parse the string `json_string` and returns it as value if
 /// successful.
+///
+/// If parsing fails, `None` is returned and a `SyntaxError` is thrown. Wrap the
+/// call in a `TryCatch` to retrieve the exception and its message.
 pub fn parse<'sc>(
   scope: &mut impl ToLocal<'sc>,
   context: Local<'_, Context>,
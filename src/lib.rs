@@ -77,15 +77,21 @@ extern crate libc;
 mod array_buffer;
 mod array_buffer_view;
 mod context;
+mod cpu_profiler;
 mod data;
 mod exception;
+mod external;
 mod external_references;
 mod function;
 mod global;
 mod handle_scope;
+mod heap_profiler;
 mod isolate;
 mod isolate_create_params;
 mod local;
+mod locker;
+mod message;
+mod microtask_queue;
 mod module;
 mod number;
 mod object;
@@ -100,12 +106,15 @@ mod script;
 mod script_or_module;
 mod shared_array_buffer;
 mod snapshot;
+mod stack_trace;
 mod string;
 mod support;
 mod template;
 mod try_catch;
 mod uint8_array;
+mod unbound_module_script;
 mod value;
+mod wasm;
 
 pub mod inspector;
 pub mod json;
@@ -117,6 +126,10 @@ pub mod script_compiler;
 pub mod V8;
 
 pub use array_buffer::*;
+pub use context::ContextOptions;
+pub use cpu_profiler::CpuProfile;
+pub use cpu_profiler::CpuProfileNode;
+pub use cpu_profiler::CpuProfiler;
 pub use data::*;
 pub use exception::*;
 pub use external_references::ExternalReference;
@@ -125,18 +138,31 @@ pub use function::*;
 pub use global::Global;
 pub use handle_scope::EscapableHandleScope;
 pub use handle_scope::HandleScope;
+pub use heap_profiler::Allocation;
+pub use heap_profiler::AllocationNode;
+pub use isolate::FatalErrorCallback;
 pub use isolate::HostImportModuleDynamicallyCallback;
 pub use isolate::HostInitializeImportMetaObjectCallback;
 pub use isolate::Isolate;
 pub use isolate::IsolateHandle;
 pub use isolate::MessageCallback;
+pub use isolate::MicrotasksPolicy;
+pub use isolate::NearHeapLimitCallback;
 pub use isolate::OwnedIsolate;
+pub use isolate::PrepareStackTraceCallback;
 pub use isolate::PromiseRejectCallback;
 pub use isolate_create_params::CreateParams;
 pub use local::Local;
+pub use locker::Locker;
+pub use locker::SharedIsolate;
+pub use locker::Unlocker;
+pub use message::MessageErrorLevel;
+pub use microtask_queue::MicrotaskQueue;
 pub use module::*;
+pub use number::PrecisionLost;
 pub use object::*;
 pub use platform::new_default_platform;
+pub use platform::new_default_platform_with;
 pub use platform::Platform;
 pub use platform::Task;
 // TODO(ry) TaskBase and TaskImpl ideally shouldn't be part of the public API.
@@ -153,16 +179,24 @@ pub use scope::PropertyCallbackScope;
 pub use scope::Scope;
 pub use scope_traits::*;
 pub use script::ScriptOrigin;
+pub use script::ScriptOriginBuilder;
+pub use snapshot::DeserializeInternalFieldsCallback;
 pub use snapshot::FunctionCodeHandling;
+pub use snapshot::SerializeInternalFieldsCallback;
 pub use snapshot::SnapshotCreator;
 pub use snapshot::StartupData;
+pub use string::FromV8String;
 pub use string::NewStringType;
+pub use string::StringTooLong;
+pub use string::ToV8String;
 pub use support::SharedPtr;
 pub use support::SharedRef;
 pub use support::UniquePtr;
 pub use support::UniqueRef;
 pub use template::*;
 pub use try_catch::{TryCatch, TryCatchScope};
+pub use value::ToPrimitiveHint;
+pub use wasm::CompiledWasmModule;
 
 // TODO(piscisaureus): Ideally this trait would not be exported.
 pub use support::MapFnTo;
@@ -37,6 +37,10 @@ use std::ptr::NonNull;
 /// Note: Local handles in Rusty V8 differ from the V8 C++ API in that they are
 /// never empty. In situations where empty handles are needed, use
 /// Option<Local>.
+///
+/// Comparing two local handles with `==` checks whether they refer to the same
+/// object, like `operator==` in the C++ API. It does not compare JavaScript
+/// values; use `Value::strict_equals()` or `Value::same_value()` for that.
 #[repr(C)]
 pub struct Local<'sc, T>(NonNull<T>, PhantomData<&'sc ()>);
 
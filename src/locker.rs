@@ -0,0 +1,158 @@
+// Copyright 2019-2020 the Deno authors. All rights reserved. MIT license.
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+use crate::isolate::Isolate;
+use crate::InIsolate;
+use crate::OwnedIsolate;
+
+extern "C" {
+  fn v8__Locker__CONSTRUCT(
+    buf: *mut MaybeUninit<RawLocker>,
+    isolate: *mut Isolate,
+  );
+  fn v8__Locker__DESTRUCT(this: *mut RawLocker);
+  fn v8__Locker__IsLocked(isolate: *const Isolate) -> bool;
+  fn v8__Unlocker__CONSTRUCT(
+    buf: *mut MaybeUninit<RawUnlocker>,
+    isolate: *mut Isolate,
+  );
+  fn v8__Unlocker__DESTRUCT(this: *mut RawUnlocker);
+}
+
+#[repr(C)]
+struct RawLocker([usize; 2]);
+
+#[repr(C)]
+struct RawUnlocker([usize; 1]);
+
+/// Locker is a scoped lock object. While it's active, i.e. between its
+/// construction and destruction, the current thread is allowed to use the
+/// locked isolate. V8 guarantees that an isolate can be locked by at most one
+/// thread at any time. In other words, the scope of a Locker is a critical
+/// section.
+///
+/// In addition to acquiring the lock, the Locker enters the isolate, so that
+/// it can be used on the current thread for as long as the Locker is alive.
+/// Once an isolate has been locked by a Locker, all further uses of it must
+/// happen while a Locker is active.
+///
+/// `Locker::new()` mutably borrows the isolate, so it can only be used by the
+/// thread that owns it. To use an isolate from multiple threads, wrap it in a
+/// `SharedIsolate` and lock it with `SharedIsolate::lock()`.
+pub struct Locker<'a> {
+  raw: RawLocker,
+  isolate: NonNull<Isolate>,
+  _phantom: PhantomData<&'a mut Isolate>,
+}
+
+impl<'a> Locker<'a> {
+  /// Initialize Locker for a given Isolate. Blocks until the lock has been
+  /// acquired.
+  pub fn new(isolate: &'a mut Isolate) -> Self {
+    unsafe { Self::from_ptr(isolate) }
+  }
+
+  // This function is marked unsafe because the caller must guarantee that
+  // the isolate outlives the Locker, and that no other references to it are
+  // alive while the Locker exists.
+  unsafe fn from_ptr(isolate_ptr: *mut Isolate) -> Self {
+    let mut buf = MaybeUninit::<RawLocker>::uninit();
+    v8__Locker__CONSTRUCT(&mut buf, isolate_ptr);
+    let mut locker = Self {
+      raw: buf.assume_init(),
+      isolate: NonNull::new(isolate_ptr).unwrap(),
+      _phantom: PhantomData,
+    };
+    locker.isolate().enter();
+    locker
+  }
+
+  /// Returns whether or not the locker for a given isolate, is locked by the
+  /// current thread.
+  pub fn is_locked(isolate: &Isolate) -> bool {
+    unsafe { v8__Locker__IsLocked(isolate) }
+  }
+}
+
+impl<'a> InIsolate for Locker<'a> {
+  fn isolate(&mut self) -> &mut Isolate {
+    unsafe { self.isolate.as_mut() }
+  }
+}
+
+impl<'a> Drop for Locker<'a> {
+  fn drop(&mut self) {
+    self.isolate().exit();
+    unsafe { v8__Locker__DESTRUCT(&mut self.raw) }
+  }
+}
+
+/// An isolate that can be shared between threads, e.g. through an `Arc`. The
+/// isolate can only be accessed through the Locker that is returned by
+/// `SharedIsolate::lock()`, so at most one thread uses it at any time.
+pub struct SharedIsolate {
+  isolate: OwnedIsolate,
+}
+
+unsafe impl Send for SharedIsolate {}
+unsafe impl Sync for SharedIsolate {}
+
+impl SharedIsolate {
+  pub fn new(isolate: OwnedIsolate) -> Self {
+    Self { isolate }
+  }
+
+  /// Locks the isolate for the current thread. Blocks until the lock has been
+  /// acquired.
+  ///
+  /// Panics if the current thread already holds the lock, because the
+  /// isolate would then be reachable through two Lockers at once. Use the
+  /// existing Locker instead.
+  pub fn lock(&self) -> Locker<'_> {
+    assert!(
+      !self.is_locked(),
+      "the isolate is already locked by the current thread"
+    );
+    unsafe { Locker::from_ptr(self.isolate.as_ptr()) }
+  }
+
+  /// Returns whether the isolate is locked by the current thread.
+  pub fn is_locked(&self) -> bool {
+    unsafe { v8__Locker__IsLocked(self.isolate.as_ptr()) }
+  }
+}
+
+/// Unlocker temporarily releases the lock that the current thread holds on an
+/// isolate, allowing other threads to lock it in the meantime. The lock is
+/// reacquired when the Unlocker is dropped.
+///
+/// The Unlocker mutably borrows the Locker that holds the lock, so the isolate
+/// can't be used through it until the Unlocker has been dropped.
+pub struct Unlocker<'a> {
+  raw: RawUnlocker,
+  _phantom: PhantomData<&'a mut Isolate>,
+}
+
+impl<'a> Unlocker<'a> {
+  /// Initialize Unlocker for the Isolate that is locked by `locker`.
+  pub fn new(locker: &'a mut Locker) -> Self {
+    let isolate_ptr: *mut Isolate = locker.isolate();
+    let mut buf = MaybeUninit::<RawUnlocker>::uninit();
+    let raw = unsafe {
+      v8__Unlocker__CONSTRUCT(&mut buf, isolate_ptr);
+      buf.assume_init()
+    };
+    Self {
+      raw,
+      _phantom: PhantomData,
+    }
+  }
+}
+
+impl<'a> Drop for Unlocker<'a> {
+  fn drop(&mut self) {
+    unsafe { v8__Unlocker__DESTRUCT(&mut self.raw) }
+  }
+}
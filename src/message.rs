@@ -0,0 +1,168 @@
+// Copyright 2019-2020 the Deno authors. All rights reserved. MIT license.
+use std::mem::MaybeUninit;
+
+use crate::support::int;
+use crate::Context;
+use crate::Local;
+use crate::Message;
+use crate::ScriptOrigin;
+use crate::StackTrace;
+use crate::String;
+use crate::ToLocal;
+use crate::Value;
+
+bitflags! {
+  /// The level of a message that is reported to message listeners, see
+  /// `Isolate::add_message_listener_with_error_level()`.
+  #[repr(transparent)]
+  pub struct MessageErrorLevel: int {
+    const LOG = 1 << 0;
+    const DEBUG = 1 << 1;
+    const INFO = 1 << 2;
+    const ERROR = 1 << 3;
+    const WARNING = 1 << 4;
+    const ALL = Self::LOG.bits
+      | Self::DEBUG.bits
+      | Self::INFO.bits
+      | Self::ERROR.bits
+      | Self::WARNING.bits;
+  }
+}
+
+extern "C" {
+  fn v8__Message__Get(this: *const Message) -> *const String;
+  fn v8__Message__GetSourceLine(
+    this: *const Message,
+    context: *const Context,
+  ) -> *const String;
+  fn v8__Message__GetScriptResourceName(this: *const Message) -> *const Value;
+  fn v8__Message__GetLineNumber(
+    this: *const Message,
+    context: *const Context,
+  ) -> int;
+  fn v8__Message__GetStartPosition(this: *const Message) -> int;
+  fn v8__Message__GetEndPosition(this: *const Message) -> int;
+  fn v8__Message__GetWasmFunctionIndex(this: *const Message) -> int;
+  fn v8__Message__ErrorLevel(this: *const Message) -> int;
+  fn v8__Message__GetStartColumn(this: *const Message) -> int;
+  fn v8__Message__GetEndColumn(this: *const Message) -> int;
+  fn v8__Message__IsSharedCrossOrigin(this: *const Message) -> bool;
+  fn v8__Message__IsOpaque(this: *const Message) -> bool;
+  fn v8__Message__GetStackTrace(this: *const Message) -> *const StackTrace;
+  fn v8__Message__GetScriptOrigin(
+    this: *const Message,
+    buf: *mut MaybeUninit<ScriptOrigin>,
+  );
+}
+
+impl Message {
+  pub fn get<'sc>(&self, scope: &mut impl ToLocal<'sc>) -> Local<'sc, String> {
+    unsafe { scope.to_local(v8__Message__Get(self)) }.unwrap()
+  }
+
+  /// Exception stack trace. By default stack traces are not captured for
+  /// uncaught exceptions. SetCaptureStackTraceForUncaughtExceptions allows
+  /// to change this option.
+  pub fn get_stack_trace<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Option<Local<'sc, StackTrace>> {
+    unsafe { scope.to_local(v8__Message__GetStackTrace(self)) }
+  }
+
+  /// Returns the line of source code where the error occurred.
+  pub fn get_source_line<'s>(
+    &self,
+    scope: &mut impl ToLocal<'s>,
+    context: Local<Context>,
+  ) -> Option<Local<'s, String>> {
+    unsafe { scope.to_local(v8__Message__GetSourceLine(self, &*context)) }
+  }
+
+  /// Returns the resource name for the script from where the function causing
+  /// the error originates.
+  pub fn get_script_resource_name<'s>(
+    &self,
+    scope: &mut impl ToLocal<'s>,
+  ) -> Option<Local<'s, Value>> {
+    unsafe { scope.to_local(v8__Message__GetScriptResourceName(self)) }
+  }
+
+  /// Returns the number, 1-based, of the line where the error occurred.
+  pub fn get_line_number(&self, context: Local<Context>) -> Option<usize> {
+    let i = unsafe { v8__Message__GetLineNumber(self, &*context) };
+    if i < 0 {
+      None
+    } else {
+      Some(i as usize)
+    }
+  }
+
+  /// Returns the index within the script of the first character where
+  /// the error occurred.
+  pub fn get_start_position(&self) -> int {
+    unsafe { v8__Message__GetStartPosition(self) }
+  }
+
+  /// Returns the index within the script of the last character where
+  /// the error occurred.
+  pub fn get_end_position(&self) -> int {
+    unsafe { v8__Message__GetEndPosition(self) }
+  }
+
+  /// Returns the Wasm function index where the error occurred. Returns -1 if
+  /// message is not from a Wasm script.
+  pub fn get_wasm_function_index(&self) -> int {
+    unsafe { v8__Message__GetWasmFunctionIndex(self) }
+  }
+
+  /// Returns the error level of the message.
+  pub fn error_level(&self) -> MessageErrorLevel {
+    let level = unsafe { v8__Message__ErrorLevel(self) };
+    MessageErrorLevel::from_bits_truncate(level)
+  }
+
+  /// Returns the index within the line of the first character where
+  /// the error occurred.
+  pub fn get_start_column(&self) -> Option<usize> {
+    let i = unsafe { v8__Message__GetStartColumn(self) };
+    if i < 0 {
+      None
+    } else {
+      Some(i as usize)
+    }
+  }
+
+  /// Returns the index within the line of the last character where
+  /// the error occurred.
+  pub fn get_end_column(&self) -> Option<usize> {
+    let i = unsafe { v8__Message__GetEndColumn(self) };
+    if i < 0 {
+      None
+    } else {
+      Some(i as usize)
+    }
+  }
+
+  /// Passes on the value set by the embedder when it fed the script from which
+  /// this Message was generated to V8.
+  pub fn is_shared_cross_origin(&self) -> bool {
+    unsafe { v8__Message__IsSharedCrossOrigin(self) }
+  }
+
+  pub fn is_opaque(&self) -> bool {
+    unsafe { v8__Message__IsOpaque(self) }
+  }
+
+  /// Returns the origin of the script from which this Message was generated.
+  pub fn get_script_origin<'sc>(
+    &self,
+    _scope: &mut impl ToLocal<'sc>,
+  ) -> ScriptOrigin<'sc> {
+    unsafe {
+      let mut buf = MaybeUninit::<ScriptOrigin>::uninit();
+      v8__Message__GetScriptOrigin(self, &mut buf);
+      buf.assume_init()
+    }
+  }
+}
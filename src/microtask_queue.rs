@@ -0,0 +1,86 @@
+use crate::isolate::Isolate;
+use crate::support::int;
+use crate::support::Opaque;
+use crate::support::UniqueRef;
+use crate::Function;
+use crate::Local;
+use crate::MicrotasksPolicy;
+
+extern "C" {
+  fn v8__MicrotaskQueue__New(
+    isolate: *mut Isolate,
+    policy: MicrotasksPolicy,
+  ) -> *mut MicrotaskQueue;
+  fn v8__MicrotaskQueue__DELETE(this: *mut MicrotaskQueue);
+  fn v8__MicrotaskQueue__EnqueueMicrotask(
+    this: *mut MicrotaskQueue,
+    isolate: *mut Isolate,
+    microtask: *const Function,
+  );
+  fn v8__MicrotaskQueue__PerformCheckpoint(
+    this: *mut MicrotaskQueue,
+    isolate: *mut Isolate,
+  );
+  fn v8__MicrotaskQueue__IsRunningMicrotasks(
+    this: *const MicrotaskQueue,
+  ) -> bool;
+  fn v8__MicrotaskQueue__GetMicrotasksScopeDepth(
+    this: *const MicrotaskQueue,
+  ) -> int;
+}
+
+/// Represents the microtask queue, where microtasks are stored and processed.
+/// https://html.spec.whatwg.org/multipage/webappapis.html#microtask-queue
+/// https://html.spec.whatwg.org/multipage/webappapis.html#enqueuejob(queuename,-job,-arguments)
+/// https://html.spec.whatwg.org/multipage/webappapis.html#perform-a-microtask-checkpoint
+///
+/// A MicrotaskQueue instance may be associated to multiple Contexts by passing
+/// it to Context::new_with_microtask_queue(). The embedder must keep the
+/// MicrotaskQueue instance alive until all associated Contexts are gone.
+///
+/// Use the same instance of MicrotaskQueue for all Contexts that may access
+/// each other synchronously. E.g. for Web embedding, use the same instance for
+/// all origins that share the same URL scheme and eTLD+1.
+#[repr(C)]
+pub struct MicrotaskQueue(Opaque);
+
+impl MicrotaskQueue {
+  /// Creates an empty MicrotaskQueue instance.
+  pub fn new(
+    isolate: &mut Isolate,
+    policy: MicrotasksPolicy,
+  ) -> UniqueRef<Self> {
+    unsafe { UniqueRef::from_raw(v8__MicrotaskQueue__New(isolate, policy)) }
+  }
+
+  /// Enqueues the callback to the queue.
+  pub fn enqueue_microtask(
+    &mut self,
+    isolate: &mut Isolate,
+    microtask: Local<Function>,
+  ) {
+    unsafe { v8__MicrotaskQueue__EnqueueMicrotask(self, isolate, &*microtask) }
+  }
+
+  /// Runs microtasks if no microtask is running on this MicrotaskQueue instance.
+  pub fn perform_checkpoint(&mut self, isolate: &mut Isolate) {
+    unsafe { v8__MicrotaskQueue__PerformCheckpoint(self, isolate) }
+  }
+
+  /// Returns true if a microtask is running on this MicrotaskQueue instance.
+  pub fn is_running_microtasks(&self) -> bool {
+    unsafe { v8__MicrotaskQueue__IsRunningMicrotasks(self) }
+  }
+
+  /// Returns the current depth of nested MicrotasksScope that has
+  /// kRunMicrotasks.
+  pub fn get_microtasks_scope_depth(&self) -> int {
+    unsafe { v8__MicrotaskQueue__GetMicrotasksScopeDepth(self) }
+  }
+}
+
+impl Drop for MicrotaskQueue {
+  fn drop(&mut self) {
+    unsafe { v8__MicrotaskQueue__DELETE(self) }
+  }
+}
@@ -13,6 +13,7 @@ use crate::Local;
 use crate::Module;
 use crate::String;
 use crate::ToLocal;
+use crate::UnboundModuleScript;
 use crate::Value;
 
 /// Called during Module::instantiate_module. Provided with arguments:
@@ -95,6 +96,9 @@ extern "C" {
   ) -> Location;
   fn v8__Module__GetModuleNamespace(this: *const Module) -> *const Value;
   fn v8__Module__GetIdentityHash(this: *const Module) -> int;
+  fn v8__Module__GetUnboundModuleScript(
+    this: *const Module,
+  ) -> *const UnboundModuleScript;
   fn v8__Module__InstantiateModule(
     this: *const Module,
     context: *const Context,
@@ -188,6 +192,22 @@ impl Module {
     unsafe { v8__Module__GetIdentityHash(self) }
   }
 
+  /// Returns the underlying script's UnboundModuleScript, which can be used to
+  /// create a code cache for the module.
+  ///
+  /// The module must be unevaluated, i.e. its status must not be kEvaluating,
+  /// kEvaluated or kErrored.
+  pub fn get_unbound_module_script<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Local<'sc, UnboundModuleScript> {
+    unsafe {
+      scope
+        .to_local(v8__Module__GetUnboundModuleScript(self))
+        .unwrap()
+    }
+  }
+
   /// Returns the namespace object of this module.
   ///
   /// The module's status must be at least kInstantiated.
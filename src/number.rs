@@ -1,3 +1,8 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
 use crate::isolate::Isolate;
 use crate::Integer;
 use crate::Local;
@@ -15,6 +20,30 @@ extern "C" {
   fn v8__Integer__Value(this: *const Integer) -> i64;
 }
 
+/// The error returned by `Integer::value_checked()` when the integer is not a
+/// safe integer, i.e. its magnitude exceeds `Number.MAX_SAFE_INTEGER`, so the
+/// number it holds may not be the exact result of the computation that
+/// produced it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PrecisionLost {
+  value: f64,
+}
+
+impl PrecisionLost {
+  /// The value of the integer, as a double.
+  pub fn value(&self) -> f64 {
+    self.value
+  }
+}
+
+impl Display for PrecisionLost {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{} is not a safe integer", self.value)
+  }
+}
+
+impl Error for PrecisionLost {}
+
 impl Number {
   pub fn new<'sc>(
     scope: &mut impl ToLocal<'sc>,
@@ -24,9 +53,31 @@ impl Number {
     unsafe { scope.to_local(local) }.unwrap()
   }
 
+  // Convenience function not present in the original V8 API.
+  pub fn nan<'sc>(scope: &mut impl ToLocal<'sc>) -> Local<'sc, Number> {
+    Self::new(scope, f64::NAN)
+  }
+
+  // Convenience function not present in the original V8 API.
+  pub fn infinity<'sc>(scope: &mut impl ToLocal<'sc>) -> Local<'sc, Number> {
+    Self::new(scope, f64::INFINITY)
+  }
+
+  // Convenience function not present in the original V8 API.
+  pub fn negative_infinity<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Local<'sc, Number> {
+    Self::new(scope, f64::NEG_INFINITY)
+  }
+
   pub fn value(&self) -> f64 {
     unsafe { v8__Number__Value(self) }
   }
+
+  // Convenience function not present in the original V8 API.
+  pub fn is_nan(&self) -> bool {
+    self.value().is_nan()
+  }
 }
 
 impl Integer {
@@ -46,7 +97,22 @@ impl Integer {
     unsafe { scope.to_local(local) }.unwrap()
   }
 
+  /// The largest integer n such that n and n + 1 are both exactly
+  /// representable as a double. Same as `Number.MAX_SAFE_INTEGER`.
+  pub const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
   pub fn value(&self) -> i64 {
     unsafe { v8__Integer__Value(self) }
   }
+
+  /// Like `Integer::value()`, but returns an error if the integer is not a
+  /// safe integer, in which case it can't be relied upon to be exact.
+  // Convenience function not present in the original V8 API.
+  pub fn value_checked(&self) -> Result<i64, PrecisionLost> {
+    let value = Number::value(self);
+    if value.abs() > Self::MAX_SAFE_INTEGER as f64 {
+      return Err(PrecisionLost { value });
+    }
+    Ok(self.value())
+  }
 }
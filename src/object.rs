@@ -1,15 +1,26 @@
+use std::convert::TryFrom;
+use std::ffi::c_void;
+use std::ptr::null;
+use std::ptr::NonNull;
+
 use crate::isolate::Isolate;
+use crate::scope_traits::internal::GetRawIsolate;
 use crate::support::int;
 use crate::support::MapFnTo;
 use crate::support::MaybeBool;
 use crate::AccessorNameGetterCallback;
+use crate::AccessorNameSetterCallback;
 use crate::Array;
 use crate::Context;
+use crate::Function;
+use crate::Integer;
 use crate::Local;
 use crate::Map;
 use crate::Name;
+use crate::Number;
 use crate::Object;
 use crate::PropertyAttribute;
+use crate::String;
 use crate::ToLocal;
 use crate::Value;
 
@@ -28,6 +39,35 @@ extern "C" {
     key: *const Name,
     getter: AccessorNameGetterCallback,
   ) -> MaybeBool;
+  fn v8__Object__SetAccessor__with_configuration(
+    this: *const Object,
+    context: *const Context,
+    key: *const Name,
+    getter: AccessorNameGetterCallback,
+    setter: Option<AccessorNameSetterCallback>,
+    data: *const Value,
+    settings: AccessControl,
+    attr: PropertyAttribute,
+  ) -> MaybeBool;
+  fn v8__Object__SetNativeDataProperty(
+    this: *const Object,
+    context: *const Context,
+    key: *const Name,
+    getter: AccessorNameGetterCallback,
+  ) -> MaybeBool;
+  fn v8__Object__SetLazyDataProperty(
+    this: *const Object,
+    context: *const Context,
+    key: *const Name,
+    getter: AccessorNameGetterCallback,
+  ) -> MaybeBool;
+  fn v8__Object__SetAccessorProperty(
+    this: *const Object,
+    key: *const Name,
+    getter: *const Function,
+    setter: *const Function,
+    attr: PropertyAttribute,
+  );
   fn v8__Object__Get(
     this: *const Object,
     context: *const Context,
@@ -69,8 +109,27 @@ extern "C" {
     value: *const Value,
     attr: PropertyAttribute,
   ) -> MaybeBool;
+  fn v8__Object__HasOwnProperty(
+    this: *const Object,
+    context: *const Context,
+    key: *const Name,
+  ) -> MaybeBool;
   fn v8__Object__GetIdentityHash(this: *const Object) -> int;
+  fn v8__Object__GetConstructorName(this: *const Object) -> *const String;
   fn v8__Object__CreationContext(this: *const Object) -> *const Context;
+  fn v8__Object__CallAsFunction(
+    this: *const Object,
+    context: *const Context,
+    recv: *const Value,
+    argc: int,
+    argv: *const *const Value,
+  ) -> *const Value;
+  fn v8__Object__CallAsConstructor(
+    this: *const Object,
+    context: *const Context,
+    argc: int,
+    argv: *const *const Value,
+  ) -> *const Value;
   fn v8__Object__GetOwnPropertyNames(
     this: *const Object,
     context: *const Context,
@@ -79,6 +138,37 @@ extern "C" {
     this: *const Object,
     context: *const Context,
   ) -> *const Array;
+  fn v8__Object__GetPropertyNamesWithArgs(
+    this: *const Object,
+    context: *const Context,
+    mode: KeyCollectionMode,
+    property_filter: PropertyFilter,
+    index_filter: IndexFilter,
+    key_conversion: KeyConversionMode,
+  ) -> *const Array;
+  fn v8__Object__PreviewEntries(
+    this: *const Object,
+    is_key_value: *mut bool,
+  ) -> *const Array;
+  fn v8__Object__InternalFieldCount(this: *const Object) -> int;
+  fn v8__Object__GetInternalField(
+    this: *const Object,
+    index: int,
+  ) -> *const Value;
+  fn v8__Object__SetInternalField(
+    this: *const Object,
+    index: int,
+    value: *const Value,
+  );
+  fn v8__Object__GetAlignedPointerFromInternalField(
+    this: *const Object,
+    index: int,
+  ) -> *mut c_void;
+  fn v8__Object__SetAlignedPointerInInternalField(
+    this: *const Object,
+    index: int,
+    value: *mut c_void,
+  );
 
   fn v8__Array__New(isolate: *mut Isolate, length: int) -> *const Array;
   fn v8__Array__New_with_elements(
@@ -91,6 +181,91 @@ extern "C" {
   fn v8__Map__As__Array(this: *const Map) -> *const Array;
 }
 
+bitflags! {
+  /// Property filter bits. They can be or'ed to build a composite filter.
+  #[repr(transparent)]
+  pub struct PropertyFilter: int {
+    const ALL_PROPERTIES = 0;
+    const ONLY_WRITABLE = 1;
+    const ONLY_ENUMERABLE = 2;
+    const ONLY_CONFIGURABLE = 4;
+    const SKIP_STRINGS = 8;
+    const SKIP_SYMBOLS = 16;
+  }
+}
+
+/// Access control specifications.
+///
+/// Some accessors should be accessible across contexts. These accessors have
+/// an explicit access control parameter which specifies the kind of cross-
+/// context access that should be allowed.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessControl {
+  Default = 0,
+  AllCanRead = 1,
+  AllCanWrite = 1 << 1,
+  ProhibitsOverwriting = 1 << 2,
+}
+
+impl Default for AccessControl {
+  fn default() -> Self {
+    Self::Default
+  }
+}
+
+/// Limits the range of collected properties. `OwnOnly` limits the collected
+/// properties to the given Object only. `IncludePrototypes` will include all
+/// keys of the object's prototype chain as well.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCollectionMode {
+  OwnOnly,
+  IncludePrototypes,
+}
+
+/// `IncludeIndices` allows for integer indices to be collected, while
+/// `SkipIndices` will exclude integer indices from being collected.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexFilter {
+  IncludeIndices,
+  SkipIndices,
+}
+
+/// Controls how integer indices are returned: converted to strings, kept as
+/// numbers, or not returned at all.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyConversionMode {
+  ConvertToString,
+  KeepNumbers,
+  NoNumbers,
+}
+
+/// Options for `Object::get_property_names_with()` and
+/// `Object::get_own_property_names_with()`. The default value collects the
+/// same keys as `Object::get_own_property_names()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GetPropertyNamesArgs {
+  pub mode: KeyCollectionMode,
+  pub property_filter: PropertyFilter,
+  pub index_filter: IndexFilter,
+  pub key_conversion: KeyConversionMode,
+}
+
+impl Default for GetPropertyNamesArgs {
+  fn default() -> Self {
+    Self {
+      mode: KeyCollectionMode::OwnOnly,
+      property_filter: PropertyFilter::ONLY_ENUMERABLE
+        | PropertyFilter::SKIP_SYMBOLS,
+      index_filter: IndexFilter::IncludeIndices,
+      key_conversion: KeyConversionMode::KeepNumbers,
+    }
+  }
+}
+
 impl Object {
   /// Creates an empty object.
   pub fn new<'sc>(scope: &mut impl ToLocal<'sc>) -> Local<'sc, Object> {
@@ -124,6 +299,20 @@ impl Object {
     }
   }
 
+  /// Creates a JavaScript object with the given key-value pairs as its own
+  /// properties, and `Object.prototype` as its prototype. This is similar to
+  /// Object.fromEntries(). All properties will be created as enumerable,
+  /// configurable and writable properties.
+  // Convenience function not present in the original V8 API.
+  pub fn from_entries<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+    entries: &[(Local<Name>, Local<Value>)],
+  ) -> Local<'sc, Object> {
+    let prototype = Self::new(scope).get_prototype(scope).unwrap();
+    let (names, values): (Vec<_>, Vec<_>) = entries.iter().cloned().unzip();
+    Self::with_prototype_and_properties(scope, prototype, &names, &values)
+  }
+
   /// Set only return Just(true) or Empty(), so if it should never fail, use
   /// result.Check().
   pub fn set(
@@ -148,6 +337,10 @@ impl Object {
 
   /// Set the prototype object. This does not skip objects marked to be
   /// skipped by proto and it does not consult the security handler.
+  ///
+  /// Returns `Some(true)` on success. If the prototype can't be changed, e.g.
+  /// because the object has an immutable prototype or isn't extensible, the
+  /// TypeError that V8 creates is discarded and `None` is returned.
   pub fn set_prototype(
     &self,
     context: Local<Context>,
@@ -192,6 +385,17 @@ impl Object {
     .into()
   }
 
+  /// Returns true if the object has an own property with the given key.
+  /// Unlike a regular property lookup, this does not walk the prototype
+  /// chain. Implements Object.prototype.hasOwnProperty().
+  pub fn has_own_property(
+    &self,
+    context: Local<Context>,
+    key: Local<Name>,
+  ) -> Option<bool> {
+    unsafe { v8__Object__HasOwnProperty(self, &*context, &*key) }.into()
+  }
+
   pub fn get<'a>(
     &self,
     scope: &mut impl ToLocal<'a>,
@@ -204,6 +408,23 @@ impl Object {
     }
   }
 
+  /// Like `get()`, but casts the value of the property to `T`. Returns `None`
+  /// if an exception was thrown, or if the value is not of type `T`.
+  // Convenience function not present in the original V8 API.
+  pub fn get_typed<'a, T>(
+    &self,
+    scope: &mut impl ToLocal<'a>,
+    context: Local<Context>,
+    key: Local<Value>,
+  ) -> Option<Local<'a, T>>
+  where
+    Local<'a, T>: TryFrom<Local<'a, Value>>,
+  {
+    self
+      .get(scope, context, key)
+      .and_then(|value| Local::<T>::try_from(value).ok())
+  }
+
   pub fn get_index<'a>(
     &self,
     scope: &mut impl ToLocal<'a>,
@@ -241,6 +462,121 @@ impl Object {
     .into()
   }
 
+  /// Like `set_accessor()`, but also allows a setter, the data that is passed
+  /// to the callbacks in `PropertyCallbackArguments::data()`, the access
+  /// control settings and the attributes of the property to be specified.
+  #[allow(clippy::too_many_arguments)]
+  pub fn set_accessor_with_configuration(
+    &self,
+    context: Local<Context>,
+    name: Local<Name>,
+    getter: impl for<'s> MapFnTo<AccessorNameGetterCallback<'s>>,
+    setter: Option<AccessorNameSetterCallback>,
+    data: Option<Local<Value>>,
+    settings: AccessControl,
+    attr: PropertyAttribute,
+  ) -> Option<bool> {
+    unsafe {
+      v8__Object__SetAccessor__with_configuration(
+        self,
+        &*context,
+        &*name,
+        getter.map_fn_to(),
+        setter,
+        data.map(|d| &*d as *const _).unwrap_or(null()),
+        settings,
+        attr,
+      )
+    }
+    .into()
+  }
+
+  /// Installs a native getter and setter pair for the property `name` with
+  /// the given attributes. This is a shorthand for
+  /// `set_accessor_with_configuration()` without callback data.
+  // Convenience function not present in the original V8 API.
+  pub fn define_native_accessor(
+    &self,
+    context: Local<Context>,
+    name: Local<Name>,
+    getter: impl for<'s> MapFnTo<AccessorNameGetterCallback<'s>>,
+    setter: impl for<'s> MapFnTo<AccessorNameSetterCallback<'s>>,
+    attr: PropertyAttribute,
+  ) -> Option<bool> {
+    self.set_accessor_with_configuration(
+      context,
+      name,
+      getter,
+      Some(setter.map_fn_to()),
+      None,
+      AccessControl::Default,
+      attr,
+    )
+  }
+
+  /// Sets a native data property, i.e. a property that looks like a regular
+  /// data property to JavaScript but whose value is computed by calling
+  /// `getter` every time it is read.
+  pub fn set_native_data_property(
+    &self,
+    context: Local<Context>,
+    name: Local<Name>,
+    getter: impl for<'s> MapFnTo<AccessorNameGetterCallback<'s>>,
+  ) -> Option<bool> {
+    unsafe {
+      v8__Object__SetNativeDataProperty(
+        self,
+        &*context,
+        &*name,
+        getter.map_fn_to(),
+      )
+    }
+    .into()
+  }
+
+  /// Attempts to create a property with the given name which behaves like a
+  /// data property, except that the provided getter is invoked (and provided
+  /// with the data value) to supply its actual value on first access. The
+  /// value returned by the getter replaces the property, so the getter is
+  /// called at most once.
+  pub fn set_lazy_data_property(
+    &self,
+    context: Local<Context>,
+    name: Local<Name>,
+    getter: impl for<'s> MapFnTo<AccessorNameGetterCallback<'s>>,
+  ) -> Option<bool> {
+    unsafe {
+      v8__Object__SetLazyDataProperty(
+        self,
+        &*context,
+        &*name,
+        getter.map_fn_to(),
+      )
+    }
+    .into()
+  }
+
+  /// Sets a JavaScript getter and, optionally, setter function as the
+  /// accessor pair for the property `name`. This is the equivalent of
+  /// `Object.defineProperty()` with `get` and `set` functions.
+  pub fn set_accessor_property(
+    &self,
+    name: Local<Name>,
+    getter: Local<Function>,
+    setter: Option<Local<Function>>,
+    attr: PropertyAttribute,
+  ) {
+    unsafe {
+      v8__Object__SetAccessorProperty(
+        self,
+        &*name,
+        &*getter,
+        setter.map(|s| &*s as *const _).unwrap_or(null()),
+        attr,
+      )
+    }
+  }
+
   /// Returns the identity hash for this object. The current implementation
   /// uses a hidden property on the object to store the identity hash.
   ///
@@ -250,6 +586,15 @@ impl Object {
     unsafe { v8__Object__GetIdentityHash(self) }
   }
 
+  /// Returns the name of the function invoked as a constructor for this
+  /// object.
+  pub fn get_constructor_name<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Local<'sc, String> {
+    unsafe { scope.to_local(v8__Object__GetConstructorName(self)) }.unwrap()
+  }
+
   /// Returns the context in which the object was created.
   pub fn creation_context<'a>(
     &self,
@@ -261,6 +606,61 @@ impl Object {
     }
   }
 
+  /// Returns the isolate that the object belongs to.
+  ///
+  /// The returned pointer must not be dereferenced while another
+  /// `&mut Isolate` reference to the same isolate is alive (e.g. one held by
+  /// a scope further up the stack).
+  // Convenience function not present in the original V8 API.
+  pub fn get_isolate(&self) -> NonNull<Isolate> {
+    NonNull::new(self.get_raw_isolate()).unwrap()
+  }
+
+  /// Call an Object as a function if a callback is set by the
+  /// ObjectTemplate::SetCallAsFunctionHandler method.
+  pub fn call_as_function<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+    context: Local<Context>,
+    recv: Local<Value>,
+    args: &[Local<Value>],
+  ) -> Option<Local<'sc, Value>> {
+    let args = Local::slice_into_raw(args);
+    let argc = int::try_from(args.len()).unwrap();
+    let argv = args.as_ptr();
+    unsafe {
+      scope.to_local(v8__Object__CallAsFunction(
+        self, &*context, &*recv, argc, argv,
+      ))
+    }
+  }
+
+  /// Call an Object as a constructor if a callback is set by the
+  /// ObjectTemplate::SetCallAsFunctionHandler method.
+  /// Note: This method behaves like the Function::NewInstance method.
+  pub fn call_as_constructor<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+    context: Local<Context>,
+    args: &[Local<Value>],
+  ) -> Option<Local<'sc, Value>> {
+    let args = Local::slice_into_raw(args);
+    let argc = int::try_from(args.len()).unwrap();
+    let argv = args.as_ptr();
+    unsafe {
+      scope.to_local(v8__Object__CallAsConstructor(self, &*context, argc, argv))
+    }
+  }
+
+  /// Returns the context in which the object was created, or None if the
+  /// object doesn't have one, e.g. because it is a remote object.
+  pub fn get_creation_context<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Option<Local<'sc, Context>> {
+    unsafe { scope.to_local(v8__Object__CreationContext(self)) }
+  }
+
   /// This function has the same functionality as GetPropertyNames but the
   /// returned array doesn't contain the names of properties from prototype
   /// objects.
@@ -272,6 +672,50 @@ impl Object {
     unsafe { scope.to_local(v8__Object__GetOwnPropertyNames(self, &*context)) }
   }
 
+  /// Like `get_own_property_names()`, but copies the names into `names`
+  /// instead of returning an array. The vector is cleared first, so its
+  /// allocation can be reused across calls. Only the Rust vector is reused:
+  /// V8 still creates a new array of names on every call, which is then read
+  /// element by element. Integer keys are converted to strings. Returns
+  /// `None` if an exception was thrown.
+  // Convenience function not present in the original V8 API.
+  pub fn collect_own_property_names<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+    context: Local<Context>,
+    names: &mut Vec<Local<'sc, Name>>,
+  ) -> Option<()> {
+    names.clear();
+    let args = GetPropertyNamesArgs {
+      key_conversion: KeyConversionMode::ConvertToString,
+      ..Default::default()
+    };
+    let array = self.get_own_property_names_with(scope, context, args)?;
+    names.reserve(array.length() as usize);
+    for index in 0..array.length() {
+      let name = array.get_index(scope, context, index)?;
+      names.push(Local::<Name>::try_from(name).ok()?);
+    }
+    Some(())
+  }
+
+  /// Returns an array containing the names of the properties of this object
+  /// that pass the filters in `args`. Unlike `get_own_property_names()`, this
+  /// can be used to collect symbol keys and non-enumerable properties. Keys
+  /// from prototype objects are never included, regardless of `args.mode`.
+  pub fn get_own_property_names_with<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+    context: Local<Context>,
+    args: GetPropertyNamesArgs,
+  ) -> Option<Local<'sc, Array>> {
+    let args = GetPropertyNamesArgs {
+      mode: KeyCollectionMode::OwnOnly,
+      ..args
+    };
+    self.get_property_names_with(scope, context, args)
+  }
+
   /// Returns an array containing the names of the filtered properties of this
   /// object, including properties from prototype objects. The array returned by
   /// this method contains the same values as would be enumerated by a for-in
@@ -283,6 +727,102 @@ impl Object {
   ) -> Option<Local<'sc, Array>> {
     unsafe { scope.to_local(v8__Object__GetPropertyNames(self, &*context)) }
   }
+
+  /// Returns an array containing the names of the properties of this object
+  /// that pass the filters in `args`. If `args.mode` is
+  /// `KeyCollectionMode::IncludePrototypes`, the keys of the objects on the
+  /// prototype chain are collected as well.
+  pub fn get_property_names_with<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+    context: Local<Context>,
+    args: GetPropertyNamesArgs,
+  ) -> Option<Local<'sc, Array>> {
+    unsafe {
+      scope.to_local(v8__Object__GetPropertyNamesWithArgs(
+        self,
+        &*context,
+        args.mode,
+        args.property_filter,
+        args.index_filter,
+        args.key_conversion,
+      ))
+    }
+  }
+
+  /// If this object is a Set, Map, WeakSet or WeakMap, or an iterator over
+  /// one of those, returns an array with a snapshot of its entries, together
+  /// with a flag that indicates whether the array contains key/value pairs.
+  /// If the flag is true, the array alternates between keys and values.
+  /// Returns None for any other kind of object.
+  pub fn preview_entries<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Option<(Local<'sc, Array>, bool)> {
+    let mut is_key_value = false;
+    let ptr = unsafe { v8__Object__PreviewEntries(self, &mut is_key_value) };
+    unsafe { scope.to_local(ptr) }.map(|entries| (entries, is_key_value))
+  }
+
+  /// Gets the number of internal fields for this Object.
+  pub fn internal_field_count(&self) -> int {
+    unsafe { v8__Object__InternalFieldCount(self) }
+  }
+
+  /// Gets the value from an internal field. The index must be less than
+  /// `internal_field_count()`; this is checked in debug builds.
+  pub fn get_internal_field<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+    index: int,
+  ) -> Local<'sc, Value> {
+    self.debug_check_internal_field_index(index);
+    unsafe { scope.to_local(v8__Object__GetInternalField(self, index)) }
+      .unwrap()
+  }
+
+  /// Sets the value in an internal field. The index must be less than
+  /// `internal_field_count()`; this is checked in debug builds.
+  pub fn set_internal_field(&self, index: int, value: Local<Value>) {
+    self.debug_check_internal_field_index(index);
+    unsafe { v8__Object__SetInternalField(self, index, &*value) }
+  }
+
+  /// Gets a 2-byte-aligned native pointer from an internal field. This field
+  /// must have been set by `set_aligned_pointer_in_internal_field()`,
+  /// everything else leads to undefined behavior.
+  pub unsafe fn get_aligned_pointer_from_internal_field(
+    &self,
+    index: int,
+  ) -> *mut c_void {
+    self.debug_check_internal_field_index(index);
+    v8__Object__GetAlignedPointerFromInternalField(self, index)
+  }
+
+  /// Sets a 2-byte-aligned native pointer in an internal field. To retrieve
+  /// such a field, `get_aligned_pointer_from_internal_field()` must be used.
+  pub fn set_aligned_pointer_in_internal_field(
+    &self,
+    index: int,
+    value: *mut c_void,
+  ) {
+    self.debug_check_internal_field_index(index);
+    debug_assert!(
+      value as usize & 1 == 0,
+      "pointer is not 2-byte-aligned: {:?}",
+      value
+    );
+    unsafe { v8__Object__SetAlignedPointerInInternalField(self, index, value) }
+  }
+
+  fn debug_check_internal_field_index(&self, index: int) {
+    debug_assert!(
+      index >= 0 && index < self.internal_field_count(),
+      "internal field index out of bounds: the count is {} but the index is {}",
+      self.internal_field_count(),
+      index
+    );
+  }
 }
 
 impl Array {
@@ -316,9 +856,67 @@ impl Array {
     unsafe { scope.to_local(ptr) }.unwrap()
   }
 
+  /// Creates a JavaScript array containing the given numbers.
+  // Convenience function not present in the original V8 API.
+  pub fn new_from_f64<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+    elements: &[f64],
+  ) -> Local<'sc, Array> {
+    let elements = elements
+      .iter()
+      .map(|&value| Number::new(scope, value).into())
+      .collect::<Vec<Local<Value>>>();
+    Self::new_with_elements(scope, &elements)
+  }
+
+  /// Creates a JavaScript array containing the given integers.
+  // Convenience function not present in the original V8 API.
+  pub fn new_from_i32<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+    elements: &[i32],
+  ) -> Local<'sc, Array> {
+    let elements = elements
+      .iter()
+      .map(|&value| Integer::new(scope, value).into())
+      .collect::<Vec<Local<Value>>>();
+    Self::new_with_elements(scope, &elements)
+  }
+
   pub fn length(&self) -> u32 {
     unsafe { v8__Array__Length(self) }
   }
+
+  /// Shortens the array to `new_length` elements by setting its `length`
+  /// property, removing the elements at and beyond `new_length`. Like
+  /// `Vec::truncate()`, this has no effect if `new_length` is greater than or
+  /// equal to the current length. Returns `None` if an exception was thrown.
+  // Convenience function not present in the original V8 API.
+  pub fn truncate<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+    new_length: u32,
+  ) -> Option<bool> {
+    if new_length >= self.length() {
+      return Some(true);
+    }
+    let context = scope.get_current_context()?;
+    let key = String::new(scope, "length").unwrap();
+    let value = Integer::new_from_unsigned(scope, new_length);
+    self.set(context, key.into(), value.into())
+  }
+
+  /// Copies all elements of the array into a `Vec`. Returns `None` if an
+  /// exception was thrown while reading the elements.
+  // Convenience function not present in the original V8 API.
+  pub fn to_vec<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Option<Vec<Local<'sc, Value>>> {
+    let context = scope.get_current_context()?;
+    (0..self.length())
+      .map(|index| self.get_index(scope, context, index))
+      .collect()
+  }
 }
 
 impl Map {
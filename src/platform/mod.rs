@@ -2,6 +2,9 @@ pub mod task;
 
 pub use task::{Task, TaskBase, TaskImpl};
 
+use std::convert::TryFrom;
+
+use crate::support::int;
 use crate::support::Opaque;
 use crate::support::UniquePtr;
 use crate::Isolate;
@@ -9,8 +12,23 @@ use crate::Isolate;
 extern "C" {
   // TODO: move this to libplatform.rs?
   fn v8__platform__NewDefaultPlatform() -> *mut Platform;
+  fn v8__platform__NewDefaultPlatform__with(
+    thread_pool_size: int,
+    idle_task_support: bool,
+  ) -> *mut Platform;
 
   fn v8__Platform__DELETE(this: *mut Platform);
+
+  fn v8__platform__PumpMessageLoop(
+    platform: *const Platform,
+    isolate: *mut Isolate,
+    wait_for_work: bool,
+  ) -> bool;
+  fn v8__platform__RunIdleTasks(
+    platform: *const Platform,
+    isolate: *mut Isolate,
+    idle_time_in_seconds: f64,
+  );
 }
 
 pub fn new_default_platform() -> UniquePtr<Platform> {
@@ -18,6 +36,24 @@ pub fn new_default_platform() -> UniquePtr<Platform> {
   unsafe { UniquePtr::from_raw(v8__platform__NewDefaultPlatform()) }
 }
 
+/// Creates a default platform like `new_default_platform()`, but with the
+/// given number of worker threads. If `thread_pool_size` is 0, the number of
+/// worker threads is chosen based on the number of processors. If
+/// `idle_task_support` is true, idle tasks can be run with
+/// `Platform::run_idle_tasks()`.
+pub fn new_default_platform_with(
+  thread_pool_size: u32,
+  idle_task_support: bool,
+) -> UniquePtr<Platform> {
+  let thread_pool_size = int::try_from(thread_pool_size).unwrap();
+  unsafe {
+    UniquePtr::from_raw(v8__platform__NewDefaultPlatform__with(
+      thread_pool_size,
+      idle_task_support,
+    ))
+  }
+}
+
 #[repr(C)]
 pub struct Platform(Opaque);
 
@@ -32,9 +68,29 @@ impl Platform {
   ///
   /// The caller has to make sure that this is called from the right thread.
   /// Returns true if a task was executed, and false otherwise. Unless requested
-  /// through the |behavior| parameter, this call does not block if no task is
-  /// pending. The |platform| has to be created using |NewDefaultPlatform|.
-  pub fn pump_message_loop(_platform: &Self, _isolate: &Isolate) -> bool {
-    todo!()
+  /// through the `wait_for_work` parameter, this call does not block if no
+  /// task is pending. The `platform` has to be created using
+  /// `new_default_platform()`.
+  pub fn pump_message_loop(
+    platform: &Self,
+    isolate: &mut Isolate,
+    wait_for_work: bool,
+  ) -> bool {
+    unsafe { v8__platform__PumpMessageLoop(platform, isolate, wait_for_work) }
+  }
+
+  /// Runs pending idle tasks for at most `idle_time_in_seconds` seconds.
+  ///
+  /// The caller has to make sure that this is called from the right thread.
+  /// The `platform` has to be created using `new_default_platform_with()`
+  /// with idle task support enabled.
+  pub fn run_idle_tasks(
+    platform: &Self,
+    isolate: &mut Isolate,
+    idle_time_in_seconds: f64,
+  ) {
+    unsafe {
+      v8__platform__RunIdleTasks(platform, isolate, idle_time_in_seconds)
+    }
   }
 }
@@ -1,4 +1,6 @@
 // Copyright 2019-2020 the Deno authors. All rights reserved. MIT license.
+use std::convert::TryInto;
+
 use crate::support::int;
 use crate::Isolate;
 use crate::Local;
@@ -33,8 +35,8 @@ impl PrimitiveArray {
     scope: &mut impl ToLocal<'sc>,
     length: usize,
   ) -> Local<'sc, PrimitiveArray> {
-    let ptr =
-      unsafe { v8__PrimitiveArray__New(scope.isolate(), length as int) };
+    let length: int = length.try_into().expect("length out of range");
+    let ptr = unsafe { v8__PrimitiveArray__New(scope.isolate(), length) };
     unsafe { scope.to_local(ptr) }.unwrap()
   }
 
@@ -42,24 +44,43 @@ impl PrimitiveArray {
     unsafe { v8__PrimitiveArray__Length(self) as usize }
   }
 
+  /// Sets the item at `index`.
+  ///
+  /// Panics in debug builds if `index` is out of bounds. In release builds,
+  /// passing an out of bounds index causes V8 to abort the process.
   pub fn set<'sc>(
     &self,
     scope: &mut impl ToLocal<'sc>,
     index: usize,
     item: Local<'_, Primitive>,
   ) {
+    self.debug_check_index(index);
     unsafe {
       v8__PrimitiveArray__Set(self, scope.isolate(), index as int, &*item)
     }
   }
 
+  /// Returns the item at `index`.
+  ///
+  /// Panics in debug builds if `index` is out of bounds. In release builds,
+  /// passing an out of bounds index causes V8 to abort the process.
   pub fn get<'sc>(
     &self,
     scope: &mut impl ToLocal<'sc>,
     index: usize,
   ) -> Local<'sc, Primitive> {
+    self.debug_check_index(index);
     let ptr =
       unsafe { v8__PrimitiveArray__Get(self, scope.isolate(), index as int) };
     unsafe { scope.to_local(ptr) }.unwrap()
   }
+
+  fn debug_check_index(&self, index: usize) {
+    debug_assert!(
+      index < self.length(),
+      "index out of bounds: the length is {} but the index is {}",
+      self.length(),
+      index
+    );
+  }
 }
@@ -9,6 +9,7 @@ extern "C" {
   fn v8__Undefined(isolate: *mut Isolate) -> *const Primitive;
 
   fn v8__Boolean__New(isolate: *mut Isolate, value: bool) -> *const Boolean;
+  fn v8__Boolean__Value(this: *const Boolean) -> bool;
 }
 
 pub fn null<'sc>(scope: &mut impl ToLocal<'sc>) -> Local<'sc, Primitive> {
@@ -29,4 +30,8 @@ impl Boolean {
     let ptr = unsafe { v8__Boolean__New(scope.isolate(), value) };
     unsafe { scope.to_local(ptr) }.unwrap()
   }
+
+  pub fn value(&self) -> bool {
+    unsafe { v8__Boolean__Value(self) }
+  }
 }
@@ -1,11 +1,19 @@
+use std::convert::TryInto;
+use std::ffi::c_void;
 use std::marker::PhantomData;
 
+use crate::isolate::Isolate;
 use crate::support::MaybeBool;
 use crate::Context;
+use crate::External;
 use crate::Function;
+use crate::FunctionCallbackArguments;
+use crate::FunctionCallbackScope;
+use crate::InIsolate;
 use crate::Local;
 use crate::Promise;
 use crate::PromiseResolver;
+use crate::ReturnValue;
 use crate::ToLocal;
 use crate::Value;
 
@@ -28,6 +36,7 @@ extern "C" {
   ) -> MaybeBool;
   fn v8__Promise__State(this: *const Promise) -> PromiseState;
   fn v8__Promise__HasHandler(this: *const Promise) -> bool;
+  fn v8__Promise__MarkAsHandled(this: *const Promise);
   fn v8__Promise__Result(this: *const Promise) -> *const Value;
   fn v8__Promise__Catch(
     this: *const Promise,
@@ -46,6 +55,13 @@ extern "C" {
     on_rejected: *const Function,
   ) -> *const Promise;
 
+  fn v8__Value__SetWeakFinalizer(
+    isolate: *mut Isolate,
+    this: *const Value,
+    finalizer: extern "C" fn(*mut c_void),
+    data: *mut c_void,
+  );
+
   fn v8__PromiseRejectMessage__GetPromise(
     this: *const PromiseRejectMessage,
   ) -> *const Promise;
@@ -77,6 +93,11 @@ impl Promise {
     unsafe { v8__Promise__HasHandler(&*self) }
   }
 
+  /// Marks this promise as handled to avoid reporting unhandled rejections.
+  pub fn mark_as_handled(&self) {
+    unsafe { v8__Promise__MarkAsHandled(&*self) }
+  }
+
   /// Returns the content of the [[PromiseResult]] field. The Promise must not
   /// be pending.
   pub fn result<'sc>(
@@ -130,6 +151,70 @@ impl Promise {
       ))
     }
   }
+
+  /// Register a Rust closure as the resolution handler of a promise. The
+  /// closure is called at most once, with the resolution value as its
+  /// argument. It is dropped once the handler function that wraps it has been
+  /// garbage collected. If the isolate is disposed before that happens, the
+  /// closure is leaked instead.
+  ///
+  /// See `Self::then`.
+  // Convenience function not present in the original V8 API.
+  pub fn then_rust<'sc, F>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+    context: Local<Context>,
+    on_fulfilled: F,
+  ) -> Option<Local<'sc, Promise>>
+  where
+    F: for<'s> FnOnce(FunctionCallbackScope<'s>, Local<'s, Value>) + 'static,
+  {
+    let data = Box::into_raw(Box::new(Some(on_fulfilled))) as *mut c_void;
+    let external = External::new(scope, data);
+    let handler = Function::new_with_data(
+      scope,
+      context,
+      external.into(),
+      then_rust_callback::<F>,
+    );
+    let handler = match handler {
+      Some(handler) => handler,
+      None => {
+        drop_then_rust_data::<F>(data);
+        return None;
+      }
+    };
+    let handler_value: Local<Value> = handler.into();
+    unsafe {
+      v8__Value__SetWeakFinalizer(
+        scope.isolate(),
+        &*handler_value,
+        drop_then_rust_data::<F>,
+        data,
+      )
+    };
+    self.then(scope, context, handler)
+  }
+}
+
+fn then_rust_callback<F>(
+  scope: FunctionCallbackScope,
+  args: FunctionCallbackArguments,
+  _rv: ReturnValue,
+) where
+  F: for<'s> FnOnce(FunctionCallbackScope<'s>, Local<'s, Value>) + 'static,
+{
+  let external: Local<External> = args.data().unwrap().try_into().unwrap();
+  let on_fulfilled = unsafe { &mut *(external.value() as *mut Option<F>) };
+  if let Some(on_fulfilled) = on_fulfilled.take() {
+    let value =
+      unsafe { scope.to_local(&*args.get(0) as *const Value) }.unwrap();
+    on_fulfilled(scope, value);
+  }
+}
+
+extern "C" fn drop_then_rust_data<F>(data: *mut c_void) {
+  drop(unsafe { Box::from_raw(data as *mut Option<F>) })
 }
 
 impl PromiseResolver {
@@ -6,6 +6,7 @@ use crate::CallbackScope;
 use crate::Context;
 use crate::ContextScope;
 use crate::EscapableHandleScope;
+use crate::Exception;
 use crate::FunctionCallbackInfo;
 use crate::HandleScope;
 use crate::Isolate;
@@ -13,6 +14,8 @@ use crate::Local;
 use crate::Message;
 use crate::Object;
 use crate::PropertyCallbackInfo;
+use crate::String;
+use crate::Value;
 
 pub(crate) mod internal {
   use super::*;
@@ -247,6 +250,36 @@ where
   ) -> Option<Local<'p, Context>> {
     <Self as ToLocal<'p>>::get_entered_or_microtask_context(self)
   }
+
+  /// Creates an `Error` with the given message and throws it. Returns the
+  /// `undefined` value, like `Isolate::throw_exception()`.
+  // Convenience function not present in the original V8 API.
+  pub fn throw_error(&mut self, message: &str) -> Local<'p, Value> {
+    self.throw_new_error(message, Exception::error)
+  }
+
+  /// Creates a `TypeError` with the given message and throws it.
+  // Convenience function not present in the original V8 API.
+  pub fn throw_type_error(&mut self, message: &str) -> Local<'p, Value> {
+    self.throw_new_error(message, Exception::type_error)
+  }
+
+  /// Creates a `RangeError` with the given message and throws it.
+  // Convenience function not present in the original V8 API.
+  pub fn throw_range_error(&mut self, message: &str) -> Local<'p, Value> {
+    self.throw_new_error(message, Exception::range_error)
+  }
+
+  fn throw_new_error(
+    &mut self,
+    message: &str,
+    new_error: fn(&mut Self, Local<String>) -> Local<'p, Value>,
+  ) -> Local<'p, Value> {
+    let message = String::new(self, message).unwrap();
+    let exception = new_error(self, message);
+    let undefined = &*self.isolate().throw_exception(exception) as *const _;
+    unsafe { self.to_local(undefined) }.unwrap()
+  }
 }
 
 impl<'s, 'p: 's, S, P> Entered<'s, S, P>
@@ -2,10 +2,13 @@ use std::marker::PhantomData;
 use std::mem::MaybeUninit;
 use std::ptr::null;
 
+use crate::support::int;
+use crate::undefined;
 use crate::Boolean;
 use crate::Context;
 use crate::Integer;
 use crate::Local;
+use crate::PrimitiveArray;
 use crate::Script;
 use crate::String;
 use crate::ToLocal;
@@ -37,7 +40,14 @@ extern "C" {
     resource_is_opaque: *const Boolean,
     is_wasm: *const Boolean,
     is_module: *const Boolean,
+    host_defined_options: *const PrimitiveArray,
   );
+  fn v8__ScriptOrigin__ResourceName(this: *const ScriptOrigin) -> *const Value;
+  fn v8__ScriptOrigin__ScriptID(this: *const ScriptOrigin) -> int;
+  fn v8__ScriptOrigin__IsSharedCrossOrigin(this: *const ScriptOrigin) -> bool;
+  fn v8__ScriptOrigin__IsOpaque(this: *const ScriptOrigin) -> bool;
+  fn v8__ScriptOrigin__IsWasm(this: *const ScriptOrigin) -> bool;
+  fn v8__ScriptOrigin__IsModule(this: *const ScriptOrigin) -> bool;
 }
 
 impl Script {
@@ -83,6 +93,36 @@ impl<'sc> ScriptOrigin<'sc> {
     resource_is_opaque: Local<'sc, Boolean>,
     is_wasm: Local<'sc, Boolean>,
     is_module: Local<'sc, Boolean>,
+  ) -> Self {
+    Self::new_with_host_defined_options(
+      resource_name,
+      resource_line_offset,
+      resource_column_offset,
+      resource_is_shared_cross_origin,
+      script_id,
+      source_map_url,
+      resource_is_opaque,
+      is_wasm,
+      is_module,
+      None,
+    )
+  }
+
+  /// Like `ScriptOrigin::new()`, but also sets the options that are passed
+  /// back to the embedder as `ScriptOrModule::get_host_defined_options()`,
+  /// e.g. in the dynamic import callback.
+  #[allow(clippy::too_many_arguments)]
+  pub fn new_with_host_defined_options(
+    resource_name: Local<'sc, Value>,
+    resource_line_offset: Local<'sc, Integer>,
+    resource_column_offset: Local<'sc, Integer>,
+    resource_is_shared_cross_origin: Local<'sc, Boolean>,
+    script_id: Local<'sc, Integer>,
+    source_map_url: Local<'sc, Value>,
+    resource_is_opaque: Local<'sc, Boolean>,
+    is_wasm: Local<'sc, Boolean>,
+    is_module: Local<'sc, Boolean>,
+    host_defined_options: Option<Local<'sc, PrimitiveArray>>,
   ) -> Self {
     unsafe {
       let mut buf = std::mem::MaybeUninit::<ScriptOrigin>::uninit();
@@ -97,8 +137,153 @@ impl<'sc> ScriptOrigin<'sc> {
         &*resource_is_opaque,
         &*is_wasm,
         &*is_module,
+        host_defined_options
+          .map(|o| &*o as *const _)
+          .unwrap_or(null()),
       );
       buf.assume_init()
     }
   }
+
+  pub fn resource_name(&self) -> Option<Local<'sc, Value>> {
+    unsafe { Local::from_raw(v8__ScriptOrigin__ResourceName(self)) }
+  }
+
+  /// Returns the script id, or Message::kNoScriptIdInfo if it is not known.
+  pub fn script_id(&self) -> int {
+    unsafe { v8__ScriptOrigin__ScriptID(self) }
+  }
+
+  pub fn is_shared_cross_origin(&self) -> bool {
+    unsafe { v8__ScriptOrigin__IsSharedCrossOrigin(self) }
+  }
+
+  pub fn is_opaque(&self) -> bool {
+    unsafe { v8__ScriptOrigin__IsOpaque(self) }
+  }
+
+  pub fn is_wasm(&self) -> bool {
+    unsafe { v8__ScriptOrigin__IsWasm(self) }
+  }
+
+  pub fn is_module(&self) -> bool {
+    unsafe { v8__ScriptOrigin__IsModule(self) }
+  }
+}
+
+/// Builder for `ScriptOrigin`. Fields that are not explicitly set take the
+/// same defaults that V8 uses: an undefined resource name and source map url,
+/// zero line and column offsets, a script id of -1, all flags unset and no
+/// host defined options.
+#[must_use]
+pub struct ScriptOriginBuilder<'sc> {
+  resource_name: Option<Local<'sc, Value>>,
+  line_offset: i32,
+  column_offset: i32,
+  shared_cross_origin: bool,
+  script_id: i32,
+  source_map_url: Option<Local<'sc, Value>>,
+  is_opaque: bool,
+  is_wasm: bool,
+  is_module: bool,
+  host_defined_options: Option<Local<'sc, PrimitiveArray>>,
+}
+
+impl<'sc> Default for ScriptOriginBuilder<'sc> {
+  fn default() -> Self {
+    Self {
+      resource_name: None,
+      line_offset: 0,
+      column_offset: 0,
+      shared_cross_origin: false,
+      script_id: -1,
+      source_map_url: None,
+      is_opaque: false,
+      is_wasm: false,
+      is_module: false,
+      host_defined_options: None,
+    }
+  }
+}
+
+impl<'sc> ScriptOriginBuilder<'sc> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn resource_name(mut self, resource_name: Local<'sc, Value>) -> Self {
+    self.resource_name = Some(resource_name);
+    self
+  }
+
+  pub fn line_offset(mut self, line_offset: i32) -> Self {
+    self.line_offset = line_offset;
+    self
+  }
+
+  pub fn column_offset(mut self, column_offset: i32) -> Self {
+    self.column_offset = column_offset;
+    self
+  }
+
+  pub fn shared_cross_origin(mut self, shared_cross_origin: bool) -> Self {
+    self.shared_cross_origin = shared_cross_origin;
+    self
+  }
+
+  pub fn script_id(mut self, script_id: i32) -> Self {
+    self.script_id = script_id;
+    self
+  }
+
+  pub fn source_map_url(mut self, source_map_url: Local<'sc, Value>) -> Self {
+    self.source_map_url = Some(source_map_url);
+    self
+  }
+
+  pub fn is_opaque(mut self, is_opaque: bool) -> Self {
+    self.is_opaque = is_opaque;
+    self
+  }
+
+  pub fn is_wasm(mut self, is_wasm: bool) -> Self {
+    self.is_wasm = is_wasm;
+    self
+  }
+
+  pub fn is_module(mut self, is_module: bool) -> Self {
+    self.is_module = is_module;
+    self
+  }
+
+  pub fn host_defined_options(
+    mut self,
+    host_defined_options: Local<'sc, PrimitiveArray>,
+  ) -> Self {
+    self.host_defined_options = Some(host_defined_options);
+    self
+  }
+
+  pub fn build(self, scope: &mut impl ToLocal<'sc>) -> ScriptOrigin<'sc> {
+    let resource_name = match self.resource_name {
+      Some(v) => v,
+      None => undefined(scope).into(),
+    };
+    let source_map_url = match self.source_map_url {
+      Some(v) => v,
+      None => undefined(scope).into(),
+    };
+    ScriptOrigin::new_with_host_defined_options(
+      resource_name,
+      Integer::new(scope, self.line_offset),
+      Integer::new(scope, self.column_offset),
+      Boolean::new(scope, self.shared_cross_origin),
+      Integer::new(scope, self.script_id),
+      source_map_url,
+      Boolean::new(scope, self.is_opaque),
+      Boolean::new(scope, self.is_wasm),
+      Boolean::new(scope, self.is_module),
+      self.host_defined_options,
+    )
+  }
 }
@@ -1,6 +1,12 @@
 // Copyright 2019-2020 the Deno authors. All rights reserved. MIT license.
+use std::convert::TryFrom;
 use std::mem::MaybeUninit;
+use std::ops::Deref;
+use std::ptr::null_mut;
+use std::slice;
 
+use crate::support::int;
+use crate::support::UniqueRef;
 use crate::Isolate;
 use crate::Local;
 use crate::Module;
@@ -13,8 +19,18 @@ extern "C" {
     buf: *mut MaybeUninit<Source>,
     source_string: *const String,
     origin: *const ScriptOrigin,
+    cached_data: *mut CachedData,
   );
   fn v8__ScriptCompiler__Source__DESTRUCT(this: *mut Source);
+  fn v8__ScriptCompiler__Source__GetCachedData(
+    this: *const Source,
+  ) -> *const CachedData;
+
+  fn v8__ScriptCompiler__CachedData__NEW(
+    data: *const u8,
+    length: int,
+  ) -> *mut CachedData;
+  fn v8__ScriptCompiler__CachedData__DELETE(this: *mut CachedData);
 
   fn v8__ScriptCompiler__CompileModule(
     isolate: *mut Isolate,
@@ -29,14 +45,49 @@ extern "C" {
 pub struct Source([usize; 8]);
 
 impl Source {
-  // TODO(ry) cached_data
   pub fn new(source_string: Local<String>, origin: &ScriptOrigin) -> Self {
     let mut buf = MaybeUninit::<Self>::uninit();
     unsafe {
-      v8__ScriptCompiler__Source__CONSTRUCT(&mut buf, &*source_string, origin);
+      v8__ScriptCompiler__Source__CONSTRUCT(
+        &mut buf,
+        &*source_string,
+        origin,
+        null_mut(),
+      );
       buf.assume_init()
     }
   }
+
+  /// Creates a Source that carries a code cache, to be used together with
+  /// `CompileOptions::ConsumeCodeCache`. The Source takes ownership of the
+  /// cached data.
+  pub fn new_with_cached_data(
+    source_string: Local<String>,
+    origin: &ScriptOrigin,
+    cached_data: UniqueRef<CachedData>,
+  ) -> Self {
+    let mut buf = MaybeUninit::<Self>::uninit();
+    unsafe {
+      v8__ScriptCompiler__Source__CONSTRUCT(
+        &mut buf,
+        &*source_string,
+        origin,
+        cached_data.into_raw(),
+      );
+      buf.assume_init()
+    }
+  }
+
+  /// Returns the cached data that was passed to
+  /// `Source::new_with_cached_data()`, if any. After compiling with
+  /// `CompileOptions::ConsumeCodeCache`, `CachedData::rejected()` reports
+  /// whether V8 was able to use it.
+  pub fn get_cached_data(&self) -> Option<&CachedData> {
+    unsafe {
+      let ptr = v8__ScriptCompiler__Source__GetCachedData(self);
+      ptr.as_ref()
+    }
+  }
 }
 
 impl Drop for Source {
@@ -45,6 +96,61 @@ impl Drop for Source {
   }
 }
 
+/// Compilation data that the embedder can cache and pass back to speed up
+/// future compilations. A code cache for a module can be created with
+/// `UnboundModuleScript::create_code_cache()`.
+#[repr(C)]
+pub struct CachedData {
+  data: *const u8,
+  length: int,
+  rejected: bool,
+  buffer_policy: BufferPolicy,
+}
+
+#[repr(C)]
+#[allow(dead_code)]
+enum BufferPolicy {
+  BufferNotOwned,
+  BufferOwned,
+}
+
+impl CachedData {
+  /// Creates a copy of `data` that can be passed to
+  /// `Source::new_with_cached_data()`.
+  pub fn new(data: &[u8]) -> UniqueRef<Self> {
+    let length = int::try_from(data.len()).unwrap();
+    unsafe {
+      UniqueRef::from_raw(v8__ScriptCompiler__CachedData__NEW(
+        data.as_ptr(),
+        length,
+      ))
+    }
+  }
+
+  /// Whether V8 rejected the cached data when it was consumed, e.g. because
+  /// it was produced by a different V8 version or for a different source.
+  pub fn rejected(&self) -> bool {
+    self.rejected
+  }
+}
+
+impl Deref for CachedData {
+  type Target = [u8];
+  fn deref(&self) -> &[u8] {
+    if self.data.is_null() {
+      &[]
+    } else {
+      unsafe { slice::from_raw_parts(self.data, self.length as usize) }
+    }
+  }
+}
+
+impl Drop for CachedData {
+  fn drop(&mut self) {
+    unsafe { v8__ScriptCompiler__CachedData__DELETE(self) }
+  }
+}
+
 #[repr(C)]
 pub enum CompileOptions {
   NoCompileOptions = 0,
@@ -106,3 +212,22 @@ pub fn compile_module2<'sc>(
     scope.to_local(ptr)
   }
 }
+
+/// Compiles a module from a Source created by
+/// `Source::new_with_cached_data()`, consuming its code cache. The Source is
+/// borrowed so that `CachedData::rejected()` can be checked afterwards.
+// Convenience function not present in the original V8 API.
+pub fn compile_module_with_cached_data<'sc>(
+  scope: &mut impl ToLocal<'sc>,
+  source: &mut Source,
+) -> Option<Local<'sc, Module>> {
+  unsafe {
+    let ptr = v8__ScriptCompiler__CompileModule(
+      scope.isolate(),
+      source,
+      CompileOptions::ConsumeCodeCache,
+      NoCacheReason::NoReason,
+    );
+    scope.to_local(ptr)
+  }
+}
@@ -1,23 +1,36 @@
 use crate::external_references::ExternalReferences;
+use crate::isolate_create_params::raw;
 use crate::support::char;
 use crate::support::int;
 use crate::support::intptr_t;
+use crate::support::Allocated;
+use crate::support::Allocation;
+use crate::support::MapFnFrom;
+use crate::support::MapFnTo;
+use crate::support::ToCFn;
+use crate::support::UnitType;
 use crate::Context;
+use crate::Data;
 use crate::Isolate;
 use crate::Local;
+use crate::Object;
 use crate::OwnedIsolate;
 
 use std::borrow::Borrow;
 use std::convert::TryFrom;
+use std::ffi::c_void;
+use std::mem::ManuallyDrop;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
+use std::ptr::null;
 
 extern "C" {
   fn v8__SnapshotCreator__CONSTRUCT(
-    buf: *mut MaybeUninit<SnapshotCreator>,
+    buf: *mut MaybeUninit<RawSnapshotCreator>,
     external_references: *const intptr_t,
+    existing_snapshot_blob: *const raw::StartupData,
   );
-  fn v8__SnapshotCreator__DESTRUCT(this: *mut SnapshotCreator);
+  fn v8__SnapshotCreator__DESTRUCT(this: *mut RawSnapshotCreator);
   fn v8__SnapshotCreator__GetIsolate(
     this: *mut SnapshotCreator,
   ) -> *mut Isolate;
@@ -29,7 +42,32 @@ extern "C" {
     this: *mut SnapshotCreator,
     context: *const Context,
   );
+  fn v8__SnapshotCreator__SetDefaultContext__with_serializer(
+    this: *mut SnapshotCreator,
+    context: *const Context,
+    serializer: SerializeInternalFieldsCallback,
+  );
+  fn v8__SnapshotCreator__AddContext(
+    this: *mut SnapshotCreator,
+    context: *const Context,
+  ) -> usize;
+  fn v8__SnapshotCreator__AddContext__with_serializer(
+    this: *mut SnapshotCreator,
+    context: *const Context,
+    serializer: SerializeInternalFieldsCallback,
+  ) -> usize;
+  fn v8__SnapshotCreator__AddData_to_isolate(
+    this: *mut SnapshotCreator,
+    data: *const Data,
+  ) -> usize;
+  fn v8__SnapshotCreator__AddData_to_context(
+    this: *mut SnapshotCreator,
+    context: *const Context,
+    data: *const Data,
+  ) -> usize;
   fn v8__StartupData__DESTRUCT(this: *mut StartupData);
+  fn v8__StartupData__CopyFrom(data: *const char, raw_size: int)
+    -> StartupData;
 }
 
 #[repr(C)]
@@ -38,11 +76,23 @@ pub struct StartupData {
   raw_size: int,
 }
 
+impl StartupData {
+  /// Copies `bytes` into a buffer allocated by C++, so that V8 can take
+  /// ownership of it.
+  fn copy_from(bytes: &[u8]) -> Self {
+    let raw_size = int::try_from(bytes.len()).unwrap();
+    unsafe { v8__StartupData__CopyFrom(bytes.as_ptr() as *const _, raw_size) }
+  }
+}
+
 impl Deref for StartupData {
   type Target = [u8];
   fn deref(&self) -> &Self::Target {
     let data = self.data as *const u8;
     let len = usize::try_from(self.raw_size).unwrap();
+    if len == 0 {
+      return &[];
+    }
     unsafe { std::slice::from_raw_parts(data, len) }
   }
 }
@@ -65,39 +115,125 @@ impl Drop for StartupData {
   }
 }
 
+/// Callback used to serialize the internal fields of objects that are
+/// included in a context snapshot, see
+/// `SnapshotCreator::add_context_with_serializer()`. The callback is invoked
+/// for every internal field, and the bytes it returns are passed to the
+/// matching `DeserializeInternalFieldsCallback` when the context is restored.
+/// Fields for which no data is returned are not passed to the deserializer.
+pub type SerializeInternalFieldsCallback<'s> =
+  extern "C" fn(Local<'s, Object>, int, *mut c_void) -> StartupData;
+
+impl<F> MapFnFrom<F> for SerializeInternalFieldsCallback<'_>
+where
+  F: UnitType + Fn(Local<Object>, int) -> Vec<u8>,
+{
+  fn mapping() -> Self {
+    let f = |holder: Local<Object>, index: int, _: *mut c_void| {
+      let payload = (F::get())(holder, index);
+      StartupData::copy_from(&payload)
+    };
+    f.to_c_fn()
+  }
+}
+
+/// Callback used to restore the internal fields of objects when a context is
+/// created from a snapshot, see `Context::from_snapshot_with_deserializer()`.
+/// The callback receives the bytes that `SerializeInternalFieldsCallback`
+/// returned for the field with the same index.
+pub type DeserializeInternalFieldsCallback<'s> =
+  extern "C" fn(Local<'s, Object>, int, ManuallyDrop<StartupData>, *mut c_void);
+
+impl<F> MapFnFrom<F> for DeserializeInternalFieldsCallback<'_>
+where
+  F: UnitType + Fn(Local<Object>, int, &[u8]),
+{
+  fn mapping() -> Self {
+    // The payload is owned by V8, so it must not be dropped here.
+    let f = |holder: Local<Object>,
+             index: int,
+             payload: ManuallyDrop<StartupData>,
+             _: *mut c_void| {
+      (F::get())(holder, index, &**payload);
+    };
+    f.to_c_fn()
+  }
+}
+
 #[repr(C)]
 pub enum FunctionCodeHandling {
   Clear,
   Keep,
 }
 
+type RawSnapshotCreator = [usize; 1];
+
 /// Helper class to create a snapshot data blob.
 #[repr(C)]
-pub struct SnapshotCreator([usize; 1]);
+pub struct SnapshotCreator {
+  raw: RawSnapshotCreator,
+  // V8 holds on to the existing snapshot blob (both the data and the header
+  // that points to it) that the creator was seeded with, so it must be kept
+  // alive until the C++ SnapshotCreator has been destroyed.
+  existing_snapshot_blob:
+    Option<(Allocation<[u8]>, Allocation<raw::StartupData>)>,
+}
 
 impl SnapshotCreator {
   /// Create and enter an isolate, and set it up for serialization.
   /// The isolate is created from scratch.
   pub fn new(external_references: Option<&'static ExternalReferences>) -> Self {
-    let mut snapshot_creator: MaybeUninit<Self> = MaybeUninit::uninit();
+    Self::construct(external_references, None)
+  }
+
+  /// Create and enter an isolate, and set it up for serialization.
+  /// The isolate is created from an existing snapshot, so that a snapshot
+  /// can be built incrementally on top of an earlier one.
+  pub fn new_from_existing_snapshot(
+    existing_snapshot_blob: impl Allocated<[u8]>,
+    external_references: Option<&'static ExternalReferences>,
+  ) -> Self {
+    let data = Allocation::of(existing_snapshot_blob);
+    let header = Allocation::of(raw::StartupData::boxed_header(&data));
+    Self::construct(external_references, Some((data, header)))
+  }
+
+  fn construct(
+    external_references: Option<&'static ExternalReferences>,
+    existing_snapshot_blob: Option<(
+      Allocation<[u8]>,
+      Allocation<raw::StartupData>,
+    )>,
+  ) -> Self {
+    let mut raw: MaybeUninit<RawSnapshotCreator> = MaybeUninit::uninit();
     let external_references_ptr = if let Some(er) = external_references {
       er.as_ptr()
     } else {
-      std::ptr::null()
+      null()
     };
+    let existing_snapshot_blob_ptr = existing_snapshot_blob
+      .as_ref()
+      .map(|(_, header)| &**header as *const _)
+      .unwrap_or(null());
     unsafe {
       v8__SnapshotCreator__CONSTRUCT(
-        &mut snapshot_creator,
+        &mut raw,
         external_references_ptr,
+        existing_snapshot_blob_ptr,
       );
-      snapshot_creator.assume_init()
+      Self {
+        raw: raw.assume_init(),
+        existing_snapshot_blob,
+      }
     }
   }
 }
 
 impl Drop for SnapshotCreator {
   fn drop(&mut self) {
-    unsafe { v8__SnapshotCreator__DESTRUCT(self) };
+    unsafe { v8__SnapshotCreator__DESTRUCT(&mut self.raw) };
+    // Only release the existing snapshot blob after V8 is done with it.
+    drop(self.existing_snapshot_blob.take());
   }
 }
 
@@ -109,6 +245,77 @@ impl SnapshotCreator {
     unsafe { v8__SnapshotCreator__SetDefaultContext(self, &*context) };
   }
 
+  /// Like `set_default_context()`, but the internal fields of objects in the
+  /// context are serialized with `serializer`. Pass a matching deserializer in
+  /// `ContextOptions::internal_fields_deserializer` to restore the context.
+  pub fn set_default_context_with_serializer<'sc>(
+    &mut self,
+    context: Local<'sc, Context>,
+    serializer: impl for<'s> MapFnTo<SerializeInternalFieldsCallback<'s>>,
+  ) {
+    unsafe {
+      v8__SnapshotCreator__SetDefaultContext__with_serializer(
+        self,
+        &*context,
+        serializer.map_fn_to(),
+      )
+    };
+  }
+
+  /// Add additional context to be included in the snapshot blob.
+  /// The snapshot will include the global proxy.
+  ///
+  /// Returns the index of the context in the snapshot blob, which can be
+  /// passed to `Context::from_snapshot()`.
+  pub fn add_context<'sc>(&mut self, context: Local<'sc, Context>) -> usize {
+    unsafe { v8__SnapshotCreator__AddContext(self, &*context) }
+  }
+
+  /// Like `add_context()`, but the internal fields of objects in the context
+  /// are serialized with `serializer`. Use
+  /// `Context::from_snapshot_with_deserializer()` to restore the context.
+  pub fn add_context_with_serializer<'sc>(
+    &mut self,
+    context: Local<'sc, Context>,
+    serializer: impl for<'s> MapFnTo<SerializeInternalFieldsCallback<'s>>,
+  ) -> usize {
+    unsafe {
+      v8__SnapshotCreator__AddContext__with_serializer(
+        self,
+        &*context,
+        serializer.map_fn_to(),
+      )
+    }
+  }
+
+  /// Attach arbitrary V8::Data to the isolate snapshot, which can be retrieved
+  /// via `Isolate::get_data_from_snapshot_once()` after deserialization. This
+  /// data does not survive when a new snapshot is created from an existing
+  /// snapshot.
+  pub fn add_isolate_data<'sc, T>(&mut self, data: Local<'sc, T>) -> usize
+  where
+    Local<'sc, Data>: From<Local<'sc, T>>,
+  {
+    let data = Local::<Data>::from(data);
+    unsafe { v8__SnapshotCreator__AddData_to_isolate(self, &*data) }
+  }
+
+  /// Attach arbitrary V8::Data to the context snapshot, which can be retrieved
+  /// via `Context::get_data_from_snapshot_once()` after deserialization. This
+  /// data does not survive when a new snapshot is created from an existing
+  /// snapshot.
+  pub fn add_context_data<'sc, T>(
+    &mut self,
+    context: Local<'sc, Context>,
+    data: Local<'sc, T>,
+  ) -> usize
+  where
+    Local<'sc, Data>: From<Local<'sc, T>>,
+  {
+    let data = Local::<Data>::from(data);
+    unsafe { v8__SnapshotCreator__AddData_to_context(self, &*context, &*data) }
+  }
+
   /// Creates a snapshot data blob.
   /// This must not be called from within a handle scope.
   pub fn create_blob(
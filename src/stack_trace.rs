@@ -0,0 +1,145 @@
+// Copyright 2019-2020 the Deno authors. All rights reserved. MIT license.
+use std::convert::TryInto;
+
+use crate::isolate::Isolate;
+use crate::support::int;
+use crate::Local;
+use crate::StackFrame;
+use crate::StackTrace;
+use crate::String;
+use crate::ToLocal;
+
+extern "C" {
+  fn v8__StackTrace__CurrentStackTrace(
+    isolate: *mut Isolate,
+    frame_limit: int,
+  ) -> *const StackTrace;
+  fn v8__StackTrace__GetFrameCount(this: *const StackTrace) -> int;
+  fn v8__StackTrace__GetFrame(
+    this: *const StackTrace,
+    isolate: *mut Isolate,
+    index: u32,
+  ) -> *const StackFrame;
+
+  fn v8__StackFrame__GetLineNumber(this: *const StackFrame) -> int;
+  fn v8__StackFrame__GetColumn(this: *const StackFrame) -> int;
+  fn v8__StackFrame__GetScriptId(this: *const StackFrame) -> int;
+  fn v8__StackFrame__GetScriptName(this: *const StackFrame) -> *const String;
+  fn v8__StackFrame__GetScriptNameOrSourceURL(
+    this: *const StackFrame,
+  ) -> *const String;
+  fn v8__StackFrame__GetFunctionName(this: *const StackFrame) -> *const String;
+  fn v8__StackFrame__IsEval(this: *const StackFrame) -> bool;
+  fn v8__StackFrame__IsConstructor(this: *const StackFrame) -> bool;
+  fn v8__StackFrame__IsWasm(this: *const StackFrame) -> bool;
+  fn v8__StackFrame__IsUserJavaScript(this: *const StackFrame) -> bool;
+}
+
+impl StackTrace {
+  /// Grab a snapshot of the current JavaScript execution stack.
+  pub fn current_stack_trace<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+    frame_limit: usize,
+  ) -> Option<Local<'sc, StackTrace>> {
+    let frame_limit = frame_limit.try_into().unwrap_or(int::max_value());
+    unsafe {
+      let ptr = v8__StackTrace__CurrentStackTrace(scope.isolate(), frame_limit);
+      scope.to_local(ptr)
+    }
+  }
+
+  /// Returns the number of StackFrames.
+  pub fn get_frame_count(&self) -> usize {
+    unsafe { v8__StackTrace__GetFrameCount(self) as usize }
+  }
+
+  /// Returns a StackFrame at a particular index.
+  pub fn get_frame<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+    index: usize,
+  ) -> Option<Local<'sc, StackFrame>> {
+    let isolate = scope.isolate();
+    unsafe {
+      let ptr = v8__StackTrace__GetFrame(self, isolate, index as u32);
+      scope.to_local(ptr)
+    }
+  }
+}
+
+impl StackFrame {
+  /// Returns the number, 1-based, of the line for the associate function call.
+  /// This method will return Message::kNoLineNumberInfo if it is unable to
+  /// retrieve the line number, or if kLineNumber was not passed as an option
+  /// when capturing the StackTrace.
+  pub fn get_line_number(&self) -> usize {
+    unsafe { v8__StackFrame__GetLineNumber(self) as usize }
+  }
+
+  /// Returns the 1-based column offset on the line for the associated function
+  /// call.
+  /// This method will return Message::kNoColumnInfo if it is unable to retrieve
+  /// the column number, or if kColumnOffset was not passed as an option when
+  /// capturing the StackTrace.
+  pub fn get_column(&self) -> usize {
+    unsafe { v8__StackFrame__GetColumn(self) as usize }
+  }
+
+  /// Returns the id of the script for the function for this StackFrame.
+  /// This method will return Message::kNoScriptIdInfo if it is unable to
+  /// retrieve the script id, or if kScriptId was not passed as an option when
+  /// capturing the StackTrace.
+  pub fn get_script_id(&self) -> usize {
+    unsafe { v8__StackFrame__GetScriptId(self) as usize }
+  }
+
+  /// Returns the name of the resource that contains the script for the
+  /// function for this StackFrame.
+  pub fn get_script_name<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Option<Local<'sc, String>> {
+    unsafe { scope.to_local(v8__StackFrame__GetScriptName(self)) }
+  }
+
+  /// Returns the name of the resource that contains the script for the
+  /// function for this StackFrame or sourceURL value if the script name
+  /// is undefined and its source ends with //# sourceURL=... string or
+  /// deprecated //@ sourceURL=... string.
+  pub fn get_script_name_or_source_url<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Option<Local<'sc, String>> {
+    unsafe { scope.to_local(v8__StackFrame__GetScriptNameOrSourceURL(self)) }
+  }
+
+  /// Returns the name of the function associated with this stack frame.
+  pub fn get_function_name<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Option<Local<'sc, String>> {
+    unsafe { scope.to_local(v8__StackFrame__GetFunctionName(self)) }
+  }
+
+  /// Returns whether or not the associated function is compiled via a call to
+  /// eval().
+  pub fn is_eval(&self) -> bool {
+    unsafe { v8__StackFrame__IsEval(self) }
+  }
+
+  /// Returns whether or not the associated function is called as a
+  /// constructor via "new".
+  pub fn is_constructor(&self) -> bool {
+    unsafe { v8__StackFrame__IsConstructor(self) }
+  }
+
+  /// Returns whether or not the associated functions is defined in wasm.
+  pub fn is_wasm(&self) -> bool {
+    unsafe { v8__StackFrame__IsWasm(self) }
+  }
+
+  /// Returns whether or not the associated function is defined by the user.
+  pub fn is_user_javascript(&self) -> bool {
+    unsafe { v8__StackFrame__IsUserJavaScript(self) }
+  }
+}
@@ -1,5 +1,9 @@
 use std::convert::TryInto;
 use std::default::Default;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
 use std::mem::forget;
 use std::slice;
 
@@ -62,7 +66,66 @@ bitflags! {
   }
 }
 
+/// The error returned by `String::new_checked()` when the string to be
+/// created is longer than `String::MAX_LENGTH`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StringTooLong {
+  length: usize,
+}
+
+impl StringTooLong {
+  /// The length, in UTF-16 code units, of the string that could not be
+  /// created.
+  pub fn length(&self) -> usize {
+    self.length
+  }
+}
+
+impl Display for StringTooLong {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "string length {} exceeds the maximum of {}",
+      self.length,
+      String::MAX_LENGTH
+    )
+  }
+}
+
+impl Error for StringTooLong {}
+
+/// Returns an error if `value` is longer than `max_length` UTF-16 code units.
+fn check_utf16_length(
+  value: &str,
+  max_length: usize,
+) -> Result<(), StringTooLong> {
+  // A string never has more UTF-16 code units than its UTF-8 encoding has
+  // bytes, so the exact length only needs to be computed for long strings.
+  // Every byte that doesn't continue a UTF-8 sequence starts one code
+  // point, and only four-byte sequences need a surrogate pair.
+  if value.len() > max_length {
+    let length = value
+      .bytes()
+      .map(|b| match b {
+        0x80..=0xBF => 0,
+        0xF0..=0xFF => 2,
+        _ => 1,
+      })
+      .sum();
+    if length > max_length {
+      return Err(StringTooLong { length });
+    }
+  }
+  Ok(())
+}
+
 impl String {
+  /// The maximum length (in UTF-16 code units) of a string.
+  #[cfg(target_pointer_width = "64")]
+  pub const MAX_LENGTH: usize = (1 << 29) - 24;
+  #[cfg(target_pointer_width = "32")]
+  pub const MAX_LENGTH: usize = (1 << 28) - 16;
+
   pub fn empty<'sc>(scope: &mut impl ToLocal<'sc>) -> Local<'sc, String> {
     let ptr = unsafe { v8__String__Empty(scope.isolate()) };
     // FIXME(bnoordhuis) v8__String__Empty() is infallible so there
@@ -132,6 +195,18 @@ impl String {
     Self::new_from_utf8(scope, value.as_ref(), NewStringType::Normal)
   }
 
+  // Convenience function not present in the original V8 API.
+  /// Like `String::new()`, but returns an error that describes why the string
+  /// could not be created instead of `None`.
+  pub fn new_checked<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+    value: &str,
+  ) -> Result<Local<'sc, String>, StringTooLong> {
+    check_utf16_length(value, Self::MAX_LENGTH)?;
+    // V8 only fails to create a string if it is too long.
+    Ok(Self::new(scope, value).unwrap())
+  }
+
   // Convenience function not present in the original V8 API.
   pub fn to_rust_string_lossy(
     &self,
@@ -150,3 +225,60 @@ impl String {
     unsafe { std::string::String::from_raw_parts(data, length, capacity) }
   }
 }
+
+/// Extension trait for creating a V8 string from a Rust string slice, e.g.
+/// `"hello".to_v8(scope)` instead of `v8::String::new(scope, "hello")`.
+// Convenience trait not present in the original V8 API.
+pub trait ToV8String {
+  /// Creates a V8 string with the same contents. Panics if the string is
+  /// longer than `String::MAX_LENGTH`.
+  fn to_v8<'sc>(&self, scope: &mut impl ToLocal<'sc>) -> Local<'sc, String>;
+}
+
+impl ToV8String for str {
+  fn to_v8<'sc>(&self, scope: &mut impl ToLocal<'sc>) -> Local<'sc, String> {
+    String::new_checked(scope, self).unwrap()
+  }
+}
+
+/// The inverse of `ToV8String`: creates a Rust string from a V8 string.
+/// Invalid UTF-16 is replaced with U+FFFD REPLACEMENT CHARACTER.
+// Convenience trait not present in the original V8 API.
+pub trait FromV8String {
+  fn from_v8(scope: &mut impl InIsolate, value: Local<String>) -> Self;
+}
+
+impl FromV8String for std::string::String {
+  fn from_v8(scope: &mut impl InIsolate, value: Local<String>) -> Self {
+    value.to_rust_string_lossy(scope)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn utf16_length() {
+    assert_eq!(check_utf16_length("", 0), Ok(()));
+    assert_eq!(check_utf16_length("abc", 3), Ok(()));
+    assert_eq!(
+      check_utf16_length("abcd", 3),
+      Err(StringTooLong { length: 4 })
+    );
+    // Two UTF-8 bytes, one UTF-16 code unit.
+    assert_eq!(check_utf16_length("é", 1), Ok(()));
+    // Three UTF-8 bytes, one UTF-16 code unit.
+    assert_eq!(check_utf16_length("中", 1), Ok(()));
+    // Four UTF-8 bytes, two UTF-16 code units.
+    assert_eq!(check_utf16_length("🦕", 2), Ok(()));
+    assert_eq!(
+      check_utf16_length("🦕", 1),
+      Err(StringTooLong { length: 2 })
+    );
+    assert_eq!(
+      check_utf16_length("é中🦕", 3),
+      Err(StringTooLong { length: 4 })
+    );
+  }
+}
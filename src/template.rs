@@ -4,15 +4,28 @@ use crate::data::Name;
 use crate::data::ObjectTemplate;
 use crate::data::Template;
 use crate::isolate::Isolate;
+use crate::support::int;
 use crate::support::MapFnTo;
+use crate::AccessorNameGetterCallback;
 use crate::Context;
 use crate::Function;
 use crate::FunctionCallback;
+use crate::GenericNamedPropertyDeleterCallback;
+use crate::GenericNamedPropertyEnumeratorCallback;
+use crate::GenericNamedPropertyGetterCallback;
+use crate::GenericNamedPropertyQueryCallback;
+use crate::GenericNamedPropertySetterCallback;
+use crate::IndexedPropertyDeleterCallback;
+use crate::IndexedPropertyEnumeratorCallback;
+use crate::IndexedPropertyGetterCallback;
+use crate::IndexedPropertyQueryCallback;
+use crate::IndexedPropertySetterCallback;
 use crate::Local;
 use crate::Object;
 use crate::PropertyAttribute;
 use crate::String;
 use crate::ToLocal;
+use crate::Value;
 use crate::NONE;
 
 extern "C" {
@@ -22,6 +35,12 @@ extern "C" {
     value: *const Data,
     attr: PropertyAttribute,
   );
+  fn v8__Template__SetIntrinsicDataProperty(
+    this: *const Template,
+    key: *const Name,
+    intrinsic: Intrinsic,
+    attr: PropertyAttribute,
+  );
 
   fn v8__FunctionTemplate__New(
     isolate: *mut Isolate,
@@ -35,6 +54,24 @@ extern "C" {
     this: *const FunctionTemplate,
     name: *const String,
   );
+  fn v8__FunctionTemplate__Inherit(
+    this: *const FunctionTemplate,
+    parent: *const FunctionTemplate,
+  );
+  fn v8__FunctionTemplate__SetLength(
+    this: *const FunctionTemplate,
+    length: int,
+  );
+  fn v8__FunctionTemplate__ReadOnlyPrototype(this: *const FunctionTemplate);
+  fn v8__FunctionTemplate__PrototypeTemplate(
+    this: *const FunctionTemplate,
+  ) -> *const ObjectTemplate;
+  fn v8__FunctionTemplate__InstanceTemplate(
+    this: *const FunctionTemplate,
+  ) -> *const ObjectTemplate;
+  fn v8__FunctionTemplate__NewRemoteInstance(
+    this: *const FunctionTemplate,
+  ) -> *const Object;
 
   fn v8__ObjectTemplate__New(
     isolate: *mut Isolate,
@@ -44,10 +81,194 @@ extern "C" {
     this: *const ObjectTemplate,
     context: *const Context,
   ) -> *const Object;
+  fn v8__ObjectTemplate__InternalFieldCount(this: *const ObjectTemplate)
+    -> int;
+  fn v8__ObjectTemplate__SetInternalFieldCount(
+    this: *const ObjectTemplate,
+    value: int,
+  );
+  fn v8__ObjectTemplate__SetAccessor(
+    this: *const ObjectTemplate,
+    key: *const Name,
+    getter: AccessorNameGetterCallback,
+  );
+  fn v8__ObjectTemplate__SetAccessorWithData(
+    this: *const ObjectTemplate,
+    key: *const Name,
+    getter: AccessorNameGetterCallback,
+    data: *const Value,
+  );
+  fn v8__ObjectTemplate__SetNamedPropertyHandler(
+    this: *const ObjectTemplate,
+    getter: Option<GenericNamedPropertyGetterCallback>,
+    setter: Option<GenericNamedPropertySetterCallback>,
+    query: Option<GenericNamedPropertyQueryCallback>,
+    deleter: Option<GenericNamedPropertyDeleterCallback>,
+    enumerator: Option<GenericNamedPropertyEnumeratorCallback>,
+  );
+  fn v8__ObjectTemplate__SetIndexedPropertyHandler(
+    this: *const ObjectTemplate,
+    getter: Option<IndexedPropertyGetterCallback>,
+    setter: Option<IndexedPropertySetterCallback>,
+    query: Option<IndexedPropertyQueryCallback>,
+    deleter: Option<IndexedPropertyDeleterCallback>,
+    enumerator: Option<IndexedPropertyEnumeratorCallback>,
+  );
+  fn v8__ObjectTemplate__SetImmutableProto(this: *const ObjectTemplate);
+  fn v8__ObjectTemplate__SetAccessCheckCallback(
+    this: *const ObjectTemplate,
+    callback: AccessCheckCallback,
+  );
+}
+
+/// Returns true if the given context should be allowed to access the given
+/// object.
+pub type AccessCheckCallback = extern "C" fn(
+  accessing_context: Local<Context>,
+  accessed_object: Local<Object>,
+  data: Local<Value>,
+) -> bool;
+
+/// Intrinsic JavaScript objects that can be installed on templates without
+/// being recreated. See `Template::set_intrinsic_data_property()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub enum Intrinsic {
+  ArrayProtoEntries,
+  ArrayProtoForEach,
+  ArrayProtoKeys,
+  ArrayProtoValues,
+  AsyncIteratorPrototype,
+  ErrorPrototype,
+  IteratorPrototype,
+  ObjProtoValueOf,
+}
+
+/// Configuration for interceptors of named properties, used by
+/// `ObjectTemplate::set_named_property_handler()`. Callbacks that are not
+/// specified are not intercepted.
+#[must_use]
+#[derive(Default)]
+pub struct NamedPropertyHandlerConfiguration<'s> {
+  getter: Option<GenericNamedPropertyGetterCallback<'s>>,
+  setter: Option<GenericNamedPropertySetterCallback<'s>>,
+  query: Option<GenericNamedPropertyQueryCallback<'s>>,
+  deleter: Option<GenericNamedPropertyDeleterCallback<'s>>,
+  enumerator: Option<GenericNamedPropertyEnumeratorCallback>,
+}
+
+impl<'s> NamedPropertyHandlerConfiguration<'s> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn getter(
+    mut self,
+    getter: impl MapFnTo<GenericNamedPropertyGetterCallback<'s>>,
+  ) -> Self {
+    self.getter = Some(getter.map_fn_to());
+    self
+  }
+
+  pub fn setter(
+    mut self,
+    setter: impl MapFnTo<GenericNamedPropertySetterCallback<'s>>,
+  ) -> Self {
+    self.setter = Some(setter.map_fn_to());
+    self
+  }
+
+  pub fn query(
+    mut self,
+    query: impl MapFnTo<GenericNamedPropertyQueryCallback<'s>>,
+  ) -> Self {
+    self.query = Some(query.map_fn_to());
+    self
+  }
+
+  pub fn deleter(
+    mut self,
+    deleter: impl MapFnTo<GenericNamedPropertyDeleterCallback<'s>>,
+  ) -> Self {
+    self.deleter = Some(deleter.map_fn_to());
+    self
+  }
+
+  pub fn enumerator(
+    mut self,
+    enumerator: impl MapFnTo<GenericNamedPropertyEnumeratorCallback>,
+  ) -> Self {
+    self.enumerator = Some(enumerator.map_fn_to());
+    self
+  }
+}
+
+/// Configuration for interceptors of indexed properties, used by
+/// `ObjectTemplate::set_indexed_property_handler()`. Callbacks that are not
+/// specified are not intercepted.
+#[must_use]
+#[derive(Default)]
+pub struct IndexedPropertyHandlerConfiguration<'s> {
+  getter: Option<IndexedPropertyGetterCallback>,
+  setter: Option<IndexedPropertySetterCallback<'s>>,
+  query: Option<IndexedPropertyQueryCallback>,
+  deleter: Option<IndexedPropertyDeleterCallback>,
+  enumerator: Option<IndexedPropertyEnumeratorCallback>,
+}
+
+impl<'s> IndexedPropertyHandlerConfiguration<'s> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn getter(
+    mut self,
+    getter: impl MapFnTo<IndexedPropertyGetterCallback>,
+  ) -> Self {
+    self.getter = Some(getter.map_fn_to());
+    self
+  }
+
+  pub fn setter(
+    mut self,
+    setter: impl MapFnTo<IndexedPropertySetterCallback<'s>>,
+  ) -> Self {
+    self.setter = Some(setter.map_fn_to());
+    self
+  }
+
+  pub fn query(
+    mut self,
+    query: impl MapFnTo<IndexedPropertyQueryCallback>,
+  ) -> Self {
+    self.query = Some(query.map_fn_to());
+    self
+  }
+
+  pub fn deleter(
+    mut self,
+    deleter: impl MapFnTo<IndexedPropertyDeleterCallback>,
+  ) -> Self {
+    self.deleter = Some(deleter.map_fn_to());
+    self
+  }
+
+  pub fn enumerator(
+    mut self,
+    enumerator: impl MapFnTo<IndexedPropertyEnumeratorCallback>,
+  ) -> Self {
+    self.enumerator = Some(enumerator.map_fn_to());
+    self
+  }
 }
 
 impl Template {
   /// Adds a property to each instance created by this template.
+  ///
+  /// Both `FunctionTemplate` and `ObjectTemplate` dereference to `Template`,
+  /// so this method is available on either. On a `FunctionTemplate` the
+  /// property is added to the function itself, i.e. it becomes a static
+  /// member.
   pub fn set(&self, key: Local<Name>, value: Local<Data>) {
     self.set_with_attr(key, value, NONE)
   }
@@ -62,6 +283,19 @@ impl Template {
   ) {
     unsafe { v8__Template__Set(self, &*key, &*value, attr) }
   }
+
+  /// During template instantiation, sets the value with the intrinsic
+  /// property from the correct context.
+  pub fn set_intrinsic_data_property(
+    &self,
+    key: Local<Name>,
+    intrinsic: Intrinsic,
+    attr: PropertyAttribute,
+  ) {
+    unsafe {
+      v8__Template__SetIntrinsicDataProperty(self, &*key, intrinsic, attr)
+    }
+  }
 }
 
 impl FunctionTemplate {
@@ -93,6 +327,57 @@ impl FunctionTemplate {
   pub fn set_class_name(&mut self, name: Local<String>) {
     unsafe { v8__FunctionTemplate__SetClassName(&*self, &*name) };
   }
+
+  /// Causes the function template to inherit from a parent function template.
+  /// This means the function's prototype.__proto__ is set to the parent
+  /// function's prototype.
+  pub fn inherit(&self, parent: Local<FunctionTemplate>) {
+    unsafe { v8__FunctionTemplate__Inherit(self, &*parent) };
+  }
+
+  /// Set the predefined length property for the FunctionTemplate.
+  pub fn set_length(&self, length: int) {
+    unsafe { v8__FunctionTemplate__SetLength(self, length) };
+  }
+
+  /// Sets the ReadOnly flag in the attributes of the 'prototype' property
+  /// of functions created from this FunctionTemplate to true.
+  pub fn read_only_prototype(&self) {
+    unsafe { v8__FunctionTemplate__ReadOnlyPrototype(self) };
+  }
+
+  /// A PrototypeTemplate is the template used to create the prototype object
+  /// of the function created by this template.
+  pub fn prototype_template<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Local<'sc, ObjectTemplate> {
+    let ptr = unsafe { v8__FunctionTemplate__PrototypeTemplate(self) };
+    unsafe { scope.to_local(ptr) }.unwrap()
+  }
+
+  /// Get the InstanceTemplate.
+  pub fn instance_template<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Local<'sc, ObjectTemplate> {
+    let ptr = unsafe { v8__FunctionTemplate__InstanceTemplate(self) };
+    unsafe { scope.to_local(ptr) }.unwrap()
+  }
+
+  /// Creates a new remote instance of this template, i.e. an object whose
+  /// properties live in a different isolate or process. Remote instances
+  /// don't have a creation context.
+  ///
+  /// The instance template must have an access check callback, see
+  /// `ObjectTemplate::set_access_check_callback()`.
+  pub fn new_remote_instance<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Option<Local<'sc, Object>> {
+    let ptr = unsafe { v8__FunctionTemplate__NewRemoteInstance(self) };
+    unsafe { scope.to_local(ptr) }
+  }
 }
 
 impl ObjectTemplate {
@@ -121,4 +406,106 @@ impl ObjectTemplate {
     let ptr = unsafe { v8__ObjectTemplate__NewInstance(self, &*context) };
     unsafe { scope.to_local(ptr) }
   }
+
+  /// Gets the number of internal fields for objects generated from
+  /// this template.
+  pub fn internal_field_count(&self) -> int {
+    unsafe { v8__ObjectTemplate__InternalFieldCount(self) }
+  }
+
+  /// Sets the number of internal fields for objects generated from
+  /// this template.
+  pub fn set_internal_field_count(&self, value: int) {
+    unsafe { v8__ObjectTemplate__SetInternalFieldCount(self, value) };
+  }
+
+  /// Sets an accessor on the object template.
+  ///
+  /// Whenever the property with the given name is accessed on objects
+  /// created from this ObjectTemplate the getter function is called instead
+  /// of accessing the property directly on the JavaScript object.
+  pub fn set_accessor(
+    &self,
+    key: Local<Name>,
+    getter: impl for<'s> MapFnTo<AccessorNameGetterCallback<'s>>,
+  ) {
+    unsafe { v8__ObjectTemplate__SetAccessor(self, &*key, getter.map_fn_to()) }
+  }
+
+  /// Like `set_accessor()`, but `data` is passed to the getter, where it can
+  /// be retrieved with `PropertyCallbackArguments::data()`.
+  pub fn set_accessor_with_data(
+    &self,
+    key: Local<Name>,
+    getter: impl for<'s> MapFnTo<AccessorNameGetterCallback<'s>>,
+    data: Local<Value>,
+  ) {
+    unsafe {
+      v8__ObjectTemplate__SetAccessorWithData(
+        self,
+        &*key,
+        getter.map_fn_to(),
+        &*data,
+      )
+    }
+  }
+
+  /// Makes the [[Prototype]] of instances of this template immutable. Trying
+  /// to change it, e.g. using `Object.setPrototypeOf()`, throws a TypeError.
+  pub fn set_immutable_proto(&self) {
+    unsafe { v8__ObjectTemplate__SetImmutableProto(self) }
+  }
+
+  /// Sets an access check callback on the object template and enables access
+  /// checks.
+  ///
+  /// When accessing properties on instances of this object template, the
+  /// access check callback will be called to determine whether or not to
+  /// allow cross-context access to the properties.
+  pub fn set_access_check_callback(&self, callback: AccessCheckCallback) {
+    unsafe { v8__ObjectTemplate__SetAccessCheckCallback(self, callback) }
+  }
+
+  /// Sets a named property handler on the object template.
+  ///
+  /// Whenever a property whose name is a string or a symbol is accessed on
+  /// objects created from this object template, the provided callback is
+  /// invoked instead of accessing the property directly on the JavaScript
+  /// object.
+  pub fn set_named_property_handler(
+    &self,
+    configuration: NamedPropertyHandlerConfiguration,
+  ) {
+    unsafe {
+      v8__ObjectTemplate__SetNamedPropertyHandler(
+        self,
+        configuration.getter,
+        configuration.setter,
+        configuration.query,
+        configuration.deleter,
+        configuration.enumerator,
+      )
+    }
+  }
+
+  /// Sets an indexed property handler on the object template.
+  ///
+  /// Whenever an indexed property is accessed on objects created from this
+  /// object template, the provided callback is invoked instead of accessing
+  /// the property directly on the JavaScript object.
+  pub fn set_indexed_property_handler(
+    &self,
+    configuration: IndexedPropertyHandlerConfiguration,
+  ) {
+    unsafe {
+      v8__ObjectTemplate__SetIndexedPropertyHandler(
+        self,
+        configuration.getter,
+        configuration.setter,
+        configuration.query,
+        configuration.deleter,
+        configuration.enumerator,
+      )
+    }
+  }
 }
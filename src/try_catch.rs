@@ -1,3 +1,4 @@
+use std::convert::TryFrom;
 use std::marker::PhantomData;
 use std::mem::size_of;
 use std::mem::size_of_val;
@@ -122,6 +123,22 @@ impl<'tc> TryCatch<'tc> {
     unsafe { scope.to_local(v8__TryCatch__Exception(&self.0)) }
   }
 
+  /// Returns the exception caught by this try/catch block, cast to `T`.
+  /// Returns `None` if no exception has been caught, or if the exception is
+  /// not of type `T`.
+  // Convenience function not present in the original V8 API.
+  pub fn exception_as<'sc, T>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Option<Local<'sc, T>>
+  where
+    Local<'sc, T>: TryFrom<Local<'sc, Value>>,
+  {
+    self
+      .exception(scope)
+      .and_then(|exception| Local::<T>::try_from(exception).ok())
+  }
+
   /// Returns the message associated with this exception. If there is
   /// no message associated an empty handle is returned.
   ///
@@ -173,6 +190,14 @@ impl<'tc> TryCatch<'tc> {
     result
   }
 
+  /// Rethrows the caught exception, if there is one, and returns true.
+  /// Returns false if no exception has been caught. See `rethrow()` for the
+  /// restrictions that apply after the exception has been rethrown.
+  // Convenience function not present in the original V8 API.
+  pub fn rethrow_if_caught(&mut self) -> bool {
+    self.has_caught() && self.rethrow().is_some()
+  }
+
   /// Returns true if verbosity is enabled.
   pub fn is_verbose(&self) -> bool {
     unsafe { v8__TryCatch__IsVerbose(&self.0) }
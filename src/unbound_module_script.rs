@@ -0,0 +1,24 @@
+// Copyright 2019-2020 the Deno authors. All rights reserved. MIT license.
+use crate::script_compiler::CachedData;
+use crate::support::UniqueRef;
+use crate::UnboundModuleScript;
+
+extern "C" {
+  fn v8__UnboundModuleScript__CreateCodeCache(
+    script: *const UnboundModuleScript,
+  ) -> *mut CachedData;
+}
+
+impl UnboundModuleScript {
+  /// Creates and returns code cache for the specified unbound_module_script.
+  /// This will return `None` if the script cannot be serialized. The
+  /// CachedData returned by this function should be owned by the caller.
+  pub fn create_code_cache(&self) -> Option<UniqueRef<CachedData>> {
+    let ptr = unsafe { v8__UnboundModuleScript__CreateCodeCache(self) };
+    if ptr.is_null() {
+      None
+    } else {
+      Some(unsafe { UniqueRef::from_raw(ptr) })
+    }
+  }
+}
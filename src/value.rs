@@ -90,6 +90,11 @@ extern "C" {
     this: *const Value,
     context: *const Context,
   ) -> *const Object;
+  fn v8__Value__ToPrimitive(
+    this: *const Value,
+    context: *const Context,
+    hint: ToPrimitiveHint,
+  ) -> *const Value;
   fn v8__Value__ToInteger(
     this: *const Value,
     context: *const Context,
@@ -102,6 +107,11 @@ extern "C" {
     this: *const Value,
     context: *const Context,
   ) -> *const Int32;
+  fn v8__Value__ToArrayIndex(
+    this: *const Value,
+    context: *const Context,
+  ) -> *const Uint32;
+  fn v8__Value__GetHash(this: *const Value) -> u32;
 
   fn v8__Value__NumberValue(
     this: *const Value,
@@ -125,6 +135,16 @@ extern "C" {
   );
 }
 
+/// The preferred type passed to `Value::to_primitive()`, which is forwarded as
+/// the hint argument of a `Symbol.toPrimitive` method.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToPrimitiveHint {
+  Default,
+  Number,
+  String,
+}
+
 impl Value {
   /// Returns true if this value is the undefined value.  See ECMA-262 4.3.10.
   pub fn is_undefined(&self) -> bool {
@@ -446,6 +466,20 @@ impl Value {
     })
   }
 
+  /// Converts this value to a string, like `to_string()`, and returns its
+  /// contents as a Rust string. If the conversion throws, the exception is left
+  /// pending and `"<failed>"` is returned instead.
+  // Convenience function not present in the original V8 API.
+  pub fn to_rust_string_lossy<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> std::string::String {
+    match self.to_string(scope) {
+      Some(s) => s.to_rust_string_lossy(scope),
+      None => "<failed>".to_owned(),
+    }
+  }
+
   pub fn to_detail_string<'sc>(
     &self,
     scope: &mut impl ToLocal<'sc>,
@@ -464,6 +498,20 @@ impl Value {
     })
   }
 
+  /// Converts this value to a primitive, calling its `Symbol.toPrimitive`,
+  /// `valueOf()` or `toString()` methods as specified by the ECMAScript
+  /// ToPrimitive operation. Primitive values are returned unchanged.
+  // Convenience function not present in the original V8 API.
+  pub fn to_primitive<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+    hint: ToPrimitiveHint,
+  ) -> Option<Local<'sc, Value>> {
+    scope.get_current_context().and_then(|context| unsafe {
+      scope.to_local(v8__Value__ToPrimitive(self, &*context, hint))
+    })
+  }
+
   pub fn to_integer<'sc>(
     &self,
     scope: &mut impl ToLocal<'sc>,
@@ -491,6 +539,27 @@ impl Value {
     })
   }
 
+  /// Returns a hash of this value that is stable for as long as the value is
+  /// alive. Values that are `strict_equals()` to each other have the same
+  /// hash, so it can be used to key a native hash map by JavaScript value.
+  /// Objects are hashed by identity, strings and symbols by their contents.
+  ///
+  /// Distinct values may have the same hash.
+  pub fn get_hash(&self) -> u32 {
+    unsafe { v8__Value__GetHash(self) }
+  }
+
+  /// Attempts to convert a string to an array index. Returns `None` if the
+  /// value is not a valid array index.
+  pub fn to_array_index<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Option<Local<'sc, Uint32>> {
+    scope.get_current_context().and_then(|context| unsafe {
+      scope.to_local(v8__Value__ToArrayIndex(self, &*context))
+    })
+  }
+
   pub fn number_value<'sc>(
     &self,
     scope: &mut impl ToLocal<'sc>,
@@ -502,6 +571,18 @@ impl Value {
     })
   }
 
+  /// Like `number_value()`, but makes it explicit that the conversion only
+  /// fails when an exception was thrown during coercion (e.g. by a throwing
+  /// `valueOf()` method) or when there is no current context. Values that
+  /// coerce to NaN yield `Ok(NaN)`.
+  // Convenience function not present in the original V8 API.
+  pub fn number_value_or_throw<'sc>(
+    &self,
+    scope: &mut impl ToLocal<'sc>,
+  ) -> Result<f64, ()> {
+    self.number_value(scope).ok_or(())
+  }
+
   pub fn integer_value<'sc>(
     &self,
     scope: &mut impl ToLocal<'sc>,
@@ -0,0 +1,76 @@
+// Copyright 2019-2020 the Deno authors. All rights reserved. MIT license.
+use std::slice;
+
+use crate::isolate::Isolate;
+use crate::support::Opaque;
+use crate::support::UniqueRef;
+use crate::Local;
+use crate::ToLocal;
+use crate::WasmModuleObject;
+
+extern "C" {
+  fn v8__WasmModuleObject__GetCompiledModule(
+    this: *const WasmModuleObject,
+  ) -> *mut CompiledWasmModule;
+  fn v8__WasmModuleObject__FromCompiledModule(
+    isolate: *mut Isolate,
+    compiled_module: *const CompiledWasmModule,
+  ) -> *const WasmModuleObject;
+
+  fn v8__CompiledWasmModule__GetWireBytesRef(
+    this: *const CompiledWasmModule,
+    length: *mut usize,
+  ) -> *const u8;
+  fn v8__CompiledWasmModule__DELETE(this: *mut CompiledWasmModule);
+}
+
+impl WasmModuleObject {
+  /// Get the compiled module for this module object. The compiled module can
+  /// be shared by several module objects.
+  pub fn get_compiled_module(&self) -> UniqueRef<CompiledWasmModule> {
+    unsafe {
+      UniqueRef::from_raw(v8__WasmModuleObject__GetCompiledModule(self))
+    }
+  }
+
+  /// Efficiently re-create a WasmModuleObject, without recompiling, from
+  /// a CompiledWasmModule.
+  pub fn from_compiled_module<'sc>(
+    scope: &mut impl ToLocal<'sc>,
+    compiled_module: &CompiledWasmModule,
+  ) -> Option<Local<'sc, WasmModuleObject>> {
+    unsafe {
+      let ptr = v8__WasmModuleObject__FromCompiledModule(
+        scope.isolate(),
+        compiled_module,
+      );
+      scope.to_local(ptr)
+    }
+  }
+}
+
+/// Wrapper around a compiled WebAssembly module, which is potentially shared
+/// by different WasmModuleObjects.
+#[repr(C)]
+pub struct CompiledWasmModule(Opaque);
+
+impl CompiledWasmModule {
+  /// Returns the wire bytes (the uncompiled Wasm binary) of the module.
+  pub fn get_wire_bytes_ref(&self) -> &[u8] {
+    let mut length = 0;
+    unsafe {
+      let data = v8__CompiledWasmModule__GetWireBytesRef(self, &mut length);
+      if length == 0 {
+        &[]
+      } else {
+        slice::from_raw_parts(data, length)
+      }
+    }
+  }
+}
+
+impl Drop for CompiledWasmModule {
+  fn drop(&mut self) {
+    unsafe { v8__CompiledWasmModule__DELETE(self) }
+  }
+}
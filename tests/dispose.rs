@@ -0,0 +1,25 @@
+// Disposing V8 is permanent for the whole process, so this test lives in its
+// own test binary instead of in test_api.rs.
+
+use rusty_v8 as v8;
+
+#[test]
+fn dispose_and_shutdown_platform() {
+  v8::V8::initialize_platform(v8::new_default_platform().unwrap());
+  v8::V8::initialize();
+  {
+    let mut isolate = v8::Isolate::new(Default::default());
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let source = v8::String::new(scope, "1 + 1").unwrap();
+    let mut script = v8::Script::compile(scope, context, source, None).unwrap();
+    let result = script.run(scope, context).unwrap();
+    assert_eq!(result.integer_value(scope), Some(2));
+  }
+  // All isolates have been dropped, so V8 can be torn down.
+  assert!(unsafe { v8::V8::dispose() });
+  v8::V8::shutdown_platform();
+}
@@ -0,0 +1,25 @@
+// V8 can only be initialized once per process, so tests that need a custom
+// platform live in their own test binary.
+
+use rusty_v8 as v8;
+
+#[test]
+fn new_default_platform_with() {
+  let platform = v8::new_default_platform_with(1, true).unwrap();
+  v8::V8::initialize_platform(platform);
+  v8::V8::initialize();
+
+  let platform = v8::V8::get_current_platform().unwrap();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+  let source = v8::String::new(scope, "6 * 7").unwrap();
+  let mut script = v8::Script::compile(scope, context, source, None).unwrap();
+  let result = script.run(scope, context).unwrap();
+  assert_eq!(result.integer_value(scope), Some(42));
+
+  v8::Platform::run_idle_tasks(platform, scope.isolate(), 0.001);
+}
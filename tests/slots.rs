@@ -163,3 +163,30 @@ fn slots_layer2() {
   drop(es_isolate);
   assert_eq!(drop_count.load(Ordering::SeqCst), 2);
 }
+
+#[test]
+fn slots_replace() {
+  struct Counter {
+    count: usize,
+  }
+
+  let drop_count = Rc::new(AtomicUsize::new(0));
+  let mut core_isolate = CoreIsolate::new(drop_count.clone());
+  assert!(core_isolate.get_slot::<Counter>().is_none());
+  assert!(core_isolate.set_slot(Counter { count: 1 }).is_none());
+  core_isolate.get_slot_mut::<Counter>().unwrap().count += 1;
+  assert_eq!(core_isolate.get_slot::<Counter>().unwrap().count, 2);
+
+  // Replacing a slot hands the previous value back to the caller.
+  let previous = core_isolate.set_slot(Counter { count: 10 }).unwrap();
+  assert_eq!(previous.count, 2);
+  assert_eq!(core_isolate.get_slot::<Counter>().unwrap().count, 10);
+
+  // A slot can't be borrowed mutably while it's borrowed immutably.
+  let counter = core_isolate.get_slot::<Counter>().unwrap();
+  assert!(core_isolate.get_slot_mut::<Counter>().is_none());
+  drop(counter);
+
+  drop(core_isolate);
+  assert_eq!(drop_count.load(Ordering::SeqCst), 1);
+}
@@ -3,10 +3,13 @@
 #[macro_use]
 extern crate lazy_static;
 
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::convert::{Into, TryFrom, TryInto};
 use std::ptr::NonNull;
+use std::rc::Rc;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 use rusty_v8 as v8;
 // TODO(piscisaureus): Ideally there would be no need to import this trait.
@@ -160,6 +163,80 @@ fn global_handle_drop() {
   let _g2 = v8::Global::new_from(scope, l2);
 }
 
+#[test]
+fn global_handle_weak() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+
+  // The weak global is boxed so it doesn't move while it is weak.
+  let mut weak = Box::new(v8::Global::<v8::Object>::new());
+  let mut strong = v8::Global::<v8::Object>::new();
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let object = v8::Object::new(scope);
+    weak.set(scope, object);
+    unsafe { weak.set_weak() };
+    let object = v8::Object::new(scope);
+    strong.set(scope, object);
+  }
+  assert!(!weak.is_empty());
+
+  isolate.low_memory_notification();
+  assert!(weak.is_empty());
+  assert!(!strong.is_empty());
+
+  // An empty weak global can be reused.
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    assert!(weak.get(scope).is_none());
+    weak.set(scope, &strong);
+    assert!(!weak.is_empty());
+  }
+  weak.reset(&mut isolate);
+  assert!(weak.is_empty());
+  strong.reset(&mut isolate);
+}
+
+#[test]
+fn global_handle_hash_set() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  let object1: v8::Local<v8::Value> = v8::Object::new(scope).into();
+  let object2: v8::Local<v8::Value> = v8::Object::new(scope).into();
+  let number: v8::Local<v8::Value> = v8::Number::new(scope, 1.5).into();
+
+  let global1 = v8::Global::new_from(scope, object1);
+  assert!(global1 == v8::Global::new_from(scope, object1));
+  assert!(global1 != v8::Global::new_from(scope, object2));
+  assert!(global1 != v8::Global::<v8::Value>::new());
+  assert!(v8::Global::<v8::Value>::new() == v8::Global::<v8::Value>::new());
+
+  let mut set = HashSet::new();
+  assert!(set.insert(v8::Global::new_from(scope, object1)));
+  assert!(!set.insert(v8::Global::new_from(scope, object1)));
+  assert!(set.insert(v8::Global::new_from(scope, object2)));
+  assert!(set.insert(v8::Global::new_from(scope, number)));
+  assert!(!set.insert(v8::Global::new_from(scope, number)));
+  assert!(set.insert(v8::Global::<v8::Value>::new()));
+  assert!(!set.insert(v8::Global::<v8::Value>::new()));
+  assert_eq!(set.len(), 4);
+  assert!(set.contains(&global1));
+  assert!(set.contains(&v8::Global::new_from(scope, object2)));
+
+  // Local handles compare by object identity as well.
+  let object1_again = global1.get(scope).unwrap();
+  assert!(object1_again == object1);
+  assert!(object1 != object2);
+}
+
 #[test]
 fn test_string() {
   let _setup_guard = setup();
@@ -192,6 +269,35 @@ fn test_string() {
   }
 }
 
+#[test]
+fn test_string_new_checked() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let local = v8::String::new_checked(scope, "Hello 🦕 world!").unwrap();
+    assert_eq!(15, local.length());
+  }
+}
+
+#[test]
+fn test_string_to_v8() {
+  use v8::FromV8String;
+  use v8::ToV8String;
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let local = "Hello 🦕 world!".to_v8(scope);
+    let expected = v8::String::new(scope, "Hello 🦕 world!").unwrap();
+    assert!(local.strict_equals(expected.into()));
+    let rust_string = String::from_v8(scope, local);
+    assert_eq!(rust_string, "Hello 🦕 world!");
+  }
+}
+
 #[test]
 #[allow(clippy::float_cmp)]
 fn escapable_handle_scope() {
@@ -315,6 +421,100 @@ fn microtasks() {
   }
 }
 
+#[test]
+fn microtasks_policy() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+
+  assert_eq!(isolate.get_microtasks_policy(), v8::MicrotasksPolicy::Auto);
+  isolate.set_microtasks_policy(v8::MicrotasksPolicy::Explicit);
+  assert_eq!(
+    isolate.get_microtasks_policy(),
+    v8::MicrotasksPolicy::Explicit
+  );
+
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+    let function = v8::Function::new(
+      scope,
+      context,
+      |_: v8::FunctionCallbackScope,
+       _: v8::FunctionCallbackArguments,
+       _: v8::ReturnValue| {
+        CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+      },
+    )
+    .unwrap();
+    scope.isolate().enqueue_microtask(function);
+
+    eval(scope, context, "1 + 1").unwrap();
+    assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 0);
+    scope.isolate().perform_microtask_checkpoint();
+    assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 1);
+  }
+}
+
+#[test]
+fn microtask_queue() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut queue1 =
+    v8::MicrotaskQueue::new(&mut isolate, v8::MicrotasksPolicy::Explicit);
+  let mut queue2 =
+    v8::MicrotaskQueue::new(&mut isolate, v8::MicrotasksPolicy::Explicit);
+
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+
+    static CALL_COUNT1: AtomicUsize = AtomicUsize::new(0);
+    static CALL_COUNT2: AtomicUsize = AtomicUsize::new(0);
+
+    let context1 =
+      unsafe { v8::Context::new_with_microtask_queue(scope, &mut queue1) };
+    let function1 = v8::Function::new(
+      scope,
+      context1,
+      |_: v8::FunctionCallbackScope,
+       _: v8::FunctionCallbackArguments,
+       _: v8::ReturnValue| {
+        CALL_COUNT1.fetch_add(1, Ordering::SeqCst);
+      },
+    )
+    .unwrap();
+    queue1.enqueue_microtask(scope.isolate(), function1);
+
+    let context2 =
+      unsafe { v8::Context::new_with_microtask_queue(scope, &mut queue2) };
+    let function2 = v8::Function::new(
+      scope,
+      context2,
+      |_: v8::FunctionCallbackScope,
+       _: v8::FunctionCallbackArguments,
+       _: v8::ReturnValue| {
+        CALL_COUNT2.fetch_add(1, Ordering::SeqCst);
+      },
+    )
+    .unwrap();
+    queue2.enqueue_microtask(scope.isolate(), function2);
+
+    assert!(!queue1.is_running_microtasks());
+    queue1.perform_checkpoint(scope.isolate());
+    assert_eq!(CALL_COUNT1.load(Ordering::SeqCst), 1);
+    assert_eq!(CALL_COUNT2.load(Ordering::SeqCst), 0);
+
+    queue2.perform_checkpoint(scope.isolate());
+    assert_eq!(CALL_COUNT1.load(Ordering::SeqCst), 1);
+    assert_eq!(CALL_COUNT2.load(Ordering::SeqCst), 1);
+  }
+}
+
 #[test]
 fn get_isolate_from_handle() {
   extern "C" {
@@ -435,6 +635,68 @@ fn array_buffer() {
   }
 }
 
+#[test]
+fn array_buffer_new_backing_store_from_ptr() {
+  let _setup_guard = setup();
+
+  static DELETER_CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+  unsafe extern "C" fn deleter(
+    data: *mut std::ffi::c_void,
+    byte_length: usize,
+    deleter_data: *mut std::ffi::c_void,
+  ) {
+    assert_eq!(byte_length, 16);
+    assert_eq!(deleter_data as usize, 0xdead_beef);
+    libc::free(data);
+    DELETER_CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+  }
+
+  // Dropping the only reference releases the backing store right away.
+  let data = unsafe { libc::malloc(16) };
+  let unique_bs = unsafe {
+    v8::ArrayBuffer::new_backing_store_from_ptr(
+      data,
+      16,
+      deleter,
+      0xdead_beef as *mut _,
+    )
+  };
+  assert_eq!(unique_bs.byte_length(), 16);
+  assert_eq!(unique_bs.data(), data);
+  assert_eq!(DELETER_CALL_COUNT.load(Ordering::SeqCst), 0);
+  drop(unique_bs);
+  assert_eq!(DELETER_CALL_COUNT.load(Ordering::SeqCst), 1);
+
+  // A backing store that is referenced by an ArrayBuffer is released when the
+  // ArrayBuffer goes away too.
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let data = unsafe { libc::malloc(16) };
+    let shared_bs = unsafe {
+      v8::ArrayBuffer::new_backing_store_from_ptr(
+        data,
+        16,
+        deleter,
+        0xdead_beef as *mut _,
+      )
+    }
+    .make_shared();
+    let ab = v8::ArrayBuffer::with_backing_store(scope, &shared_bs);
+    assert_eq!(ab.byte_length(), 16);
+    drop(shared_bs);
+    assert_eq!(DELETER_CALL_COUNT.load(Ordering::SeqCst), 1);
+  }
+  drop(isolate);
+  assert_eq!(DELETER_CALL_COUNT.load(Ordering::SeqCst), 2);
+}
+
 #[test]
 fn backing_store_segfault() {
   let _setup_guard = setup();
@@ -617,9 +879,63 @@ fn try_catch() {
       }
       assert!(tc1.has_caught());
     };
+    {
+      // Conditional rethrow.
+      let mut try_catch_1 = v8::TryCatch::new(scope);
+      let tc1 = try_catch_1.enter();
+      {
+        let mut try_catch_2 = v8::TryCatch::new(scope);
+        let tc2 = try_catch_2.enter();
+        eval(scope, context, "1 + 1");
+        assert!(!tc2.rethrow_if_caught());
+        eval(scope, context, "throw 'baz'");
+        assert!(tc2.rethrow_if_caught());
+      }
+      assert!(tc1.has_caught());
+      assert!(tc1
+        .exception(scope)
+        .unwrap()
+        .strict_equals(v8_str(scope, "baz").into()));
+    };
   }
 }
 
+#[test]
+fn set_stack_limit() {
+  let _setup_guard = setup();
+  // Run V8 on a thread with a small stack, which the default stack limit
+  // wouldn't account for.
+  let t = std::thread::Builder::new()
+    .stack_size(1 << 20)
+    .spawn(|| {
+      let mut isolate = v8::Isolate::new(Default::default());
+      let stack_top = 0u8;
+      let stack_limit = &stack_top as *const u8 as usize - (512 << 10);
+      isolate.set_stack_limit(stack_limit);
+
+      let mut hs = v8::HandleScope::new(&mut isolate);
+      let scope = hs.enter();
+      let context = v8::Context::new(scope);
+      let mut cs = v8::ContextScope::new(scope, context);
+      let scope = cs.enter();
+      let mut try_catch = v8::TryCatch::new(scope);
+      let tc = try_catch.enter();
+      let result =
+        eval(scope, context, "(function f(n) { return f(n + 1); })(0)");
+      assert!(result.is_none());
+      assert!(tc.has_caught());
+      let exception = tc.exception(scope).unwrap();
+      assert!(exception.is_native_error());
+      let message = tc.message(scope).unwrap().get(scope);
+      assert_eq!(
+        message.to_rust_string_lossy(scope),
+        "Uncaught RangeError: Maximum call stack size exceeded"
+      );
+    })
+    .unwrap();
+  t.join().expect("join t");
+}
+
 #[test]
 fn try_catch_caught_lifetime() {
   let _setup_guard = setup();
@@ -655,6 +971,113 @@ fn try_catch_caught_lifetime() {
     .contains("DANG"));
 }
 
+#[test]
+fn try_catch_exception_as() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+  {
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    assert!(tc.exception_as::<v8::Object>(scope).is_none());
+    let result = eval(scope, context, "null.foo");
+    assert!(result.is_none());
+    let exception = tc.exception_as::<v8::Object>(scope).unwrap();
+    let key = v8_str(scope, "message");
+    let message = exception.get(scope, context, key.into()).unwrap();
+    assert_eq!(
+      message.to_rust_string_lossy(scope),
+      "Cannot read property 'foo' of null"
+    );
+    let error_type = v8::Exception::get_error_type(scope, exception.into());
+    assert_eq!(error_type, Some(v8::NativeErrorKind::TypeError));
+    assert!(tc.exception_as::<v8::String>(scope).is_none());
+  }
+  {
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    let result = eval(scope, context, "throw 'oops'");
+    assert!(result.is_none());
+    assert!(tc.exception_as::<v8::Object>(scope).is_none());
+    let exception = tc.exception_as::<v8::String>(scope).unwrap();
+    assert_eq!(exception.to_rust_string_lossy(scope), "oops");
+  }
+}
+
+#[test]
+fn scope_throw_error() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  fn type_error_callback(
+    scope: v8::FunctionCallbackScope,
+    _: v8::FunctionCallbackArguments,
+    _: v8::ReturnValue,
+  ) {
+    let result = scope.throw_type_error("bad arg");
+    assert!(result.is_undefined());
+  }
+  fn range_error_callback(
+    scope: v8::FunctionCallbackScope,
+    _: v8::FunctionCallbackArguments,
+    _: v8::ReturnValue,
+  ) {
+    scope.throw_range_error("out of range");
+  }
+  fn error_callback(
+    scope: v8::FunctionCallbackScope,
+    _: v8::FunctionCallbackArguments,
+    _: v8::ReturnValue,
+  ) {
+    scope.throw_error("generic");
+  }
+
+  fn set_function<'sc>(
+    scope: &mut impl v8::ToLocal<'sc>,
+    context: v8::Local<v8::Context>,
+    name: &str,
+    callback: impl v8::MapFnTo<v8::FunctionCallback>,
+  ) {
+    let name = v8_str(scope, name);
+    let function = v8::Function::new(scope, context, callback).unwrap();
+    let global = context.global(scope);
+    global.set(context, name.into(), function.into());
+  }
+  set_function(scope, context, "throwTypeError", type_error_callback);
+  set_function(scope, context, "throwRangeError", range_error_callback);
+  set_function(scope, context, "throwError", error_callback);
+
+  let source = r#"
+    function check(f, type) {
+      try {
+        f();
+      } catch (e) {
+        return e.constructor === type ? e.message : "wrong type";
+      }
+      return "not thrown";
+    }
+    [
+      check(throwTypeError, TypeError),
+      check(throwRangeError, RangeError),
+      check(throwError, Error),
+    ].join();
+  "#;
+  let result = eval(scope, context, source).unwrap();
+  assert_eq!(
+    result.to_rust_string_lossy(scope),
+    "bad arg,out of range,generic"
+  );
+}
+
 #[test]
 fn throw_exception() {
   let _setup_guard = setup();
@@ -745,43 +1168,332 @@ fn terminate_execution() {
   t.join().expect("join t");
 }
 
-// TODO(ry) This test should use threads
 #[test]
-fn request_interrupt_small_scripts() {
+fn locker() {
+  let _setup_guard = setup();
+  let isolate = v8::Isolate::new(Default::default());
+  let shared = Arc::new(v8::SharedIsolate::new(isolate));
+  assert!(!shared.is_locked());
+
+  let threads = (1..=2)
+    .map(|thread_id: i32| {
+      let shared = shared.clone();
+      std::thread::spawn(move || {
+        for i in 0..10 {
+          let mut locker = shared.lock();
+          assert!(shared.is_locked());
+          {
+            let mut hs = v8::HandleScope::new(&mut locker);
+            let scope = hs.enter();
+            let context = v8::Context::new(scope);
+            let mut cs = v8::ContextScope::new(scope, context);
+            let scope = cs.enter();
+            let code = format!("{} * {}", thread_id, i);
+            let result = eval(scope, context, &code).unwrap();
+            assert_eq!(
+              result.integer_value(scope).unwrap(),
+              (thread_id * i) as i64
+            );
+          }
+          {
+            // Give the other thread a chance to lock the isolate.
+            let _unlocker = v8::Unlocker::new(&mut locker);
+            assert!(!shared.is_locked());
+            std::thread::yield_now();
+          }
+          assert!(shared.is_locked());
+        }
+      })
+    })
+    .collect::<Vec<_>>();
+
+  for t in threads {
+    t.join().expect("join t");
+  }
+  assert!(!shared.is_locked());
+}
+
+#[test]
+#[should_panic(
+  expected = "the isolate is already locked by the current thread"
+)]
+fn shared_isolate_lock_twice() {
+  let _setup_guard = setup();
+  let shared = v8::SharedIsolate::new(v8::Isolate::new(Default::default()));
+  let _locker = shared.lock();
+  let _locker = shared.lock();
+}
+
+#[test]
+fn locker_nested() {
+  use v8::InIsolate;
   let _setup_guard = setup();
   let mut isolate = v8::Isolate::new(Default::default());
-  let handle = isolate.thread_safe_handle();
+  let mut locker = v8::Locker::new(&mut isolate);
+  assert!(v8::Locker::is_locked(locker.isolate()));
+  {
+    let mut inner = v8::Locker::new(locker.isolate());
+    assert!(v8::Locker::is_locked(inner.isolate()));
+  }
+  assert!(v8::Locker::is_locked(locker.isolate()));
+  drop(locker);
+  assert!(!v8::Locker::is_locked(&isolate));
+}
+
+#[test]
+fn isolate_get_current() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let isolate_ptr = &mut *isolate as *mut v8::Isolate;
+  assert_eq!(v8::Isolate::get_current(), None);
+  {
+    let _locker = v8::Locker::new(&mut isolate);
+    assert_eq!(v8::Isolate::get_current().unwrap().as_ptr(), isolate_ptr);
+  }
+  assert_eq!(v8::Isolate::get_current(), None);
+}
+
+#[test]
+fn adjust_amount_of_external_allocated_memory() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  const TEN_MB: i64 = 10 * 1024 * 1024;
+
+  let initial = isolate.adjust_amount_of_external_allocated_memory(0);
+  let adjusted = isolate.adjust_amount_of_external_allocated_memory(TEN_MB);
+  assert_eq!(adjusted, initial + TEN_MB);
   {
     let mut hs = v8::HandleScope::new(&mut isolate);
     let scope = hs.enter();
     let context = v8::Context::new(scope);
     let mut cs = v8::ContextScope::new(scope, context);
     let scope = cs.enter();
-
-    static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
-    extern "C" fn callback(
-      _isolate: &mut v8::Isolate,
-      data: *mut std::ffi::c_void,
-    ) {
-      assert_eq!(data, std::ptr::null_mut());
-      CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+    for _ in 0..1000 {
+      v8::Object::new(scope);
     }
-    handle.request_interrupt(callback, std::ptr::null_mut());
-    eval(scope, context, "(function(x){return x;})(1);");
-    assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 1);
   }
+  let adjusted = isolate.adjust_amount_of_external_allocated_memory(-TEN_MB);
+  assert_eq!(adjusted, initial);
 }
 
 #[test]
-fn add_message_listener() {
+fn isolate_is_in_use() {
+  use v8::InIsolate;
   let _setup_guard = setup();
   let mut isolate = v8::Isolate::new(Default::default());
-  isolate.set_capture_stack_trace_for_uncaught_exceptions(true, 32);
-
-  static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+  assert!(!isolate.is_in_use());
+  {
+    let mut locker = v8::Locker::new(&mut isolate);
+    assert!(locker.isolate().is_in_use());
+  }
+  assert!(!isolate.is_in_use());
+}
 
-  extern "C" fn check_message_0(
-    message: v8::Local<v8::Message>,
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(
+  expected = "cannot dispose an isolate that is still entered by a thread"
+)]
+fn isolate_dispose_while_entered() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  std::mem::forget(v8::Locker::new(&mut isolate));
+  drop(isolate);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the isolate is not entered by the current thread")]
+fn isolate_run_microtasks_while_not_entered() {
+  let _setup_guard = setup();
+  let mut isolate1 = v8::Isolate::new(Default::default());
+  let mut isolate2 = v8::Isolate::new(Default::default());
+  let _locker = v8::Locker::new(&mut isolate1);
+  isolate2.run_microtasks();
+}
+
+#[test]
+fn set_fatal_error_handler() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+
+  static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+  extern "C" fn fatal_error_handler(
+    _location: *const std::os::raw::c_char,
+    _message: *const std::os::raw::c_char,
+  ) {
+    CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+  }
+  isolate.set_fatal_error_handler(fatal_error_handler);
+
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let result = eval(scope, context, "1 + 2").unwrap();
+    assert_eq!(result.integer_value(scope).unwrap(), 3);
+  }
+  assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn near_heap_limit_callback() {
+  let _setup_guard = setup();
+  let heap_limit = 10 << 20; // 10 MB.
+  let params = v8::Isolate::create_params().heap_limits(0, heap_limit);
+  let mut isolate = v8::Isolate::new(params);
+
+  static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+  extern "C" fn callback(
+    data: *mut std::ffi::c_void,
+    current_heap_limit: usize,
+    _initial_heap_limit: usize,
+  ) -> usize {
+    assert_eq!(data as usize, 42);
+    // Only extend the heap limit once.
+    if CALL_COUNT.fetch_add(1, Ordering::SeqCst) == 0 {
+      current_heap_limit * 2
+    } else {
+      current_heap_limit
+    }
+  }
+  isolate.add_near_heap_limit_callback(callback, 42 as *mut _);
+
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    eval(scope, context, "var chunks = [];").unwrap();
+    // Retain 1 MB chunks until the heap limit is approached. The callback
+    // raises the limit, so the script should keep running past that point.
+    let mut chunks = 0;
+    while CALL_COUNT.load(Ordering::SeqCst) == 0 {
+      assert!(chunks < 100, "near heap limit callback never invoked");
+      let result =
+        eval(scope, context, "chunks.push(new Array(1 << 17).fill(0.5))")
+          .unwrap();
+      chunks += 1;
+      assert_eq!(result.integer_value(scope).unwrap(), chunks);
+    }
+    for _ in 0..3 {
+      let result =
+        eval(scope, context, "chunks.push(new Array(1 << 17).fill(0.5))")
+          .unwrap();
+      chunks += 1;
+      assert_eq!(result.integer_value(scope).unwrap(), chunks);
+    }
+  }
+
+  assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 1);
+  isolate.remove_near_heap_limit_callback(callback, heap_limit);
+}
+
+// TODO(ry) This test should use threads
+#[test]
+fn request_interrupt_small_scripts() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let handle = isolate.thread_safe_handle();
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+    extern "C" fn callback(
+      _isolate: &mut v8::Isolate,
+      data: *mut std::ffi::c_void,
+    ) {
+      assert_eq!(data, std::ptr::null_mut());
+      CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+    handle.request_interrupt(callback, std::ptr::null_mut());
+    eval(scope, context, "(function(x){return x;})(1);");
+    assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 1);
+  }
+}
+
+#[test]
+fn request_interrupt_from_multiple_threads() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let handle = isolate.thread_safe_handle();
+
+  static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+  static DATA_SUM: AtomicUsize = AtomicUsize::new(0);
+  extern "C" fn callback(
+    _isolate: &mut v8::Isolate,
+    data: *mut std::ffi::c_void,
+  ) {
+    CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+    DATA_SUM.fetch_add(data as usize, Ordering::SeqCst);
+  }
+
+  let threads = (1..=2)
+    .map(|id: usize| {
+      let handle = handle.clone();
+      std::thread::spawn(move || {
+        assert!(handle.request_interrupt(callback, id as *mut _));
+      })
+    })
+    .collect::<Vec<_>>();
+  for t in threads {
+    t.join().expect("join t");
+  }
+
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    eval(scope, context, "(function(x){return x;})(1);");
+  }
+  assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 2);
+  assert_eq!(DATA_SUM.load(Ordering::SeqCst), 3);
+
+  drop(isolate);
+  assert!(!handle.request_interrupt(callback, std::ptr::null_mut()));
+}
+
+#[test]
+fn increase_heap_limit_for_debugging() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let handle = isolate.thread_safe_handle();
+  assert!(handle.increase_heap_limit_for_debugging());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let result = eval(scope, context, "1 + 1").unwrap();
+    assert_eq!(result.integer_value(scope).unwrap(), 2);
+  }
+  assert!(handle.restore_original_heap_limit());
+  drop(isolate);
+  assert!(!handle.increase_heap_limit_for_debugging());
+  assert!(!handle.restore_original_heap_limit());
+}
+
+#[test]
+fn add_message_listener() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  isolate.set_capture_stack_trace_for_uncaught_exceptions(true, 32);
+
+  static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+  extern "C" fn check_message_0(
+    message: v8::Local<v8::Message>,
     _exception: v8::Local<v8::Value>,
   ) {
     let mut sc = v8::CallbackScope::new(message);
@@ -796,9 +1508,9 @@ fn add_message_listener() {
     assert_eq!(message.get_start_position(), 0);
     assert_eq!(message.get_end_position(), 1);
     assert_eq!(message.get_wasm_function_index(), -1);
-    assert!(message.error_level() >= 0);
-    assert_eq!(message.get_start_column(), 0);
-    assert_eq!(message.get_end_column(), 1);
+    assert_eq!(message.error_level(), v8::MessageErrorLevel::ERROR);
+    assert_eq!(message.get_start_column(), Some(0));
+    assert_eq!(message.get_end_column(), Some(1));
     assert!(!message.is_shared_cross_origin());
     assert!(!message.is_opaque());
     let stack_trace = message.get_stack_trace(scope).unwrap();
@@ -831,6 +1543,57 @@ fn add_message_listener() {
   }
 }
 
+#[test]
+fn add_message_listener_with_error_level() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+
+  static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+  extern "C" fn check_message(
+    message: v8::Local<v8::Message>,
+    _data: v8::Local<v8::Value>,
+  ) {
+    let mut sc = v8::CallbackScope::new(message);
+    let mut sc = v8::HandleScope::new(sc.enter());
+    let scope = sc.enter();
+    assert_eq!(message.error_level(), v8::MessageErrorLevel::WARNING);
+    let message_str = message.get(scope).to_rust_string_lossy(scope);
+    assert!(message_str.starts_with("Invalid asm.js"));
+    CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+  }
+  isolate.add_message_listener_with_error_level(
+    check_message,
+    v8::MessageErrorLevel::WARNING,
+  );
+
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    // An asm.js module that fails validation is reported as a warning and
+    // falls back to being executed as regular JavaScript.
+    let result = eval(
+      scope,
+      context,
+      r#"
+        function Module() {
+          "use asm";
+          function f() { return "not asm"; }
+          return f;
+        }
+        Module()();
+      "#,
+    )
+    .unwrap();
+    let result = result.to_string(scope).unwrap();
+    assert_eq!(result.to_rust_string_lossy(scope), "not asm");
+    assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 1);
+  }
+}
+
 fn unexpected_module_resolve_callback<'a>(
   _context: v8::Local<'a, v8::Context>,
   _specifier: v8::Local<'a, v8::String>,
@@ -964,6 +1727,55 @@ fn set_flags_from_command_line() {
   );
 }
 
+#[test]
+fn set_flags_from_string() {
+  let _setup_guard = setup();
+  v8::V8::set_flags_from_string("--expose-gc");
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+  let result = eval(scope, context, "typeof gc").unwrap();
+  assert_eq!(result.to_rust_string_lossy(scope), "function");
+}
+
+#[test]
+fn platform_pump_message_loop() {
+  let _setup_guard = setup();
+  let platform = v8::V8::get_current_platform().unwrap();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  // Asynchronous WebAssembly compilation finishes in a foreground task.
+  let promise = eval(
+    scope,
+    context,
+    "WebAssembly.compile(new Uint8Array([0, 97, 115, 109, 1, 0, 0, 0]))",
+  )
+  .unwrap();
+  let promise = v8::Local::<v8::Promise>::try_from(promise).unwrap();
+  scope.isolate().run_microtasks();
+  assert_eq!(promise.state(), v8::PromiseState::Pending);
+  while promise.state() == v8::PromiseState::Pending {
+    assert!(v8::Platform::pump_message_loop(
+      platform,
+      scope.isolate(),
+      true
+    ));
+    scope.isolate().run_microtasks();
+  }
+  assert_eq!(promise.state(), v8::PromiseState::Fulfilled);
+
+  // The default platform has no idle task support, so this is a no-op.
+  v8::Platform::run_idle_tasks(platform, scope.isolate(), 0.001);
+}
+
 #[test]
 fn inspector_string_view() {
   let chars = b"Hello world!";
@@ -1010,12 +1822,14 @@ fn test_primitives() {
     assert!(undefined.is_null_or_undefined());
 
     let true_ = v8::Boolean::new(scope, true);
+    assert!(true_.value());
     assert!(true_.is_true());
     assert!(!true_.is_undefined());
     assert!(!true_.is_null());
     assert!(!true_.is_null_or_undefined());
 
     let false_ = v8::Boolean::new(scope, false);
+    assert!(!false_.value());
     assert!(false_.is_false());
     assert!(!false_.is_undefined());
     assert!(!false_.is_null());
@@ -1023,6 +1837,81 @@ fn test_primitives() {
   }
 }
 
+#[test]
+#[allow(clippy::float_cmp)]
+fn integer_value_checked() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let int = v8::Integer::new(scope, i32::max_value());
+    assert_eq!(int.value_checked(), Ok(i32::max_value() as i64));
+    let int = v8::Integer::new(scope, i32::min_value());
+    assert_eq!(int.value_checked(), Ok(i32::min_value() as i64));
+
+    let max_safe = v8::Integer::MAX_SAFE_INTEGER;
+    let number = v8::Number::new(scope, max_safe as f64);
+    let int = number.to_integer(scope).unwrap();
+    assert_eq!(int.value_checked(), Ok(max_safe));
+
+    let number = v8::Number::new(scope, (1u64 << 60) as f64);
+    let int = number.to_integer(scope).unwrap();
+    let err = int.value_checked().unwrap_err();
+    assert_eq!(err.value(), (1u64 << 60) as f64);
+    let number = v8::Number::new(scope, -((1u64 << 60) as f64));
+    let int = number.to_integer(scope).unwrap();
+    assert!(int.value_checked().is_err());
+  }
+}
+
+#[test]
+fn number_nan_and_infinity() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let nan = v8::Number::nan(scope);
+    assert!(nan.value().is_nan());
+    assert!(nan.is_nan());
+    assert!(!v8::Number::new(scope, 1.5).is_nan());
+
+    let infinity = v8::Number::infinity(scope);
+    assert_eq!(infinity.value(), f64::INFINITY);
+    assert!(!infinity.is_nan());
+    let negative_infinity = v8::Number::negative_infinity(scope);
+    assert_eq!(negative_infinity.value(), f64::NEG_INFINITY);
+
+    let global = context.global(scope);
+    let values = [
+      ("nan", nan),
+      ("infinity", infinity),
+      ("negativeInfinity", negative_infinity),
+    ];
+    for (name, value) in values.iter() {
+      let name = v8_str(scope, name);
+      global.set(context, name.into(), (*value).into());
+    }
+    let result = eval(
+      scope,
+      context,
+      "Number.isNaN(nan) && infinity === Infinity && \
+       negativeInfinity === -Infinity",
+    )
+    .unwrap();
+    assert!(result.is_true());
+  }
+}
+
 #[test]
 fn exception() {
   let _setup_guard = setup();
@@ -1048,6 +1937,41 @@ fn exception() {
   assert!(v8::Exception::get_stack_trace(scope, exception).is_none());
 }
 
+#[test]
+fn exception_get_error_type() {
+  use v8::NativeErrorKind as Kind;
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  let cases = [
+    ("throw new Error('e')", Some(Kind::Error)),
+    ("eval.call(null, '(')", Some(Kind::SyntaxError)),
+    ("throw new EvalError('e')", Some(Kind::EvalError)),
+    ("new Array(-1)", Some(Kind::RangeError)),
+    ("undefined_variable", Some(Kind::ReferenceError)),
+    ("null.foo", Some(Kind::TypeError)),
+    (
+      "class MyError extends TypeError {}; throw new MyError('e')",
+      Some(Kind::TypeError),
+    ),
+    ("throw 'not an error'", None),
+    ("throw { name: 'TypeError' }", None),
+  ];
+  for (code, expected) in cases.iter() {
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    assert!(eval(scope, context, code).is_none());
+    let exception = tc.exception(scope).unwrap();
+    let actual = v8::Exception::get_error_type(scope, exception);
+    assert_eq!(actual, *expected, "{}", code);
+  }
+}
+
 #[test]
 fn create_message_argument_lifetimes() {
   let _setup_guard = setup();
@@ -1084,20 +2008,67 @@ fn create_message_argument_lifetimes() {
 }
 
 #[test]
-fn json() {
+fn create_message_from_thrown_value() {
   let _setup_guard = setup();
   let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
   {
-    let mut hs = v8::HandleScope::new(&mut isolate);
-    let scope = hs.enter();
-    let context = v8::Context::new(scope);
-    let mut cs = v8::ContextScope::new(scope, context);
-    let scope = cs.enter();
-    let json_string = v8_str(scope, "{\"a\": 1, \"b\": 2}");
-    let maybe_value = v8::json::parse(scope, context, json_string);
-    assert!(maybe_value.is_some());
-    let value = maybe_value.unwrap();
-    let maybe_stringified = v8::json::stringify(scope, context, value);
+    let check_message = v8::Function::new(
+      scope,
+      context,
+      |scope: v8::FunctionCallbackScope,
+       args: v8::FunctionCallbackArguments,
+       mut rv: v8::ReturnValue| {
+        let context = scope.get_current_context().unwrap();
+        let exception = args.get(0);
+        assert!(!exception.is_native_error());
+        let message = v8::Exception::create_message(scope, exception);
+        assert_eq!(message.get_line_number(context), Some(2));
+        let start_column = message.get_start_column().unwrap();
+        let end_column = message.get_end_column().unwrap();
+        assert!(start_column < end_column);
+        let source_line = message.get_source_line(scope, context).unwrap();
+        assert_eq!(
+          source_line.to_rust_string_lossy(scope),
+          "  checkMessage(e);"
+        );
+        rv.set(v8::Boolean::new(scope, true).into())
+      },
+    )
+    .unwrap();
+    let global = context.global(scope);
+    let name = v8_str(scope, "checkMessage");
+    global.set(context, name.into(), check_message.into());
+    let result = eval(
+      scope,
+      context,
+      "try { throw { custom: 'value' }; } catch (e) {\n  checkMessage(e);\n}",
+    )
+    .unwrap();
+    assert!(result.is_true());
+  }
+}
+
+#[test]
+fn json() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let json_string = v8_str(scope, "{\"a\": 1, \"b\": 2}");
+    let maybe_value = v8::json::parse(scope, context, json_string);
+    assert!(maybe_value.is_some());
+    let value = maybe_value.unwrap();
+    let maybe_stringified = v8::json::stringify(scope, context, value);
     assert!(maybe_stringified.is_some());
     let stringified = maybe_stringified.unwrap();
     let rust_str = stringified.to_rust_string_lossy(scope);
@@ -1105,6 +2076,33 @@ fn json() {
   }
 }
 
+#[test]
+fn json_parse_error() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    let json_string = v8_str(scope, "{bad");
+    let maybe_value = v8::json::parse(scope, context, json_string);
+    assert!(maybe_value.is_none());
+    assert!(tc.has_caught());
+    let exception = tc.exception(scope).unwrap();
+    assert!(exception.is_native_error());
+    let exception_str = exception
+      .to_string(scope)
+      .unwrap()
+      .to_rust_string_lossy(scope);
+    assert!(exception_str.starts_with("SyntaxError: "));
+    assert!(exception_str.contains("JSON"));
+  }
+}
+
 #[test]
 fn object_template() {
   let _setup_guard = setup();
@@ -1181,169 +2179,1319 @@ fn object_template_from_function_template() {
 }
 
 #[test]
-fn object() {
+fn function_template_inherit() {
   let _setup_guard = setup();
   let mut isolate = v8::Isolate::new(Default::default());
   {
     let mut hs = v8::HandleScope::new(&mut isolate);
     let scope = hs.enter();
+    let constructor = |_: v8::FunctionCallbackScope,
+                       _: v8::FunctionCallbackArguments,
+                       _: v8::ReturnValue| {};
+    let mut parent_templ = v8::FunctionTemplate::new(scope, constructor);
+    let mut child_templ = v8::FunctionTemplate::new(scope, constructor);
+    child_templ.inherit(parent_templ);
     let context = v8::Context::new(scope);
     let mut cs = v8::ContextScope::new(scope, context);
     let scope = cs.enter();
-    let null: v8::Local<v8::Value> = v8::null(scope).into();
-    let n1: v8::Local<v8::Name> = v8::String::new(scope, "a").unwrap().into();
-    let n2: v8::Local<v8::Name> = v8::String::new(scope, "b").unwrap().into();
-    let v1: v8::Local<v8::Value> = v8::Number::new(scope, 1.0).into();
-    let v2: v8::Local<v8::Value> = v8::Number::new(scope, 2.0).into();
-    let object = v8::Object::with_prototype_and_properties(
-      scope,
-      null,
-      &[n1, n2],
-      &[v1, v2],
-    );
-    assert!(!object.is_null_or_undefined());
-    let lhs = object.creation_context(scope).global(scope);
-    let rhs = context.global(scope);
-    assert!(lhs.strict_equals(rhs.into()));
-
-    let object_ = v8::Object::new(scope);
-    assert!(!object_.is_null_or_undefined());
-    let id = object_.get_identity_hash();
-    assert_ne!(id, 0);
+    let parent = parent_templ.get_function(scope, context).unwrap();
+    let child = child_templ.get_function(scope, context).unwrap();
+    let global = context.global(scope);
+    let name = v8_str(scope, "Parent");
+    global.set(context, name.into(), parent.into());
+    let name = v8_str(scope, "Child");
+    global.set(context, name.into(), child.into());
+    let source = r#"
+      Parent.prototype.method = function() { return 42; };
+      const child = new Child();
+      child instanceof Parent && child.method()
+    "#;
+    let actual = eval(scope, context, source).unwrap();
+    let expected = v8::Integer::new(scope, 42);
+    assert!(expected.strict_equals(actual));
   }
 }
 
 #[test]
-fn array() {
+fn object_template_set_accessor_with_data() {
   let _setup_guard = setup();
   let mut isolate = v8::Isolate::new(Default::default());
+  let isolate_ptr = &mut *isolate as *mut v8::Isolate;
   {
     let mut hs = v8::HandleScope::new(&mut isolate);
     let scope = hs.enter();
+
+    static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+    static ISOLATE_PTR: AtomicUsize = AtomicUsize::new(0);
+    ISOLATE_PTR.store(isolate_ptr as usize, Ordering::SeqCst);
+    let getter = |scope: v8::PropertyCallbackScope,
+                  _: v8::Local<v8::Name>,
+                  args: v8::PropertyCallbackArguments,
+                  mut rv: v8::ReturnValue| {
+      let isolate_ptr = args.get_isolate().as_ptr();
+      assert_eq!(isolate_ptr as usize, ISOLATE_PTR.load(Ordering::SeqCst));
+      let key = v8_str(scope, "isHolder");
+      let context = scope.get_current_context().unwrap();
+      let is_holder = args.holder().get(scope, context, key.into()).unwrap();
+      assert!(is_holder.is_true());
+      if CALL_COUNT.fetch_add(1, Ordering::SeqCst) == 0 {
+        assert!(args.this() == args.holder());
+      } else {
+        assert!(args.this() != args.holder());
+      }
+      rv.set(args.data());
+    };
+
+    let templ = v8::ObjectTemplate::new(scope);
+    let name = v8_str(scope, "field");
+    let data = v8_str(scope, "callback data");
+    templ.set_accessor_with_data(name.into(), getter, data.into());
     let context = v8::Context::new(scope);
     let mut cs = v8::ContextScope::new(scope, context);
     let scope = cs.enter();
-    let s1 = v8::String::new(scope, "a").unwrap();
-    let s2 = v8::String::new(scope, "b").unwrap();
-    let array = v8::Array::new(scope, 2);
-    assert_eq!(array.length(), 2);
-    let lhs = array.creation_context(scope).global(scope);
-    let rhs = context.global(scope);
-    assert!(lhs.strict_equals(rhs.into()));
-    array.set_index(context, 0, s1.into());
-    array.set_index(context, 1, s2.into());
-
-    let maybe_v1 = array.get_index(scope, context, 0);
-    assert!(maybe_v1.is_some());
-    assert!(maybe_v1.unwrap().same_value(s1.into()));
-    let maybe_v2 = array.get_index(scope, context, 1);
-    assert!(maybe_v2.is_some());
-    assert!(maybe_v2.unwrap().same_value(s2.into()));
-
-    let array = v8::Array::new_with_elements(scope, &[]);
-    assert_eq!(array.length(), 0);
-
-    let array = v8::Array::new_with_elements(scope, &[s1.into(), s2.into()]);
-    assert_eq!(array.length(), 2);
-
-    let maybe_v1 = array.get_index(scope, context, 0);
-    assert!(maybe_v1.is_some());
-    assert!(maybe_v1.unwrap().same_value(s1.into()));
-    let maybe_v2 = array.get_index(scope, context, 1);
-    assert!(maybe_v2.is_some());
-    assert!(maybe_v2.unwrap().same_value(s2.into()));
+    let obj = templ.new_instance(scope, context).unwrap();
+    let name = v8_str(scope, "obj");
+    context.global(scope).set(context, name.into(), obj.into());
+    let source = r#"
+      obj.isHolder = true;
+      const child = Object.create(obj);
+      [obj.field, child.field].toString()
+    "#;
+    let actual = eval(scope, context, source).unwrap();
+    let expected = v8_str(scope, "callback data,callback data");
+    assert!(expected.strict_equals(actual));
+    assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 2);
   }
 }
 
 #[test]
-fn create_data_property() {
+fn function_template_prototype_and_instance_template() {
   let _setup_guard = setup();
   let mut isolate = v8::Isolate::new(Default::default());
   {
     let mut hs = v8::HandleScope::new(&mut isolate);
     let scope = hs.enter();
+    let constructor = |_: v8::FunctionCallbackScope,
+                       _: v8::FunctionCallbackArguments,
+                       _: v8::ReturnValue| {};
+    let getter = |scope: v8::PropertyCallbackScope,
+                  _: v8::Local<v8::Name>,
+                  _: v8::PropertyCallbackArguments,
+                  mut rv: v8::ReturnValue| {
+      rv.set(v8::Integer::new(scope, 7).into());
+    };
+    let mut function_templ = v8::FunctionTemplate::new(scope, constructor);
+    let prototype_templ = function_templ.prototype_template(scope);
+    let name = v8_str(scope, "proto_value");
+    let value = v8_str(scope, "from prototype");
+    prototype_templ.set(name.into(), value.into());
+    let instance_templ = function_templ.instance_template(scope);
+    let name = v8_str(scope, "field");
+    instance_templ.set_accessor(name.into(), getter);
     let context = v8::Context::new(scope);
     let mut cs = v8::ContextScope::new(scope, context);
     let scope = cs.enter();
+    let function = function_templ.get_function(scope, context).unwrap();
+    let name = v8_str(scope, "Klass");
+    context
+      .global(scope)
+      .set(context, name.into(), function.into());
+    let source = r#"
+      {
+        const instance = new Klass();
+        [
+          instance.proto_value,
+          instance.hasOwnProperty("proto_value"),
+          instance.field,
+        ].toString()
+      }
+    "#;
+    let actual = eval(scope, context, source).unwrap();
+    let expected = v8_str(scope, "from prototype,false,7");
+    assert!(expected.strict_equals(actual));
+  }
+}
 
-    eval(scope, context, "var a = {};");
+#[test]
+fn function_template_length_and_read_only_prototype() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let mut function_templ =
+      v8::FunctionTemplate::new(scope, fortytwo_callback);
+    function_templ.set_length(2);
+    function_templ.read_only_prototype();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let function = function_templ.get_function(scope, context).unwrap();
+    let name = v8_str(scope, "f");
+    context
+      .global(scope)
+      .set(context, name.into(), function.into());
+    let actual = eval(scope, context, "f.length === 2").unwrap();
+    assert!(actual.is_true());
+    let source = r#"
+      {
+        const prototype = f.prototype;
+        f.prototype = {};
+        f.prototype === prototype
+      }
+    "#;
+    let actual = eval(scope, context, source).unwrap();
+    assert!(actual.is_true());
+  }
+}
 
-    let key = v8_str(scope, "a");
-    let obj = context
+#[test]
+fn function_template_static_property() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let function_templ = v8::FunctionTemplate::new(scope, fortytwo_callback);
+    let name = v8_str(scope, "answer");
+    let value = v8::Integer::new(scope, 42);
+    function_templ.set(name.into(), value.into());
+    let name = v8_str(scope, "constant");
+    let value = v8_str(scope, "fixed");
+    function_templ.set_with_attr(
+      name.into(),
+      value.into(),
+      v8::READ_ONLY + v8::DONT_ENUM,
+    );
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let function = function_templ.get_function(scope, context).unwrap();
+    let name = v8_str(scope, "f");
+    context
       .global(scope)
-      .get(scope, context, key.into())
-      .unwrap();
-    assert!(obj.is_object());
-    let obj = obj.to_object(scope).unwrap();
-    let key = v8_str(scope, "foo");
-    let value = v8_str(scope, "bar");
-    assert!(obj
-      .create_data_property(context, key.into(), value.into())
-      .unwrap());
-    let actual = obj.get(scope, context, key.into()).unwrap();
-    assert!(value.strict_equals(actual));
+      .set(context, name.into(), function.into());
+    let actual = eval(scope, context, "f.answer === 42").unwrap();
+    assert!(actual.is_true());
+    let actual = eval(scope, context, "f() === 42 && f.answer === 42").unwrap();
+    assert!(actual.is_true());
+    let source = r#"
+      f.constant = "changed";
+      f.constant === "fixed" && !Object.keys(f).includes("constant")
+    "#;
+    let actual = eval(scope, context, source).unwrap();
+    assert!(actual.is_true());
+  }
+}
 
-    let key2 = v8_str(scope, "foo2");
-    assert!(obj.set(context, key2.into(), value.into()).unwrap());
-    let actual = obj.get(scope, context, key2.into()).unwrap();
-    assert!(value.strict_equals(actual));
+#[test]
+fn object_template_internal_fields() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let object_templ = v8::ObjectTemplate::new(scope);
+    assert_eq!(object_templ.internal_field_count(), 0);
+    object_templ.set_internal_field_count(2);
+    assert_eq!(object_templ.internal_field_count(), 2);
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let object = object_templ.new_instance(scope, context).unwrap();
+    assert_eq!(object.internal_field_count(), 2);
+
+    let mut data = 42u64;
+    let data_ptr = &mut data as *mut u64 as *mut std::ffi::c_void;
+    let external = v8::External::new(scope, data_ptr);
+    object.set_internal_field(0, external.into());
+    let field = object.get_internal_field(scope, 0);
+    let field = v8::Local::<v8::External>::try_from(field).unwrap();
+    assert_eq!(field.value(), data_ptr);
+    assert_eq!(unsafe { *(field.value() as *mut u64) }, 42);
+
+    object.set_aligned_pointer_in_internal_field(1, data_ptr);
+    let ptr = unsafe { object.get_aligned_pointer_from_internal_field(1) };
+    assert_eq!(ptr, data_ptr);
   }
 }
 
 #[test]
-fn object_set_accessor() {
+fn object_aligned_pointer_in_internal_field() {
   let _setup_guard = setup();
   let mut isolate = v8::Isolate::new(Default::default());
   let mut hs = v8::HandleScope::new(&mut isolate);
   let scope = hs.enter();
+  let object_templ = v8::ObjectTemplate::new(scope);
+  object_templ.set_internal_field_count(1);
   let context = v8::Context::new(scope);
   let mut cs = v8::ContextScope::new(scope, context);
   let scope = cs.enter();
+  let object = object_templ.new_instance(scope, context).unwrap();
+
+  let data = Box::new(0x1234_5678u32);
+  let data_ptr = Box::into_raw(data) as *mut std::ffi::c_void;
+  object.set_aligned_pointer_in_internal_field(0, data_ptr);
+  let ptr = unsafe { object.get_aligned_pointer_from_internal_field(0) };
+  assert_eq!(ptr as usize, data_ptr as usize);
+  let data = unsafe { Box::from_raw(ptr as *mut u32) };
+  assert_eq!(*data, 0x1234_5678);
+}
 
-  {
-    static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
-
-    let getter = |scope: v8::PropertyCallbackScope,
-                  key: v8::Local<v8::Name>,
-                  args: v8::PropertyCallbackArguments,
-                  mut rv: v8::ReturnValue| {
-      let context = scope.get_current_context().unwrap();
-      let this = args.this();
-
-      let expected_key = v8::String::new(scope, "getter_key").unwrap();
-      assert!(key.strict_equals(expected_key.into()));
-
-      let int_key = v8::String::new(scope, "int_key").unwrap();
-      let int_value = this.get(scope, context, int_key.into()).unwrap();
-      let int_value = v8::Local::<v8::Integer>::try_from(int_value).unwrap();
-      assert_eq!(int_value.value(), 42);
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "internal field index out of bounds")]
+fn object_internal_field_index_out_of_bounds() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let object_templ = v8::ObjectTemplate::new(scope);
+  object_templ.set_internal_field_count(1);
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+  let object = object_templ.new_instance(scope, context).unwrap();
+  object.set_aligned_pointer_in_internal_field(1, std::ptr::null_mut());
+}
 
-      let s = v8::String::new(scope, "hello").unwrap();
-      assert!(rv.get(scope).is_undefined());
-      rv.set(s.into());
+#[test]
+fn object_template_property_handlers() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
 
-      CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+    let named_getter = |scope: v8::PropertyCallbackScope,
+                        key: v8::Local<v8::Name>,
+                        _: v8::PropertyCallbackArguments,
+                        mut rv: v8::ReturnValue| {
+      let key = v8::Local::<v8::String>::try_from(key).unwrap();
+      let key = key.to_rust_string_lossy(scope);
+      let value = v8::String::new(scope, &format!("synthesized {}", key));
+      rv.set(value.unwrap().into());
+    };
+    let indexed_getter = |scope: v8::PropertyCallbackScope,
+                          index: u32,
+                          _: v8::PropertyCallbackArguments,
+                          mut rv: v8::ReturnValue| {
+      rv.set(v8::Integer::new_from_unsigned(scope, index * 2).into());
     };
 
-    let mut obj = v8::Object::new(scope);
-
-    let getter_key = v8::String::new(scope, "getter_key").unwrap();
-    obj.set_accessor(context, getter_key.into(), getter);
+    let object_templ = v8::ObjectTemplate::new(scope);
+    object_templ.set_named_property_handler(
+      v8::NamedPropertyHandlerConfiguration::new().getter(named_getter),
+    );
+    object_templ.set_indexed_property_handler(
+      v8::IndexedPropertyHandlerConfiguration::new().getter(indexed_getter),
+    );
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let object = object_templ.new_instance(scope, context).unwrap();
+    let name = v8_str(scope, "instance");
+    context
+      .global(scope)
+      .set(context, name.into(), object.into());
 
-    let int_key = v8::String::new(scope, "int_key").unwrap();
-    obj.set(context, int_key.into(), v8::Integer::new(scope, 42).into());
+    let actual = eval(scope, context, "instance.anything").unwrap();
+    let expected = v8_str(scope, "synthesized anything");
+    assert!(expected.strict_equals(actual));
+    let actual = eval(scope, context, "instance[21]").unwrap();
+    let expected = v8::Integer::new(scope, 42);
+    assert!(expected.strict_equals(actual));
+  }
+}
+
+#[test]
+fn object_template_intrinsic_data_property() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let object_templ = v8::ObjectTemplate::new(scope);
+    let name = v8_str(scope, "values");
+    object_templ.set_intrinsic_data_property(
+      name.into(),
+      v8::Intrinsic::ArrayProtoValues,
+      v8::NONE,
+    );
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let object = object_templ.new_instance(scope, context).unwrap();
+    let name = v8_str(scope, "instance");
+    context
+      .global(scope)
+      .set(context, name.into(), object.into());
+    let actual =
+      eval(scope, context, "instance.values === Array.prototype.values")
+        .unwrap();
+    assert!(actual.is_true());
+  }
+}
+
+#[test]
+fn object_template_immutable_proto() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let object_templ = v8::ObjectTemplate::new(scope);
+    object_templ.set_immutable_proto();
+    let object = object_templ.new_instance(scope, context).unwrap();
+    let name = v8_str(scope, "obj");
+    context
+      .global(scope)
+      .set(context, name.into(), object.into());
+
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    let result = eval(scope, context, "Object.setPrototypeOf(obj, {})");
+    assert!(result.is_none());
+    assert!(tc.has_caught());
+    let exception = tc.exception(scope).unwrap();
+    assert!(exception.is_native_error());
+    let message = tc.message(scope).unwrap().get(scope);
+    assert!(message
+      .to_rust_string_lossy(scope)
+      .starts_with("Uncaught TypeError"));
+  }
+}
+
+#[test]
+fn object_set_prototype_immutable_proto() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let object_templ = v8::ObjectTemplate::new(scope);
+    object_templ.set_immutable_proto();
+    let object = object_templ.new_instance(scope, context).unwrap();
+    let original_proto = object.get_prototype(scope).unwrap();
+    let proto = v8::Object::new(scope);
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    assert_eq!(object.set_prototype(context, proto.into()), None);
+    assert!(!tc.has_caught());
+    let actual_proto = object.get_prototype(scope).unwrap();
+    assert!(actual_proto.strict_equals(original_proto));
+
+    let object = v8::Object::new(scope);
+    assert_eq!(object.set_prototype(context, proto.into()), Some(true));
+    let actual_proto = object.get_prototype(scope).unwrap();
+    assert!(actual_proto.strict_equals(proto.into()));
+  }
+}
+
+#[test]
+fn object() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let null: v8::Local<v8::Value> = v8::null(scope).into();
+    let n1: v8::Local<v8::Name> = v8::String::new(scope, "a").unwrap().into();
+    let n2: v8::Local<v8::Name> = v8::String::new(scope, "b").unwrap().into();
+    let v1: v8::Local<v8::Value> = v8::Number::new(scope, 1.0).into();
+    let v2: v8::Local<v8::Value> = v8::Number::new(scope, 2.0).into();
+    let object = v8::Object::with_prototype_and_properties(
+      scope,
+      null,
+      &[n1, n2],
+      &[v1, v2],
+    );
+    assert!(!object.is_null_or_undefined());
+    let lhs = object.creation_context(scope).global(scope);
+    let rhs = context.global(scope);
+    assert!(lhs.strict_equals(rhs.into()));
+
+    let object_ = v8::Object::new(scope);
+    assert!(!object_.is_null_or_undefined());
+    let id = object_.get_identity_hash();
+    assert_ne!(id, 0);
+  }
+}
+
+#[test]
+fn object_has_own_property() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let inherited: v8::Local<v8::Name> = v8_str(scope, "inherited").into();
+    let own: v8::Local<v8::Name> = v8_str(scope, "own").into();
+    let missing: v8::Local<v8::Name> = v8_str(scope, "missing").into();
+    let value: v8::Local<v8::Value> = v8::Integer::new(scope, 42).into();
+
+    let proto = v8::Object::new(scope);
+    proto.create_data_property(context, inherited, value);
+    let child = v8::Object::new(scope);
+    child.set_prototype(context, proto.into());
+    child.create_data_property(context, own, value);
+
+    assert_eq!(child.has_own_property(context, own), Some(true));
+    assert_eq!(child.has_own_property(context, inherited), Some(false));
+    assert_eq!(child.has_own_property(context, missing), Some(false));
+    assert_eq!(proto.has_own_property(context, inherited), Some(true));
+    let actual = child.get(scope, context, inherited.into()).unwrap();
+    assert!(actual.strict_equals(value));
+  }
+}
+
+#[test]
+fn object_from_entries() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let n1: v8::Local<v8::Name> = v8::String::new(scope, "a").unwrap().into();
+    let n2: v8::Local<v8::Name> = v8::String::new(scope, "b").unwrap().into();
+    let n3: v8::Local<v8::Name> = v8::String::new(scope, "c").unwrap().into();
+    let v1: v8::Local<v8::Value> = v8::Number::new(scope, 1.0).into();
+    let v2: v8::Local<v8::Value> = v8::Number::new(scope, 2.0).into();
+    let v3: v8::Local<v8::Value> = v8::Number::new(scope, 3.0).into();
+    let object1 =
+      v8::Object::from_entries(scope, &[(n1, v1), (n2, v2), (n3, v3)]);
+    let object2 =
+      v8::Object::from_entries(scope, &[(n3, v3), (n1, v1), (n2, v2)]);
+    for object in &[object1, object2] {
+      for (name, value) in &[(n1, v1), (n2, v2), (n3, v3)] {
+        let actual = object.get(scope, context, (*name).into()).unwrap();
+        assert!(actual.strict_equals(*value));
+      }
+      let prototype = object.get_prototype(scope).unwrap();
+      let expected = v8::Object::new(scope).get_prototype(scope).unwrap();
+      assert!(prototype.strict_equals(expected));
+    }
+  }
+}
+
+#[test]
+fn array() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let s1 = v8::String::new(scope, "a").unwrap();
+    let s2 = v8::String::new(scope, "b").unwrap();
+    let array = v8::Array::new(scope, 2);
+    assert_eq!(array.length(), 2);
+    let lhs = array.creation_context(scope).global(scope);
+    let rhs = context.global(scope);
+    assert!(lhs.strict_equals(rhs.into()));
+    array.set_index(context, 0, s1.into());
+    array.set_index(context, 1, s2.into());
+
+    let maybe_v1 = array.get_index(scope, context, 0);
+    assert!(maybe_v1.is_some());
+    assert!(maybe_v1.unwrap().same_value(s1.into()));
+    let maybe_v2 = array.get_index(scope, context, 1);
+    assert!(maybe_v2.is_some());
+    assert!(maybe_v2.unwrap().same_value(s2.into()));
+
+    let array = v8::Array::new_with_elements(scope, &[]);
+    assert_eq!(array.length(), 0);
+
+    let array = v8::Array::new_with_elements(scope, &[s1.into(), s2.into()]);
+    assert_eq!(array.length(), 2);
+
+    let maybe_v1 = array.get_index(scope, context, 0);
+    assert!(maybe_v1.is_some());
+    assert!(maybe_v1.unwrap().same_value(s1.into()));
+    let maybe_v2 = array.get_index(scope, context, 1);
+    assert!(maybe_v2.is_some());
+    assert!(maybe_v2.unwrap().same_value(s2.into()));
+  }
+}
+
+#[test]
+fn array_new_from_primitives() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let array = v8::Array::new_from_f64(scope, &[1.0, 2.0, 3.5]);
+    assert_eq!(array.length(), 3);
+    let name = v8_str(scope, "floats");
+    context
+      .global(scope)
+      .set(context, name.into(), array.into());
+    let result = eval(scope, context, "floats.join()").unwrap();
+    assert!(result.strict_equals(v8_str(scope, "1,2,3.5").into()));
+
+    let array = v8::Array::new_from_i32(scope, &[-1, 0, 42]);
+    assert_eq!(array.length(), 3);
+    let name = v8_str(scope, "ints");
+    context
+      .global(scope)
+      .set(context, name.into(), array.into());
+    let result = eval(scope, context, "ints.join()").unwrap();
+    assert!(result.strict_equals(v8_str(scope, "-1,0,42").into()));
+
+    let array = v8::Array::new_from_f64(scope, &[]);
+    assert_eq!(array.length(), 0);
+  }
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn array_to_vec() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let array = eval(scope, context, "[1, 'two', {}, null, 5.5]").unwrap();
+    let array = v8::Local::<v8::Array>::try_from(array).unwrap();
+    let elements = array.to_vec(scope).unwrap();
+    assert_eq!(elements.len(), 5);
+    for (index, element) in elements.iter().enumerate() {
+      let expected = array.get_index(scope, context, index as u32).unwrap();
+      assert!(element.strict_equals(expected));
+    }
+    assert_eq!(elements[0].integer_value(scope).unwrap(), 1);
+    assert_eq!(elements[1].to_rust_string_lossy(scope), "two");
+    assert!(elements[2].is_object());
+    assert!(elements[3].is_null());
+    assert_eq!(elements[4].number_value(scope).unwrap(), 5.5);
+
+    let empty = v8::Array::new(scope, 0);
+    assert!(empty.to_vec(scope).unwrap().is_empty());
+  }
+}
+
+#[test]
+fn array_truncate() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let array = v8::Array::new_from_i32(scope, &[1, 2, 3, 4, 5]);
+    assert_eq!(array.length(), 5);
+    assert_eq!(array.truncate(scope, 2), Some(true));
+    assert_eq!(array.length(), 2);
+    for index in 2..5 {
+      let element = array.get_index(scope, context, index).unwrap();
+      assert!(element.is_undefined());
+    }
+
+    // Truncating to a greater length leaves the array unchanged.
+    assert_eq!(array.truncate(scope, 4), Some(true));
+    assert_eq!(array.length(), 2);
+    let element = array.get_index(scope, context, 1).unwrap();
+    assert_eq!(element.integer_value(scope).unwrap(), 2);
+  }
+}
+
+#[test]
+fn value_number_value_or_throw() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let value: v8::Local<v8::Value> = v8_str(scope, "3.5").into();
+    assert_eq!(value.number_value_or_throw(scope), Ok(3.5));
+
+    let value: v8::Local<v8::Value> = v8_str(scope, "abc").into();
+    assert!(value.number_value_or_throw(scope).unwrap().is_nan());
+
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    let value = eval(
+      scope,
+      context,
+      "({ valueOf() { throw new Error('boom'); } })",
+    )
+    .unwrap();
+    assert!(!tc.has_caught());
+    assert_eq!(value.number_value_or_throw(scope), Err(()));
+    assert!(tc.has_caught());
+  }
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn value_to_primitive() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let value = eval(
+      scope,
+      context,
+      "({ [Symbol.toPrimitive]: (hint) => (hint === 'number' ? 42 : hint) })",
+    )
+    .unwrap();
+    let number = value
+      .to_primitive(scope, v8::ToPrimitiveHint::Number)
+      .unwrap();
+    assert!(number.is_number());
+    assert_eq!(number.number_value(scope).unwrap(), 42.0);
+    let string = value
+      .to_primitive(scope, v8::ToPrimitiveHint::String)
+      .unwrap();
+    assert_eq!(string.to_rust_string_lossy(scope), "string");
+
+    let value = eval(
+      scope,
+      context,
+      "({ valueOf: () => 1, toString: () => 'two' })",
+    )
+    .unwrap();
+    let number = value
+      .to_primitive(scope, v8::ToPrimitiveHint::Default)
+      .unwrap();
+    assert_eq!(number.number_value(scope).unwrap(), 1.0);
+    let string = value
+      .to_primitive(scope, v8::ToPrimitiveHint::String)
+      .unwrap();
+    assert_eq!(string.to_rust_string_lossy(scope), "two");
+
+    let value: v8::Local<v8::Value> = v8_str(scope, "abc").into();
+    let same = value
+      .to_primitive(scope, v8::ToPrimitiveHint::Number)
+      .unwrap();
+    assert!(same.strict_equals(value));
+
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    let value = eval(
+      scope,
+      context,
+      "({ [Symbol.toPrimitive]() { return {}; } })",
+    )
+    .unwrap();
+    assert!(value
+      .to_primitive(scope, v8::ToPrimitiveHint::Default)
+      .is_none());
+    assert!(tc.has_caught());
+  }
+}
+
+#[test]
+fn create_data_property() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    eval(scope, context, "var a = {};");
+
+    let key = v8_str(scope, "a");
+    let obj = context
+      .global(scope)
+      .get(scope, context, key.into())
+      .unwrap();
+    assert!(obj.is_object());
+    let obj = obj.to_object(scope).unwrap();
+    let key = v8_str(scope, "foo");
+    let value = v8_str(scope, "bar");
+    assert!(obj
+      .create_data_property(context, key.into(), value.into())
+      .unwrap());
+    let actual = obj.get(scope, context, key.into()).unwrap();
+    assert!(value.strict_equals(actual));
+
+    let key2 = v8_str(scope, "foo2");
+    assert!(obj.set(context, key2.into(), value.into()).unwrap());
+    let actual = obj.get(scope, context, key2.into()).unwrap();
+    assert!(value.strict_equals(actual));
+  }
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn object_get_typed() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  let obj = v8::Object::new(scope);
+  let key = v8_str(scope, "answer");
+  let value = v8::Number::new(scope, 42.5);
+  obj.set(context, key.into(), value.into());
+
+  let number = obj
+    .get_typed::<v8::Number>(scope, context, key.into())
+    .unwrap();
+  assert_eq!(number.value(), 42.5);
+  assert!(obj
+    .get_typed::<v8::String>(scope, context, key.into())
+    .is_none());
+  let missing = v8_str(scope, "missing");
+  assert!(obj
+    .get_typed::<v8::Number>(scope, context, missing.into())
+    .is_none());
+}
+
+#[test]
+fn object_set_accessor() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  {
+    static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    let getter = |scope: v8::PropertyCallbackScope,
+                  key: v8::Local<v8::Name>,
+                  args: v8::PropertyCallbackArguments,
+                  mut rv: v8::ReturnValue| {
+      let context = scope.get_current_context().unwrap();
+      let this = args.this();
+
+      let expected_key = v8::String::new(scope, "getter_key").unwrap();
+      assert!(key.strict_equals(expected_key.into()));
+
+      let int_key = v8::String::new(scope, "int_key").unwrap();
+      let int_value = this.get(scope, context, int_key.into()).unwrap();
+      let int_value = v8::Local::<v8::Integer>::try_from(int_value).unwrap();
+      assert_eq!(int_value.value(), 42);
+
+      let s = v8::String::new(scope, "hello").unwrap();
+      assert!(rv.get(scope).is_undefined());
+      rv.set(s.into());
+
+      CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+    };
+
+    let mut obj = v8::Object::new(scope);
+
+    let getter_key = v8::String::new(scope, "getter_key").unwrap();
+    obj.set_accessor(context, getter_key.into(), getter);
+
+    let int_key = v8::String::new(scope, "int_key").unwrap();
+    obj.set(context, int_key.into(), v8::Integer::new(scope, 42).into());
+
+    let obj_name = v8::String::new(scope, "obj").unwrap();
+    context
+      .global(scope)
+      .set(context, obj_name.into(), obj.into());
+
+    let actual = eval(scope, context, "obj.getter_key").unwrap();
+    let expected = v8::String::new(scope, "hello").unwrap();
+    assert!(actual.strict_equals(expected.into()));
+
+    assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 1);
+  }
+}
+
+#[test]
+fn object_set_accessor_with_configuration() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  static SET_VALUE: AtomicUsize = AtomicUsize::new(0);
+
+  let getter = |_scope: v8::PropertyCallbackScope,
+                _key: v8::Local<v8::Name>,
+                args: v8::PropertyCallbackArguments,
+                mut rv: v8::ReturnValue| {
+    rv.set(args.data());
+  };
+  let setter = |scope: v8::PropertyCallbackScope,
+                key: v8::Local<v8::Name>,
+                value: v8::Local<v8::Value>,
+                args: v8::PropertyCallbackArguments,
+                _rv: v8::ReturnValue| {
+    let expected_key = v8::String::new(scope, "secret").unwrap();
+    assert!(key.strict_equals(expected_key.into()));
+    let expected_data = v8::String::new(scope, "callback data").unwrap();
+    assert!(args.data().strict_equals(expected_data.into()));
+    let value = value.uint32_value(scope).unwrap();
+    SET_VALUE.store(value as usize, Ordering::SeqCst);
+  };
+
+  let obj = v8::Object::new(scope);
+  let key = v8::String::new(scope, "secret").unwrap();
+  let data = v8::String::new(scope, "callback data").unwrap();
+  let setter: v8::AccessorNameSetterCallback = setter.map_fn_to();
+  assert_eq!(
+    obj.set_accessor_with_configuration(
+      context,
+      key.into(),
+      getter,
+      Some(setter),
+      Some(data.into()),
+      v8::AccessControl::Default,
+      v8::DONT_ENUM,
+    ),
+    Some(true)
+  );
+  let obj_name = v8::String::new(scope, "obj").unwrap();
+  context
+    .global(scope)
+    .set(context, obj_name.into(), obj.into());
+
+  let actual = eval(scope, context, "Object.keys(obj).length").unwrap();
+  assert_eq!(actual.uint32_value(scope), Some(0));
+  let actual = eval(scope, context, "obj.secret").unwrap();
+  assert!(actual.strict_equals(data.into()));
+  eval(scope, context, "obj.secret = 42").unwrap();
+  assert_eq!(SET_VALUE.load(Ordering::SeqCst), 42);
+}
+
+#[test]
+fn object_define_native_accessor() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  static VALUE: AtomicUsize = AtomicUsize::new(7);
+
+  let getter = |scope: v8::PropertyCallbackScope,
+                _key: v8::Local<v8::Name>,
+                _args: v8::PropertyCallbackArguments,
+                mut rv: v8::ReturnValue| {
+    let value = VALUE.load(Ordering::SeqCst) as u32;
+    rv.set(v8::Integer::new_from_unsigned(scope, value).into());
+  };
+  let setter = |scope: v8::PropertyCallbackScope,
+                _key: v8::Local<v8::Name>,
+                value: v8::Local<v8::Value>,
+                _args: v8::PropertyCallbackArguments,
+                _rv: v8::ReturnValue| {
+    let value = value.uint32_value(scope).unwrap();
+    VALUE.store(value as usize, Ordering::SeqCst);
+  };
+
+  let obj = v8::Object::new(scope);
+  let key = v8::String::new(scope, "key").unwrap();
+  assert_eq!(
+    obj.define_native_accessor(
+      context,
+      key.into(),
+      getter,
+      setter,
+      v8::DONT_DELETE,
+    ),
+    Some(true)
+  );
+  let obj_name = v8::String::new(scope, "obj").unwrap();
+  context
+    .global(scope)
+    .set(context, obj_name.into(), obj.into());
+
+  let actual = eval(scope, context, "obj.key").unwrap();
+  assert_eq!(actual.uint32_value(scope), Some(7));
+  let actual = eval(scope, context, "delete obj.key").unwrap();
+  assert!(actual.is_false());
+  let actual = eval(scope, context, "obj.key = 9; obj.key").unwrap();
+  assert_eq!(actual.uint32_value(scope), Some(9));
+  assert_eq!(VALUE.load(Ordering::SeqCst), 9);
+}
+
+#[test]
+fn object_set_native_and_lazy_data_property() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  static NATIVE_CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+  static LAZY_CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+  let native_getter = |scope: v8::PropertyCallbackScope,
+                       _key: v8::Local<v8::Name>,
+                       _args: v8::PropertyCallbackArguments,
+                       mut rv: v8::ReturnValue| {
+    let count = NATIVE_CALL_COUNT.fetch_add(1, Ordering::SeqCst) + 1;
+    rv.set(v8::Integer::new(scope, count as i32).into());
+  };
+  let lazy_getter = |scope: v8::PropertyCallbackScope,
+                     _key: v8::Local<v8::Name>,
+                     _args: v8::PropertyCallbackArguments,
+                     mut rv: v8::ReturnValue| {
+    let count = LAZY_CALL_COUNT.fetch_add(1, Ordering::SeqCst) + 1;
+    rv.set(v8::Integer::new(scope, count as i32).into());
+  };
+
+  let obj = v8::Object::new(scope);
+  let native_key = v8::String::new(scope, "native").unwrap();
+  assert_eq!(
+    obj.set_native_data_property(context, native_key.into(), native_getter),
+    Some(true)
+  );
+  let lazy_key = v8::String::new(scope, "lazy").unwrap();
+  assert_eq!(
+    obj.set_lazy_data_property(context, lazy_key.into(), lazy_getter),
+    Some(true)
+  );
+  let obj_name = v8::String::new(scope, "obj").unwrap();
+  context
+    .global(scope)
+    .set(context, obj_name.into(), obj.into());
+
+  let actual = eval(scope, context, "[obj.native, obj.native].join()").unwrap();
+  let expected = v8::String::new(scope, "1,2").unwrap();
+  assert!(actual.strict_equals(expected.into()));
+  assert_eq!(NATIVE_CALL_COUNT.load(Ordering::SeqCst), 2);
+
+  let actual = eval(scope, context, "[obj.lazy, obj.lazy].join()").unwrap();
+  let expected = v8::String::new(scope, "1,1").unwrap();
+  assert!(actual.strict_equals(expected.into()));
+  assert_eq!(LAZY_CALL_COUNT.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn object_set_accessor_property() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  let accessors = eval(
+    scope,
+    context,
+    "globalThis.backing = 1; [() => backing, (v) => { backing = v * 2; }]",
+  )
+  .unwrap();
+  let accessors = v8::Local::<v8::Object>::try_from(accessors).unwrap();
+  let getter = accessors.get_index(scope, context, 0).unwrap();
+  let getter = v8::Local::<v8::Function>::try_from(getter).unwrap();
+  let setter = accessors.get_index(scope, context, 1).unwrap();
+  let setter = v8::Local::<v8::Function>::try_from(setter).unwrap();
+
+  let obj = v8::Object::new(scope);
+  let key = v8::String::new(scope, "prop").unwrap();
+  obj.set_accessor_property(key.into(), getter, Some(setter), v8::NONE);
+  let read_only_key = v8::String::new(scope, "readOnly").unwrap();
+  obj.set_accessor_property(read_only_key.into(), getter, None, v8::NONE);
+  let obj_name = v8::String::new(scope, "obj").unwrap();
+  context
+    .global(scope)
+    .set(context, obj_name.into(), obj.into());
+
+  let actual = eval(scope, context, "obj.prop").unwrap();
+  assert!(actual.strict_equals(v8::Integer::new(scope, 1).into()));
+  let actual = eval(scope, context, "obj.prop = 5; backing").unwrap();
+  assert!(actual.strict_equals(v8::Integer::new(scope, 10).into()));
+  let actual = eval(scope, context, "obj.prop").unwrap();
+  assert!(actual.strict_equals(v8::Integer::new(scope, 10).into()));
+  let actual = eval(scope, context, "obj.readOnly = 7; obj.readOnly").unwrap();
+  assert!(actual.strict_equals(v8::Integer::new(scope, 10).into()));
+  let actual = eval(
+    scope,
+    context,
+    "typeof Object.getOwnPropertyDescriptor(obj, 'prop').get",
+  )
+  .unwrap();
+  let actual = actual.to_string(scope).unwrap();
+  assert_eq!(actual.to_rust_string_lossy(scope), "function");
+}
+
+#[test]
+fn context_new_with_options() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+
+  let global_template = v8::ObjectTemplate::new(scope);
+  let name = v8_str(scope, "fromTemplate");
+  global_template.set(name.into(), v8::Integer::new(scope, 42).into());
+  let context1 = unsafe {
+    v8::Context::new_with_options(
+      scope,
+      v8::ContextOptions {
+        global_template: Some(global_template),
+        ..Default::default()
+      },
+    )
+  };
+  let global1 = context1.global(scope);
+  {
+    let mut cs = v8::ContextScope::new(scope, context1);
+    let scope = cs.enter();
+    let result = eval(scope, context1, "fromTemplate").unwrap();
+    assert!(result.strict_equals(v8::Integer::new(scope, 42).into()));
+  }
+
+  context1.detach_global();
+  let context2 = unsafe {
+    v8::Context::new_with_options(
+      scope,
+      v8::ContextOptions {
+        global_object: Some(global1.into()),
+        ..Default::default()
+      },
+    )
+  };
+  let global2 = context2.global(scope);
+  assert!(global1.strict_equals(global2.into()));
+  {
+    let mut cs = v8::ContextScope::new(scope, context2);
+    let scope = cs.enter();
+    let result = eval(scope, context2, "globalThis").unwrap();
+    assert!(result.strict_equals(global1.into()));
+    let result = eval(scope, context2, "typeof fromTemplate").unwrap();
+    assert!(result.strict_equals(v8_str(scope, "undefined").into()));
+  }
+}
+
+#[test]
+fn context_code_generation_from_strings() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  assert!(context.is_code_generation_from_strings_allowed());
+  context.allow_code_generation_from_strings(false);
+  assert!(!context.is_code_generation_from_strings_allowed());
+  let message = v8_str(scope, "eval is locked down");
+  context.set_error_message_for_code_gen_from_strings(message);
+
+  let mut try_catch = v8::TryCatch::new(scope);
+  let tc = try_catch.enter();
+  let result = eval(scope, context, "eval('1')");
+  assert!(result.is_none());
+  assert!(tc.has_caught());
+  let exception = tc.exception(scope).unwrap();
+  let exception_str = exception
+    .to_string(scope)
+    .unwrap()
+    .to_rust_string_lossy(scope);
+  assert!(exception_str.starts_with("EvalError: "));
+  assert!(exception_str.contains("eval is locked down"));
+}
+
+fn names_to_strings<'sc>(
+  scope: &mut impl v8::ToLocal<'sc>,
+  names: &[v8::Local<v8::Name>],
+) -> Vec<String> {
+  names
+    .iter()
+    .map(|name| name.to_rust_string_lossy(scope))
+    .collect()
+}
+
+#[test]
+fn object_collect_own_property_names() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  let obj = eval(scope, context, "({ a: 1, b: 2, 3: 'c' })").unwrap();
+  let obj = v8::Local::<v8::Object>::try_from(obj).unwrap();
+  let mut names = Vec::new();
+  obj
+    .collect_own_property_names(scope, context, &mut names)
+    .unwrap();
+  assert_eq!(names_to_strings(scope, &names), vec!["3", "a", "b"]);
+  assert!(names.iter().all(|name| name.is_string()));
+  let capacity = names.capacity();
+  let ptr = names.as_ptr();
+
+  let obj = eval(scope, context, "({ x: 1, y: 2 })").unwrap();
+  let obj = v8::Local::<v8::Object>::try_from(obj).unwrap();
+  obj
+    .collect_own_property_names(scope, context, &mut names)
+    .unwrap();
+  assert_eq!(names_to_strings(scope, &names), vec!["x", "y"]);
+  assert_eq!(names.capacity(), capacity);
+  assert_eq!(names.as_ptr(), ptr);
+}
+
+#[test]
+fn object_get_own_property_names_with() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  let obj = eval(
+    scope,
+    context,
+    r#"
+      const obj = { a: 1 };
+      obj[Symbol.for("sym")] = 2;
+      Object.defineProperty(obj, "hidden", { value: 3, enumerable: false });
+      obj
+    "#,
+  )
+  .unwrap();
+  let obj = v8::Local::<v8::Object>::try_from(obj).unwrap();
 
-    let obj_name = v8::String::new(scope, "obj").unwrap();
-    context
-      .global(scope)
-      .set(context, obj_name.into(), obj.into());
+  let names = obj
+    .get_own_property_names_with(scope, context, Default::default())
+    .unwrap();
+  assert_eq!(names.length(), 1);
 
-    let actual = eval(scope, context, "obj.getter_key").unwrap();
-    let expected = v8::String::new(scope, "hello").unwrap();
-    assert!(actual.strict_equals(expected.into()));
+  let args = v8::GetPropertyNamesArgs {
+    property_filter: v8::PropertyFilter::ALL_PROPERTIES,
+    ..Default::default()
+  };
+  let names = obj
+    .get_own_property_names_with(scope, context, args)
+    .unwrap();
+  assert_eq!(names.length(), 3);
+  let name = names.get_index(scope, context, 1).unwrap();
+  let hidden = v8::String::new(scope, "hidden").unwrap();
+  assert!(name.strict_equals(hidden.into()));
+  let name = names.get_index(scope, context, 2).unwrap();
+  assert!(name.is_symbol());
+
+  let args = v8::GetPropertyNamesArgs {
+    property_filter: v8::PropertyFilter::SKIP_STRINGS,
+    ..Default::default()
+  };
+  let names = obj
+    .get_own_property_names_with(scope, context, args)
+    .unwrap();
+  assert_eq!(names.length(), 1);
+  let name = names.get_index(scope, context, 0).unwrap();
+  assert!(name.is_symbol());
+}
 
-    assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 1);
-  }
+#[test]
+fn object_get_property_names_with() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  let obj = eval(
+    scope,
+    context,
+    r#"
+      const proto = { inherited: 1 };
+      proto[Symbol.for("inherited_sym")] = 2;
+      const obj = Object.create(proto);
+      obj.own = 3;
+      obj
+    "#,
+  )
+  .unwrap();
+  let obj = v8::Local::<v8::Object>::try_from(obj).unwrap();
+  let inherited_sym =
+    eval(scope, context, "Symbol.for('inherited_sym')").unwrap();
+
+  let args = v8::GetPropertyNamesArgs {
+    mode: v8::KeyCollectionMode::IncludePrototypes,
+    property_filter: v8::PropertyFilter::ONLY_ENUMERABLE,
+    ..Default::default()
+  };
+  let names = obj.get_property_names_with(scope, context, args).unwrap();
+  assert_eq!(names.length(), 3);
+  let name = names.get_index(scope, context, 0).unwrap();
+  assert!(name.strict_equals(v8_str(scope, "own").into()));
+  let name = names.get_index(scope, context, 1).unwrap();
+  assert!(name.strict_equals(v8_str(scope, "inherited").into()));
+  let name = names.get_index(scope, context, 2).unwrap();
+  assert!(name.strict_equals(inherited_sym));
+
+  // Symbols are skipped unless requested.
+  let args = v8::GetPropertyNamesArgs {
+    mode: v8::KeyCollectionMode::IncludePrototypes,
+    ..Default::default()
+  };
+  let names = obj.get_property_names_with(scope, context, args).unwrap();
+  assert_eq!(names.length(), 2);
+
+  // The own-only variant never walks the prototype chain.
+  let args = v8::GetPropertyNamesArgs {
+    mode: v8::KeyCollectionMode::IncludePrototypes,
+    property_filter: v8::PropertyFilter::ONLY_ENUMERABLE,
+    ..Default::default()
+  };
+  let names = obj
+    .get_own_property_names_with(scope, context, args)
+    .unwrap();
+  assert_eq!(names.length(), 1);
 }
 
 #[test]
@@ -1410,6 +3558,65 @@ fn promise_rejected() {
     assert_eq!(result_str.to_rust_string_lossy(scope), "test".to_string());
   }
 }
+
+#[test]
+fn promise_then_rust() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let resolver = v8::PromiseResolver::new(scope, context).unwrap();
+    let promise = resolver.get_promise(scope);
+    let observed = Rc::new(RefCell::new(None));
+    let observed_ = observed.clone();
+    let derived = promise.then_rust(scope, context, move |scope, value| {
+      let value = value.to_string(scope).unwrap();
+      *observed_.borrow_mut() = Some(value.to_rust_string_lossy(scope));
+    });
+    assert!(derived.is_some());
+    assert!(promise.has_handler());
+    let value = v8::String::new(scope, "test").unwrap();
+    resolver.resolve(context, value.into());
+    assert_eq!(*observed.borrow(), None);
+    scope.isolate().run_microtasks();
+    assert_eq!(observed.borrow().as_deref(), Some("test"));
+  }
+}
+
+#[test]
+fn promise_then_rust_drops_closure() {
+  struct DropFlag(Rc<RefCell<bool>>);
+  impl Drop for DropFlag {
+    fn drop(&mut self) {
+      *self.0.borrow_mut() = true;
+    }
+  }
+
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let dropped = Rc::new(RefCell::new(false));
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let resolver = v8::PromiseResolver::new(scope, context).unwrap();
+    let promise = resolver.get_promise(scope);
+    let flag = DropFlag(dropped.clone());
+    promise
+      .then_rust(scope, context, move |_, _| drop(flag))
+      .unwrap();
+  }
+  assert!(!*dropped.borrow());
+  isolate.low_memory_notification();
+  assert!(*dropped.borrow());
+}
+
 #[test]
 fn proxy() {
   let _setup_guard = setup();
@@ -1433,6 +3640,119 @@ fn proxy() {
   }
 }
 
+#[test]
+fn object_get_isolate() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let isolate_ptr = &mut *isolate as *mut v8::Isolate;
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let object = v8::Object::new(scope);
+    assert_eq!(object.get_isolate().as_ptr(), isolate_ptr);
+    let global = context.global(scope);
+    assert_eq!(global.get_isolate().as_ptr(), isolate_ptr);
+  }
+}
+
+#[test]
+fn object_call_as_function() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let proxy = eval(
+      scope,
+      context,
+      r#"
+        new Proxy(function() {}, {
+          apply(target, thisArg, args) {
+            globalThis.applyTrapped = true;
+            return args[0] + args[1];
+          },
+          construct(target, args) {
+            globalThis.constructTrapped = true;
+            return { value: args[0] };
+          },
+        });
+      "#,
+    )
+    .unwrap();
+    assert!(proxy.is_proxy());
+    let proxy = v8::Local::<v8::Object>::try_from(proxy).unwrap();
+
+    let recv: v8::Local<v8::Value> = v8::undefined(scope).into();
+    let one: v8::Local<v8::Value> = v8::Integer::new(scope, 1).into();
+    let two: v8::Local<v8::Value> = v8::Integer::new(scope, 2).into();
+    let result = proxy
+      .call_as_function(scope, context, recv, &[one, two])
+      .unwrap();
+    assert_eq!(result.integer_value(scope).unwrap(), 3);
+    let trapped = eval(scope, context, "applyTrapped").unwrap();
+    assert!(trapped.is_true());
+
+    let result = proxy.call_as_constructor(scope, context, &[one]).unwrap();
+    let result = v8::Local::<v8::Object>::try_from(result).unwrap();
+    let key = v8_str(scope, "value");
+    let value = result.get(scope, context, key.into()).unwrap();
+    assert!(value.strict_equals(one));
+    let trapped = eval(scope, context, "constructTrapped").unwrap();
+    assert!(trapped.is_true());
+
+    // Plain objects are not callable.
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    let object = v8::Object::new(scope);
+    assert!(object.call_as_function(scope, context, recv, &[]).is_none());
+    assert!(tc.has_caught());
+  }
+}
+
+#[test]
+fn wasm_module_object() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    // The smallest valid Wasm module: the magic number and the version.
+    let wire_bytes = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+    let value = eval(
+      scope,
+      context,
+      "new WebAssembly.Module(new Uint8Array([0, 97, 115, 109, 1, 0, 0, 0]))",
+    )
+    .unwrap();
+    assert!(value.is_wasm_module_object());
+    let module = v8::Local::<v8::WasmModuleObject>::try_from(value).unwrap();
+
+    let compiled_module = module.get_compiled_module();
+    assert_eq!(compiled_module.get_wire_bytes_ref(), &wire_bytes[..]);
+
+    let restored =
+      v8::WasmModuleObject::from_compiled_module(scope, &compiled_module)
+        .unwrap();
+    let value: v8::Local<v8::Value> = restored.into();
+    assert!(value.is_wasm_module_object());
+    let name = v8_str(scope, "m");
+    context.global(scope).set(context, name.into(), value);
+    let actual =
+      eval(scope, context, "m instanceof WebAssembly.Module").unwrap();
+    assert!(actual.is_true());
+  }
+}
+
 fn fn_callback(
   scope: v8::FunctionCallbackScope,
   args: v8::FunctionCallbackArguments,
@@ -1484,6 +3804,194 @@ fn data_is_true_callback(
   assert!(data.is_true());
 }
 
+#[test]
+fn function_callback_new_target() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let isolate_ptr = &mut *isolate as *mut v8::Isolate;
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    static ISOLATE_PTR: AtomicUsize = AtomicUsize::new(0);
+    static PLAIN_CALLS: AtomicUsize = AtomicUsize::new(0);
+    static CONSTRUCT_CALLS: AtomicUsize = AtomicUsize::new(0);
+    ISOLATE_PTR.store(isolate_ptr as usize, Ordering::SeqCst);
+    fn constructor_callback(
+      scope: v8::FunctionCallbackScope,
+      args: v8::FunctionCallbackArguments,
+      _: v8::ReturnValue,
+    ) {
+      let isolate_ptr = args.get_isolate().as_ptr();
+      assert_eq!(isolate_ptr as usize, ISOLATE_PTR.load(Ordering::SeqCst));
+      let new_target = args.new_target();
+      if new_target.is_undefined() {
+        PLAIN_CALLS.fetch_add(1, Ordering::SeqCst);
+      } else {
+        let context = scope.get_current_context().unwrap();
+        let key = v8_str(scope, "Foo");
+        let foo = context.global(scope).get(scope, context, key.into());
+        assert!(new_target.strict_equals(foo.unwrap()));
+        CONSTRUCT_CALLS.fetch_add(1, Ordering::SeqCst);
+      }
+    }
+
+    let function =
+      v8::Function::new(scope, context, constructor_callback).unwrap();
+    let name = v8_str(scope, "Foo");
+    context
+      .global(scope)
+      .set(context, name.into(), function.into());
+    eval(scope, context, "Foo()").unwrap();
+    assert_eq!(PLAIN_CALLS.load(Ordering::SeqCst), 1);
+    assert_eq!(CONSTRUCT_CALLS.load(Ordering::SeqCst), 0);
+    let result = eval(scope, context, "new Foo() instanceof Foo").unwrap();
+    assert!(result.is_true());
+    assert_eq!(PLAIN_CALLS.load(Ordering::SeqCst), 1);
+    assert_eq!(CONSTRUCT_CALLS.load(Ordering::SeqCst), 1);
+  }
+}
+
+#[test]
+fn return_value_primitive_setters() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  fn callback(
+    scope: v8::FunctionCallbackScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+  ) {
+    match args.get(0).int32_value(scope).unwrap() {
+      0 => rv.set_bool(true),
+      1 => rv.set_int32(42),
+      2 => rv.set_uint32(u32::MAX),
+      3 => rv.set_double(1.5),
+      4 => rv.set_null(),
+      5 => rv.set_undefined(),
+      6 => rv.set_empty_string(),
+      _ => unreachable!(),
+    }
+  }
+
+  let function = v8::Function::new(scope, context, callback).unwrap();
+  let name = v8_str(scope, "ret");
+  context
+    .global(scope)
+    .set(context, name.into(), function.into());
+
+  let result = eval(scope, context, "ret(0) === true").unwrap();
+  assert!(result.is_true());
+  let result = eval(scope, context, "ret(1) === 42").unwrap();
+  assert!(result.is_true());
+  let result = eval(scope, context, "ret(2) === 4294967295").unwrap();
+  assert!(result.is_true());
+  let result = eval(scope, context, "ret(3) === 1.5").unwrap();
+  assert!(result.is_true());
+  let result = eval(scope, context, "ret(4) === null").unwrap();
+  assert!(result.is_true());
+  let result = eval(scope, context, "ret(5) === undefined").unwrap();
+  assert!(result.is_true());
+  let result = eval(scope, context, "ret(6) === ''").unwrap();
+  assert!(result.is_true());
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn local_widening_conversions() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let scope = cs.enter();
+
+  let value = eval(scope, context, "(function f() {})").unwrap();
+  let function = v8::Local::<v8::Function>::try_from(value).unwrap();
+  let object: v8::Local<v8::Object> = function.into();
+  assert!(object == function);
+  let value: v8::Local<v8::Value> = object.into();
+  assert!(value.strict_equals(function.into()));
+  let _: v8::Local<v8::Data> = value.into();
+
+  let array = v8::Array::new(scope, 1);
+  let object: v8::Local<v8::Object> = array.into();
+  let value: v8::Local<v8::Value> = object.into();
+  assert!(value.is_array());
+
+  let string = v8::String::new(scope, "str").unwrap();
+  let name: v8::Local<v8::Name> = string.into();
+  assert!(name == string);
+  let primitive: v8::Local<v8::Primitive> = name.into();
+  assert!(primitive == name);
+
+  let value = eval(scope, context, "Symbol('sym')").unwrap();
+  let symbol = v8::Local::<v8::Symbol>::try_from(value).unwrap();
+  let name: v8::Local<v8::Name> = symbol.into();
+  assert!(symbol == name);
+
+  let int32 =
+    v8::Local::<v8::Int32>::try_from(eval(scope, context, "-7").unwrap())
+      .unwrap();
+  let integer: v8::Local<v8::Integer> = int32.into();
+  assert_eq!(integer.value(), -7);
+  let number: v8::Local<v8::Number> = integer.into();
+  assert!(number == integer);
+  assert_eq!(number.value(), -7.0);
+  let primitive: v8::Local<v8::Primitive> = number.into();
+  let value: v8::Local<v8::Value> = primitive.into();
+  assert!(value.strict_equals(int32.into()));
+}
+
+#[test]
+fn function_names() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let source = r#"
+      const obj = {};
+      obj.handler = () => 42;
+      obj.handler;
+    "#;
+    let function = eval(scope, context, source).unwrap();
+    let function = v8::Local::<v8::Function>::try_from(function).unwrap();
+    let name = function.get_inferred_name(scope);
+    assert_eq!(name.to_rust_string_lossy(scope), "obj.handler");
+    let name = function.get_debug_name(scope);
+    assert_eq!(name.to_rust_string_lossy(scope), "obj.handler");
+    assert!(function.get_bound_function(scope).is_undefined());
+
+    let source = r#"
+      const f = () => 42;
+      f;
+    "#;
+    let f = eval(scope, context, source).unwrap();
+    let f = v8::Local::<v8::Function>::try_from(f).unwrap();
+    let name = f.get_debug_name(scope);
+    assert_eq!(name.to_rust_string_lossy(scope), "f");
+
+    let bound = eval(scope, context, "f.bind(null)").unwrap();
+    let bound = v8::Local::<v8::Function>::try_from(bound).unwrap();
+    let target = bound.get_bound_function(scope);
+    assert!(target.strict_equals(f.into()));
+  }
+}
+
 #[test]
 fn function() {
   let _setup_guard = setup();
@@ -1534,6 +4042,131 @@ fn function() {
   }
 }
 
+fn current_stack_trace_callback(
+  scope: v8::FunctionCallbackScope,
+  _: v8::FunctionCallbackArguments,
+  mut rv: v8::ReturnValue,
+) {
+  let stack_trace = v8::StackTrace::current_stack_trace(scope, 5).unwrap();
+  assert_eq!(stack_trace.get_frame_count(), 3);
+  let frame = stack_trace.get_frame(scope, 0).unwrap();
+  let function_name = frame.get_function_name(scope).unwrap();
+  rv.set(function_name.into());
+}
+
+#[test]
+fn stack_frame() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  isolate.set_capture_stack_trace_for_uncaught_exceptions(true, 10);
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let code = v8_str(
+      scope,
+      "function inner() {\n  return new Error('boom');\n}\n\
+       function outer() { return inner(); }\n\
+       outer();",
+    );
+    let resource_name = v8_str(scope, "stack_frame.js");
+    let origin = v8::ScriptOriginBuilder::new()
+      .resource_name(resource_name.into())
+      .build(scope);
+    let script =
+      v8::Script::compile(scope, context, code, Some(&origin)).unwrap();
+    let error = script.run(scope, context).unwrap();
+    let stack_trace = v8::Exception::get_stack_trace(scope, error).unwrap();
+    assert_eq!(stack_trace.get_frame_count(), 3);
+
+    let frame = stack_trace.get_frame(scope, 0).unwrap();
+    let function_name = frame.get_function_name(scope).unwrap();
+    assert_eq!(function_name.to_rust_string_lossy(scope), "inner");
+    let script_name = frame.get_script_name(scope).unwrap();
+    assert_eq!(script_name.to_rust_string_lossy(scope), "stack_frame.js");
+    assert_eq!(frame.get_line_number(), 2);
+    assert_eq!(frame.get_column(), 10);
+    assert_ne!(frame.get_script_id(), 0);
+    assert!(!frame.is_eval());
+    assert!(!frame.is_constructor());
+    assert!(!frame.is_wasm());
+    assert!(frame.is_user_javascript());
+
+    let frame = stack_trace.get_frame(scope, 1).unwrap();
+    let function_name = frame.get_function_name(scope).unwrap();
+    assert_eq!(function_name.to_rust_string_lossy(scope), "outer");
+    assert_eq!(frame.get_line_number(), 4);
+  }
+}
+
+#[test]
+fn message_script_origin() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    let code = v8_str(scope, "throw new Error('boom')");
+    let resource_name = v8_str(scope, "origin.js");
+    let origin = v8::ScriptOriginBuilder::new()
+      .resource_name(resource_name.into())
+      .shared_cross_origin(true)
+      .is_opaque(true)
+      .build(scope);
+    let mut script =
+      v8::Script::compile(scope, context, code, Some(&origin)).unwrap();
+    assert!(script.run(scope, context).is_none());
+    let message = tc.message(scope).unwrap();
+    assert!(message.is_shared_cross_origin());
+    assert!(message.is_opaque());
+
+    let origin = message.get_script_origin(scope);
+    let resource_name = origin.resource_name().unwrap();
+    assert_eq!(resource_name.to_rust_string_lossy(scope), "origin.js");
+    assert!(origin.is_shared_cross_origin());
+    assert!(origin.is_opaque());
+    assert!(!origin.is_wasm());
+    assert!(!origin.is_module());
+  }
+}
+
+#[test]
+fn current_stack_trace() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let global = context.global(scope);
+    let function =
+      v8::Function::new(scope, context, current_stack_trace_callback).unwrap();
+    let name = v8_str(scope, "getFunctionName");
+    global.set(context, name.into(), function.into());
+    let result = eval(
+      scope,
+      context,
+      "function inner() { return getFunctionName(); }\n\
+       function outer() { return inner(); }\n\
+       outer()",
+    )
+    .unwrap();
+    let expected = v8_str(scope, "inner");
+    assert!(result.strict_equals(expected.into()));
+  }
+}
+
 extern "C" fn promise_reject_callback(msg: v8::PromiseRejectMessage) {
   let mut scope = v8::CallbackScope::new(&msg);
   let scope = scope.enter();
@@ -1555,7 +4188,29 @@ extern "C" fn promise_reject_callback(msg: v8::PromiseRejectMessage) {
 fn set_promise_reject_callback() {
   let _setup_guard = setup();
   let mut isolate = v8::Isolate::new(Default::default());
-  isolate.set_promise_reject_callback(promise_reject_callback);
+  isolate.set_promise_reject_callback(promise_reject_callback);
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let resolver = v8::PromiseResolver::new(scope, context).unwrap();
+    let value = v8::String::new(scope, "promise rejected").unwrap();
+    resolver.reject(context, value.into());
+  }
+}
+
+#[test]
+fn promise_mark_as_handled() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+
+  static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+  extern "C" fn callback(_msg: v8::PromiseRejectMessage) {
+    CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+  }
+  isolate.set_promise_reject_callback(callback);
   {
     let mut hs = v8::HandleScope::new(&mut isolate);
     let scope = hs.enter();
@@ -1563,9 +4218,16 @@ fn set_promise_reject_callback() {
     let mut cs = v8::ContextScope::new(scope, context);
     let scope = cs.enter();
     let resolver = v8::PromiseResolver::new(scope, context).unwrap();
+    let promise = resolver.get_promise(scope);
+    assert!(!promise.has_handler());
+    promise.mark_as_handled();
+    assert!(promise.has_handler());
     let value = v8::String::new(scope, "promise rejected").unwrap();
     resolver.reject(context, value.into());
+    assert_eq!(promise.state(), v8::PromiseState::Rejected);
+    scope.isolate().run_microtasks();
   }
+  assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 0);
 }
 
 fn mock_script_origin<'sc>(
@@ -1694,6 +4356,100 @@ fn module_instantiation_failures1() {
   }
 }
 
+#[test]
+fn script_origin_builder() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let source_text = v8_str(scope, "import './dep.js';");
+    let resource_name = v8_str(scope, "main.js");
+    let origin = v8::ScriptOriginBuilder::new()
+      .resource_name(resource_name.into())
+      .is_module(true)
+      .build(scope);
+    let source = v8::script_compiler::Source::new(source_text, &origin);
+    let mut module =
+      v8::script_compiler::compile_module(scope, source).unwrap();
+    assert_eq!(1, module.get_module_requests_length());
+    assert_eq!(
+      "./dep.js",
+      module.get_module_request(0).to_rust_string_lossy(scope)
+    );
+
+    fn resolve_callback<'a>(
+      context: v8::Local<'a, v8::Context>,
+      specifier: v8::Local<'a, v8::String>,
+      _referrer: v8::Local<'a, v8::Module>,
+    ) -> Option<v8::Local<'a, v8::Module>> {
+      let mut cbs = v8::CallbackScope::new_escapable(context);
+      let mut hs = v8::EscapableHandleScope::new(cbs.enter());
+      let scope = hs.enter();
+      assert_eq!("./dep.js", specifier.to_rust_string_lossy(scope));
+      let source_text = v8_str(scope, "throw new Error('boom');");
+      let resource_name = v8_str(scope, "dep.js");
+      let origin = v8::ScriptOriginBuilder::new()
+        .resource_name(resource_name.into())
+        .is_module(true)
+        .build(scope);
+      let source = v8::script_compiler::Source::new(source_text, &origin);
+      let module = v8::script_compiler::compile_module(scope, source).unwrap();
+      Some(scope.escape(module))
+    }
+    let result = module.instantiate_module(context, resolve_callback);
+    assert_eq!(Some(true), result);
+
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    assert!(module.evaluate(scope, context).is_none());
+    assert!(tc.has_caught());
+    let message = tc.message(scope).unwrap();
+    let name = message.get_script_resource_name(scope).unwrap();
+    assert_eq!(
+      "dep.js",
+      name.to_string(scope).unwrap().to_rust_string_lossy(scope)
+    );
+  }
+}
+
+#[test]
+fn module_code_cache() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let source_text = "export function add(a, b) { return a + b; }";
+    let source = mock_source(scope, "add.js", source_text);
+    let module = v8::script_compiler::compile_module(scope, source).unwrap();
+    let unbound_module_script = module.get_unbound_module_script(scope);
+    let cached_data = unbound_module_script.create_code_cache().unwrap();
+    assert!(!cached_data.is_empty());
+
+    let cached_data = v8::script_compiler::CachedData::new(&cached_data);
+    let source_str = v8_str(scope, source_text);
+    let origin = mock_script_origin(scope, "add.js");
+    let mut source = v8::script_compiler::Source::new_with_cached_data(
+      source_str,
+      &origin,
+      cached_data,
+    );
+    let module =
+      v8::script_compiler::compile_module_with_cached_data(scope, &mut source);
+    assert!(module.is_some());
+    assert!(!source.get_cached_data().unwrap().rejected());
+  }
+}
+
 fn compile_specifier_as_module_resolve_callback<'a>(
   context: v8::Local<'a, v8::Context>,
   specifier: v8::Local<'a, v8::String>,
@@ -1782,6 +4538,18 @@ fn primitive_array() {
   }
 }
 
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn primitive_array_index_out_of_bounds() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let array = v8::PrimitiveArray::new(scope, 3);
+  array.get(scope, 3);
+}
+
 #[test]
 fn equality() {
   let _setup_guard = setup();
@@ -1886,6 +4654,62 @@ fn snapshot_creator() {
   }
 }
 
+#[test]
+fn snapshot_creator_from_existing_snapshot() {
+  let _setup_guard = setup();
+  // Create a snapshot with a single global variable 'a'.
+  let startup_data = {
+    let mut snapshot_creator = v8::SnapshotCreator::new(None);
+    {
+      let mut isolate = unsafe { snapshot_creator.get_owned_isolate() };
+      let mut hs = v8::HandleScope::new(&mut isolate);
+      let scope = hs.enter();
+      let context = v8::Context::new(scope);
+      let mut cs = v8::ContextScope::new(scope, context);
+      let scope = cs.enter();
+      eval(scope, context, "globalThis.a = 1").unwrap();
+      snapshot_creator.set_default_context(context);
+      std::mem::forget(isolate); // TODO(ry) this shouldn't be necessary.
+    }
+    snapshot_creator
+      .create_blob(v8::FunctionCodeHandling::Clear)
+      .unwrap()
+  };
+  // Extend that snapshot with a second global variable 'b'.
+  let startup_data = {
+    let mut snapshot_creator =
+      v8::SnapshotCreator::new_from_existing_snapshot(startup_data, None);
+    {
+      let mut isolate = unsafe { snapshot_creator.get_owned_isolate() };
+      let mut hs = v8::HandleScope::new(&mut isolate);
+      let scope = hs.enter();
+      let context = v8::Context::new(scope);
+      let mut cs = v8::ContextScope::new(scope, context);
+      let scope = cs.enter();
+      let result = eval(scope, context, "a").unwrap();
+      assert_eq!(result.integer_value(scope).unwrap(), 1);
+      eval(scope, context, "globalThis.b = 2").unwrap();
+      snapshot_creator.set_default_context(context);
+      std::mem::forget(isolate); // TODO(ry) this shouldn't be necessary.
+    }
+    snapshot_creator
+      .create_blob(v8::FunctionCodeHandling::Clear)
+      .unwrap()
+  };
+  // An isolate created from the final snapshot sees both variables.
+  {
+    let params = v8::Isolate::create_params().snapshot_blob(startup_data);
+    let mut isolate = v8::Isolate::new(params);
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let result = eval(scope, context, "a === 1 && b === 2").unwrap();
+    assert!(result.is_true());
+  }
+}
+
 lazy_static! {
   static ref EXTERNAL_REFERENCES: v8::ExternalReferences =
     v8::ExternalReferences::new(&[v8::ExternalReference {
@@ -1893,6 +4717,300 @@ lazy_static! {
     }]);
 }
 
+#[test]
+fn snapshot_creator_multiple_contexts() {
+  let _setup_guard = setup();
+  let (startup_data, context_index) = {
+    let mut snapshot_creator = v8::SnapshotCreator::new(None);
+    let context_index;
+    {
+      let mut isolate = unsafe { snapshot_creator.get_owned_isolate() };
+      let mut hs = v8::HandleScope::new(&mut isolate);
+      let scope = hs.enter();
+
+      let default_context = v8::Context::new(scope);
+      {
+        let mut cs = v8::ContextScope::new(scope, default_context);
+        let scope = cs.enter();
+        eval(scope, default_context, "globalThis.a = 'default'").unwrap();
+      }
+      snapshot_creator.set_default_context(default_context);
+
+      let extra_context = v8::Context::new(scope);
+      {
+        let mut cs = v8::ContextScope::new(scope, extra_context);
+        let scope = cs.enter();
+        eval(scope, extra_context, "globalThis.b = 'extra'").unwrap();
+      }
+      context_index = snapshot_creator.add_context(extra_context);
+
+      std::mem::forget(isolate); // TODO(ry) this shouldn't be necessary.
+    }
+
+    let startup_data = snapshot_creator
+      .create_blob(v8::FunctionCodeHandling::Clear)
+      .unwrap();
+    (startup_data, context_index)
+  };
+  assert_eq!(context_index, 0);
+  {
+    let params = v8::Isolate::create_params().snapshot_blob(startup_data);
+    let mut isolate = v8::Isolate::new(params);
+    {
+      let mut hs = v8::HandleScope::new(&mut isolate);
+      let scope = hs.enter();
+
+      let context = v8::Context::new(scope);
+      let mut cs = v8::ContextScope::new(scope, context);
+      let scope = cs.enter();
+      let result = eval(
+        scope,
+        context,
+        "a === 'default' && typeof b === 'undefined'",
+      )
+      .unwrap();
+      assert!(result.is_true());
+
+      let context = v8::Context::from_snapshot(scope, context_index).unwrap();
+      let mut cs = v8::ContextScope::new(scope, context);
+      let scope = cs.enter();
+      let result =
+        eval(scope, context, "b === 'extra' && typeof a === 'undefined'")
+          .unwrap();
+      assert!(result.is_true());
+    }
+  }
+}
+
+#[test]
+fn snapshot_creator_internal_fields() {
+  let _setup_guard = setup();
+
+  fn serialize(holder: v8::Local<v8::Object>, index: i32) -> Vec<u8> {
+    let ptr = unsafe { holder.get_aligned_pointer_from_internal_field(index) };
+    if ptr.is_null() {
+      return vec![];
+    }
+    let value = unsafe { *(ptr as *const u32) };
+    value.to_ne_bytes().to_vec()
+  }
+
+  fn deserialize(holder: v8::Local<v8::Object>, index: i32, payload: &[u8]) {
+    let value = u32::from_ne_bytes(TryFrom::try_from(payload).unwrap());
+    let ptr = Box::into_raw(Box::new(value)) as *mut std::ffi::c_void;
+    holder.set_aligned_pointer_in_internal_field(index, ptr);
+  }
+
+  let (startup_data, context_index) = {
+    let mut snapshot_creator = v8::SnapshotCreator::new(None);
+    let context_index;
+    {
+      let mut isolate = unsafe { snapshot_creator.get_owned_isolate() };
+      let mut hs = v8::HandleScope::new(&mut isolate);
+      let scope = hs.enter();
+
+      let default_context = v8::Context::new(scope);
+      snapshot_creator.set_default_context(default_context);
+
+      let context = v8::Context::new(scope);
+      {
+        let mut cs = v8::ContextScope::new(scope, context);
+        let scope = cs.enter();
+        let templ = v8::ObjectTemplate::new(scope);
+        templ.set_internal_field_count(1);
+        let object = templ.new_instance(scope, context).unwrap();
+        let value = Box::leak(Box::new(0xC0FFEEu32)) as *mut u32;
+        object.set_aligned_pointer_in_internal_field(0, value as *mut _);
+        let key = v8_str(scope, "obj");
+        context
+          .global(scope)
+          .set(context, key.into(), object.into())
+          .unwrap();
+      }
+      context_index =
+        snapshot_creator.add_context_with_serializer(context, serialize);
+
+      std::mem::forget(isolate); // TODO(ry) this shouldn't be necessary.
+    }
+
+    let startup_data = snapshot_creator
+      .create_blob(v8::FunctionCodeHandling::Clear)
+      .unwrap();
+    (startup_data, context_index)
+  };
+  {
+    let params = v8::Isolate::create_params().snapshot_blob(startup_data);
+    let mut isolate = v8::Isolate::new(params);
+    {
+      let mut hs = v8::HandleScope::new(&mut isolate);
+      let scope = hs.enter();
+
+      let context = v8::Context::from_snapshot_with_deserializer(
+        scope,
+        context_index,
+        deserialize,
+      )
+      .unwrap();
+      let mut cs = v8::ContextScope::new(scope, context);
+      let scope = cs.enter();
+      let object = eval(scope, context, "obj").unwrap();
+      let object = v8::Local::<v8::Object>::try_from(object).unwrap();
+      assert_eq!(object.internal_field_count(), 1);
+      let ptr = unsafe { object.get_aligned_pointer_from_internal_field(0) };
+      assert!(!ptr.is_null());
+      let value = unsafe { Box::from_raw(ptr as *mut u32) };
+      assert_eq!(*value, 0xC0FFEE);
+    }
+  }
+}
+
+#[test]
+fn snapshot_creator_default_context_internal_fields() {
+  let _setup_guard = setup();
+
+  const PATTERN: &[u8] = &[0xDE, 0xAD, 0xBE, 0xEF, 0x00, 0x2A];
+
+  fn serialize(holder: v8::Local<v8::Object>, index: i32) -> Vec<u8> {
+    let ptr = unsafe { holder.get_aligned_pointer_from_internal_field(index) };
+    assert!(!ptr.is_null());
+    PATTERN.to_vec()
+  }
+
+  fn deserialize(holder: v8::Local<v8::Object>, index: i32, payload: &[u8]) {
+    let ptr = Box::into_raw(Box::new(payload.to_vec())) as *mut _;
+    holder.set_aligned_pointer_in_internal_field(index, ptr);
+  }
+
+  let startup_data = {
+    let mut snapshot_creator = v8::SnapshotCreator::new(None);
+    {
+      let mut isolate = unsafe { snapshot_creator.get_owned_isolate() };
+      let mut hs = v8::HandleScope::new(&mut isolate);
+      let scope = hs.enter();
+
+      let context = v8::Context::new(scope);
+      {
+        let mut cs = v8::ContextScope::new(scope, context);
+        let scope = cs.enter();
+        let templ = v8::ObjectTemplate::new(scope);
+        templ.set_internal_field_count(1);
+        let object = templ.new_instance(scope, context).unwrap();
+        let value = Box::leak(Box::new(0u64)) as *mut u64;
+        object.set_aligned_pointer_in_internal_field(0, value as *mut _);
+        let key = v8_str(scope, "obj");
+        context
+          .global(scope)
+          .set(context, key.into(), object.into())
+          .unwrap();
+      }
+      snapshot_creator.set_default_context_with_serializer(context, serialize);
+
+      std::mem::forget(isolate); // TODO(ry) this shouldn't be necessary.
+    }
+
+    snapshot_creator
+      .create_blob(v8::FunctionCodeHandling::Clear)
+      .unwrap()
+  };
+  {
+    let params = v8::Isolate::create_params().snapshot_blob(startup_data);
+    let mut isolate = v8::Isolate::new(params);
+    {
+      let mut hs = v8::HandleScope::new(&mut isolate);
+      let scope = hs.enter();
+
+      let context = unsafe {
+        v8::Context::new_with_options(
+          scope,
+          v8::ContextOptions {
+            internal_fields_deserializer: Some(deserialize.map_fn_to()),
+            ..Default::default()
+          },
+        )
+      };
+      let mut cs = v8::ContextScope::new(scope, context);
+      let scope = cs.enter();
+      let object = eval(scope, context, "obj").unwrap();
+      let object = v8::Local::<v8::Object>::try_from(object).unwrap();
+      let ptr = unsafe { object.get_aligned_pointer_from_internal_field(0) };
+      assert!(!ptr.is_null());
+      let payload = unsafe { Box::from_raw(ptr as *mut Vec<u8>) };
+      assert_eq!(&payload[..], PATTERN);
+    }
+  }
+}
+
+#[test]
+fn snapshot_creator_add_data() {
+  let _setup_guard = setup();
+  let (startup_data, isolate_data_index, context_data_index) = {
+    let mut snapshot_creator = v8::SnapshotCreator::new(None);
+    let isolate_data_index;
+    let context_data_index;
+    {
+      let mut isolate = unsafe { snapshot_creator.get_owned_isolate() };
+      let mut hs = v8::HandleScope::new(&mut isolate);
+      let scope = hs.enter();
+
+      let object_templ = v8::ObjectTemplate::new(scope);
+      let name = v8_str(scope, "answer");
+      let value = v8::Integer::new(scope, 42);
+      object_templ.set(name.into(), value.into());
+      isolate_data_index = snapshot_creator.add_isolate_data(object_templ);
+
+      let context = v8::Context::new(scope);
+      let value = v8_str(scope, "context data");
+      context_data_index = snapshot_creator.add_context_data(context, value);
+      snapshot_creator.set_default_context(context);
+
+      std::mem::forget(isolate); // TODO(ry) this shouldn't be necessary.
+    }
+
+    let startup_data = snapshot_creator
+      .create_blob(v8::FunctionCodeHandling::Clear)
+      .unwrap();
+    (startup_data, isolate_data_index, context_data_index)
+  };
+  {
+    let params = v8::Isolate::create_params().snapshot_blob(startup_data);
+    let mut isolate = v8::Isolate::new(params);
+    {
+      let mut hs = v8::HandleScope::new(&mut isolate);
+      let scope = hs.enter();
+      let context = v8::Context::new(scope);
+      let mut cs = v8::ContextScope::new(scope, context);
+      let scope = cs.enter();
+
+      let object_templ = unsafe {
+        v8::Isolate::get_data_from_snapshot_once::<v8::ObjectTemplate>(
+          scope,
+          isolate_data_index,
+        )
+      }
+      .unwrap();
+      let object = object_templ.new_instance(scope, context).unwrap();
+      let name = v8_str(scope, "answer");
+      let value = object.get(scope, context, name.into()).unwrap();
+      assert!(v8::Integer::new(scope, 42).strict_equals(value));
+      // The data can only be retrieved once.
+      assert!(unsafe {
+        v8::Isolate::get_data_from_snapshot_once::<v8::ObjectTemplate>(
+          scope,
+          isolate_data_index,
+        )
+      }
+      .is_none());
+
+      let value = unsafe {
+        context
+          .get_data_from_snapshot_once::<v8::String>(scope, context_data_index)
+      }
+      .unwrap();
+      assert_eq!(value.to_rust_string_lossy(scope), "context data");
+    }
+  }
+}
+
 #[test]
 fn external_references() {
   let _setup_guard = setup();
@@ -2044,6 +5162,99 @@ fn dynamic_import() {
   }
 }
 
+#[test]
+fn dynamic_import_host_defined_options() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+
+  static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+  extern "C" fn dynamic_import_cb(
+    context: v8::Local<v8::Context>,
+    referrer: v8::Local<v8::ScriptOrModule>,
+    _specifier: v8::Local<v8::String>,
+  ) -> *mut v8::Promise {
+    let mut cbs = v8::CallbackScope::new(context);
+    let mut hs = v8::HandleScope::new(cbs.enter());
+    let scope = hs.enter();
+    let resource_name = referrer.get_resource_name();
+    assert!(resource_name.strict_equals(v8_str(scope, "main.js").into()));
+    let host_defined_options = referrer.get_host_defined_options();
+    assert_eq!(host_defined_options.length(), 2);
+    let first = host_defined_options.get(scope, 0);
+    assert!(first.strict_equals(v8_str(scope, "foo").into()));
+    let second = host_defined_options.get(scope, 1);
+    assert!(second.strict_equals(v8::Integer::new(scope, 42).into()));
+    let e = v8_str(scope, "boom");
+    scope.isolate().throw_exception(e.into());
+    CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+    std::ptr::null_mut()
+  }
+  isolate.set_host_import_module_dynamically_callback(dynamic_import_cb);
+
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let host_defined_options = v8::PrimitiveArray::new(scope, 2);
+    let first = v8_str(scope, "foo");
+    host_defined_options.set(scope, 0, first.into());
+    let second = v8::Integer::new(scope, 42);
+    host_defined_options.set(scope, 1, second.into());
+    let resource_name = v8_str(scope, "main.js");
+    let origin = v8::ScriptOriginBuilder::new()
+      .resource_name(resource_name.into())
+      .host_defined_options(host_defined_options)
+      .build(scope);
+    let code = v8_str(scope, "import('bar.js');");
+    let mut script =
+      v8::Script::compile(scope, context, code, Some(&origin)).unwrap();
+    let result = script.run(scope, context);
+    assert!(result.is_some());
+    assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 1);
+  }
+}
+
+#[test]
+fn prepare_stack_trace_callback() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+
+  static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+  extern "C" fn callback(
+    context: v8::Local<v8::Context>,
+    error: v8::Local<v8::Value>,
+    sites: v8::Local<v8::Array>,
+  ) -> *mut v8::Value {
+    let mut cbs = v8::CallbackScope::new_escapable(context);
+    let mut hs = v8::EscapableHandleScope::new(cbs.enter());
+    let scope = hs.enter();
+    assert!(error.is_native_error());
+    assert!(sites.length() > 0);
+    CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+    let stack = v8_str(scope, "custom stack");
+    let stack: v8::Local<v8::Value> = scope.escape(stack.into());
+    &*stack as *const _ as *mut _
+  }
+  isolate.set_prepare_stack_trace_callback(callback);
+
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let result = eval(scope, context, "new Error().stack").unwrap();
+    assert!(result.strict_equals(v8_str(scope, "custom stack").into()));
+    assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 1);
+  }
+}
+
 #[test]
 fn shared_array_buffer() {
   let _setup_guard = setup();
@@ -2433,6 +5644,77 @@ fn value_checker() {
   }
 }
 
+#[test]
+fn value_to_rust_string_lossy() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let value = eval(scope, context, "42").unwrap();
+    assert_eq!(value.to_rust_string_lossy(scope), "42");
+
+    let value =
+      eval(scope, context, "({ toString() { throw 'nope'; } })").unwrap();
+    let mut try_catch = v8::TryCatch::new(scope);
+    let tc = try_catch.enter();
+    assert_eq!(value.to_rust_string_lossy(scope), "<failed>");
+    assert!(tc.has_caught());
+  }
+}
+
+#[test]
+fn value_get_hash() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let s1 = eval(scope, context, "'hash' + 'me'").unwrap();
+    let s2 = v8_str(scope, "hashme");
+    assert!(s1.strict_equals(s2.into()));
+    assert_eq!(s1.get_hash(), s2.get_hash());
+
+    let n1 = eval(scope, context, "0").unwrap();
+    let n2 = eval(scope, context, "-0").unwrap();
+    assert!(n1.strict_equals(n2));
+    assert_eq!(n1.get_hash(), n2.get_hash());
+
+    let o1 = v8::Object::new(scope);
+    let o2 = v8::Object::new(scope);
+    assert_eq!(o1.get_hash(), o1.get_hash());
+    assert_ne!(o1.get_hash(), o2.get_hash());
+  }
+}
+
+#[test]
+fn value_to_array_index() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let value = eval(scope, context, "'5'").unwrap();
+    let index = value.to_array_index(scope).unwrap();
+    assert_eq!(5, index.value());
+
+    let value = eval(scope, context, "'5x'").unwrap();
+    assert!(value.to_array_index(scope).is_none());
+  }
+}
+
 #[test]
 fn try_from_local() {
   let _setup_guard = setup();
@@ -2618,6 +5900,8 @@ struct ChannelCounter {
   count_send_response: usize,
   count_send_notification: usize,
   count_flush_protocol_notifications: usize,
+  last_response: Option<(i32, String)>,
+  last_notification: Option<String>,
 }
 
 impl ChannelCounter {
@@ -2627,6 +5911,8 @@ impl ChannelCounter {
       count_send_response: 0,
       count_send_notification: 0,
       count_flush_protocol_notifications: 0,
+      last_response: None,
+      last_notification: None,
     }
   }
 }
@@ -2643,19 +5929,19 @@ impl v8::inspector::ChannelImpl for ChannelCounter {
     call_id: i32,
     message: v8::UniquePtr<v8::inspector::StringBuffer>,
   ) {
-    println!(
-      "send_response call_id {} message {}",
-      call_id,
-      message.unwrap().string()
-    );
+    let message = message.unwrap().string().to_string();
+    println!("send_response call_id {} message {}", call_id, message);
     self.count_send_response += 1;
+    self.last_response = Some((call_id, message));
   }
   fn send_notification(
     &mut self,
     message: v8::UniquePtr<v8::inspector::StringBuffer>,
   ) {
-    println!("send_notificatio message {}", message.unwrap().string());
+    let message = message.unwrap().string().to_string();
+    println!("send_notificatio message {}", message);
     self.count_send_notification += 1;
+    self.last_notification = Some(message);
   }
   fn flush_protocol_notifications(&mut self) {
     self.count_flush_protocol_notifications += 1;
@@ -2695,6 +5981,159 @@ fn inspector_dispatch_protocol_message() {
   assert_eq!(channel.count_flush_protocol_notifications, 0);
 }
 
+/// Extracts the execution context id from a
+/// `Runtime.executionContextCreated` notification.
+fn execution_context_id(notification: &str) -> i32 {
+  let prefix = r#""context":{"id":"#;
+  let start = notification.find(prefix).unwrap() + prefix.len();
+  notification[start..]
+    .chars()
+    .take_while(|c| c.is_ascii_digit())
+    .collect::<String>()
+    .parse()
+    .unwrap()
+}
+
+#[test]
+fn inspector_post_message() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+
+  use v8::inspector::*;
+  let mut default_client = ClientCounter::new();
+  let mut inspector = V8Inspector::create(&mut isolate, &mut default_client);
+
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context);
+  let _scope = cs.enter();
+
+  let name = b"";
+  let name_view = StringView::from(&name[..]);
+  inspector.context_created(context, 1, name_view);
+  let mut channel = ChannelCounter::new();
+  let state = b"{}";
+  let state_view = StringView::from(&state[..]);
+  let mut session = inspector.connect(1, &mut channel, state_view);
+
+  // The default client doesn't provide a default context, so evaluation
+  // needs an explicit context id, which is reported by `Runtime.enable`.
+  assert_eq!(session.post_message("Runtime.enable", "{}"), Some(1));
+  assert_eq!(channel.count_send_response, 1);
+  let notification = channel.last_notification.take().unwrap();
+  assert!(notification.contains("Runtime.executionContextCreated"));
+  let context_id = execution_context_id(&notification);
+
+  let params = format!(r#"{{"expression":"1+1","contextId":{}}}"#, context_id);
+  let id1 = session.post_message("Runtime.evaluate", &params).unwrap();
+  assert_eq!(channel.count_send_response, 2);
+  let (call_id, message) = channel.last_response.take().unwrap();
+  assert_eq!(call_id as u32, id1);
+  assert!(message.contains(&format!(r#""id":{}"#, id1)));
+  assert!(message.contains(r#""value":2"#));
+
+  let params = format!(r#"{{"expression":"6*7","contextId":{}}}"#, context_id);
+  let id2 = session.post_message("Runtime.evaluate", &params).unwrap();
+  assert_eq!(id2, id1 + 1);
+  assert_eq!(channel.count_send_response, 3);
+  let (call_id, message) = channel.last_response.take().unwrap();
+  assert_eq!(call_id as u32, id2);
+  assert!(message.contains(r#""value":42"#));
+
+  // Non-ASCII text must reach the inspector unmangled.
+  let params = format!(
+    r#"{{"expression":"'é'.length","contextId":{}}}"#,
+    context_id
+  );
+  session.post_message("Runtime.evaluate", &params).unwrap();
+  let (_, message) = channel.last_response.take().unwrap();
+  assert!(message.contains(r#""value":1"#));
+
+  // Malformed params are rejected without dispatching a command.
+  for params in &["", "[]", r#"{"expression":}"#, "{} {}"] {
+    assert_eq!(session.post_message("Runtime.evaluate", params), None);
+  }
+  assert_eq!(channel.count_send_response, 4);
+
+  // Every session numbers its commands starting at 1.
+  let mut channel2 = ChannelCounter::new();
+  let mut session2 = inspector.connect(1, &mut channel2, state_view);
+  assert_eq!(session2.post_message("Runtime.enable", "{}"), Some(1));
+  assert_eq!(channel2.count_send_response, 1);
+}
+
+#[test]
+fn inspector_context_destroyed() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+
+  use v8::inspector::*;
+  let mut default_client = ClientCounter::new();
+  let mut inspector = V8Inspector::create(&mut isolate, &mut default_client);
+
+  let mut hs = v8::HandleScope::new(&mut isolate);
+  let scope = hs.enter();
+  let context1 = v8::Context::new(scope);
+  let mut cs = v8::ContextScope::new(scope, context1);
+  let scope = cs.enter();
+
+  let name = b"";
+  let name_view = StringView::from(&name[..]);
+  inspector.context_created(context1, 1, name_view);
+  let mut channel = ChannelCounter::new();
+  let state = b"{}";
+  let state_view = StringView::from(&state[..]);
+  let mut session = inspector.connect(1, &mut channel, state_view);
+
+  session.post_message("Runtime.enable", "{}").unwrap();
+  let notification = channel.last_notification.take().unwrap();
+  assert!(notification.contains("Runtime.executionContextCreated"));
+  let context_id1 = execution_context_id(&notification);
+
+  inspector.context_destroyed(context1);
+  let notification = channel.last_notification.take().unwrap();
+  assert!(notification.contains("Runtime.executionContextDestroyed"));
+  assert!(
+    notification.contains(&format!(r#""executionContextId":{}"#, context_id1))
+  );
+
+  let context2 = v8::Context::new(scope);
+  let name_view = StringView::from(&name[..]);
+  inspector.context_created(context2, 1, name_view);
+  let notification = channel.last_notification.take().unwrap();
+  assert!(notification.contains("Runtime.executionContextCreated"));
+  let context_id2 = execution_context_id(&notification);
+  assert_ne!(context_id1, context_id2);
+
+  // The destroyed context is no longer known to the inspector.
+  let params = format!(r#"{{"expression":"1+1","contextId":{}}}"#, context_id1);
+  session.post_message("Runtime.evaluate", &params).unwrap();
+  let (_, message) = channel.last_response.take().unwrap();
+  assert!(message.contains(r#""error""#));
+
+  let params = format!(r#"{{"expression":"1+1","contextId":{}}}"#, context_id2);
+  session.post_message("Runtime.evaluate", &params).unwrap();
+  let (_, message) = channel.last_response.take().unwrap();
+  assert!(message.contains(r#""value":2"#));
+  assert!(channel.last_notification.is_none());
+
+  inspector.reset_context_group(1);
+  let notification = channel.last_notification.take().unwrap();
+  assert!(notification.contains("Runtime.executionContextsCleared"));
+}
+
+#[test]
+fn inspector_string_buffer_from_utf8() {
+  use v8::inspector::*;
+  let source = "caf\u{e9} \u{1f995}";
+  let buffer = StringBuffer::from_utf8(source).unwrap();
+  let view = buffer.string();
+  assert!(!view.is_8bit());
+  assert_eq!(view.len(), source.encode_utf16().count());
+  assert_eq!(view.to_string(), source);
+}
+
 #[test]
 fn inspector_schedule_pause_on_next_statement() {
   let _setup_guard = setup();
@@ -2871,6 +6310,78 @@ fn take_heap_snapshot() {
   }
 }
 
+#[test]
+fn sampling_heap_profiler() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  assert!(isolate.get_allocation_profile().is_none());
+  assert!(isolate.start_sampling_heap_profiler(64, 16));
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+    let source = r#"
+      function allocateLots() {
+        const objects = globalThis.objects = [];
+        for (let i = 0; i < 1e4; i++) objects.push({ i });
+      }
+      allocateLots();
+    "#;
+    let _ = eval(scope, context, source).unwrap();
+  }
+
+  fn find(node: &v8::AllocationNode, name: &str) -> bool {
+    node.name == name || node.children.iter().any(|n| find(n, name))
+  }
+  let root = isolate.get_allocation_profile().unwrap();
+  assert!(find(&root, "allocateLots"));
+
+  isolate.stop_sampling_heap_profiler();
+  assert!(isolate.get_allocation_profile().is_none());
+}
+
+#[test]
+fn cpu_profiler() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  let mut profiler = v8::CpuProfiler::new(&mut isolate);
+  profiler.set_sampling_interval(100);
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let title = v8_str(scope, "hot loop");
+    profiler.start_profiling(title);
+    let source = r#"
+      function hotLoop() {
+        const start = Date.now();
+        let n = 0;
+        while (Date.now() - start < 200) n++;
+        return n;
+      }
+      hotLoop();
+    "#;
+    let _ = eval(scope, context, source).unwrap();
+    let profile = profiler.stop_profiling(title).unwrap();
+    assert!(profile.get_samples_count() > 0);
+    assert!(profile.get_end_time() >= profile.get_start_time());
+
+    fn find(node: &v8::CpuProfileNode, name: &str) -> bool {
+      node.get_function_name() == name
+        || (0..node.get_children_count())
+          .any(|i| find(node.get_child(i).unwrap(), name))
+    }
+    assert!(find(profile.get_top_down_root(), "hotLoop"));
+
+    assert!(profiler.stop_profiling(title).is_none());
+  }
+}
+
 #[test]
 fn test_prototype_api() {
   let _setup_guard = setup();
@@ -2966,6 +6477,99 @@ fn test_map_api() {
   }
 }
 
+#[test]
+fn object_preview_entries() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let map = eval(scope, context, "new Map([['r','s'],['v',8]])").unwrap();
+    let map = map.to_object(scope).unwrap();
+    let (entries, is_key_value) = map.preview_entries(scope).unwrap();
+    assert!(is_key_value);
+    assert_eq!(entries.length(), 4);
+    assert!(
+      entries.get_index(scope, context, 0).unwrap()
+        == v8::String::new(scope, "r").unwrap()
+    );
+    assert!(
+      entries.get_index(scope, context, 3).unwrap()
+        == v8::Number::new(scope, 8f64)
+    );
+
+    let set = eval(scope, context, "new Set(['a', 'b', 'c'])").unwrap();
+    let set = set.to_object(scope).unwrap();
+    let (entries, is_key_value) = set.preview_entries(scope).unwrap();
+    assert!(!is_key_value);
+    assert_eq!(entries.length(), 3);
+    assert!(
+      entries.get_index(scope, context, 2).unwrap()
+        == v8::String::new(scope, "c").unwrap()
+    );
+
+    let weak_map = eval(
+      scope,
+      context,
+      "globalThis.key = {}; new WeakMap([[globalThis.key, 42]])",
+    )
+    .unwrap();
+    let weak_map = weak_map.to_object(scope).unwrap();
+    let (entries, is_key_value) = weak_map.preview_entries(scope).unwrap();
+    assert!(is_key_value);
+    assert_eq!(entries.length(), 2);
+    assert!(
+      entries.get_index(scope, context, 1).unwrap()
+        == v8::Number::new(scope, 42f64)
+    );
+
+    let object = v8::Object::new(scope);
+    assert!(object.preview_entries(scope).is_none());
+  }
+}
+
+#[test]
+fn object_get_creation_context() {
+  let _setup_guard = setup();
+  let mut isolate = v8::Isolate::new(Default::default());
+  {
+    let mut hs = v8::HandleScope::new(&mut isolate);
+    let scope = hs.enter();
+    let context = v8::Context::new(scope);
+    let mut cs = v8::ContextScope::new(scope, context);
+    let scope = cs.enter();
+
+    let object = v8::Object::new(scope);
+    let creation_context = object.get_creation_context(scope).unwrap();
+    let lhs = creation_context.global(scope);
+    let rhs = context.global(scope);
+    assert!(lhs.strict_equals(rhs.into()));
+
+    let function = eval(scope, context, "(function() {})").unwrap();
+    let function = v8::Local::<v8::Function>::try_from(function).unwrap();
+    let creation_context = function.get_creation_context(scope).unwrap();
+    let lhs = creation_context.global(scope);
+    assert!(lhs.strict_equals(rhs.into()));
+
+    extern "C" fn access_check(
+      _accessing_context: v8::Local<v8::Context>,
+      _accessed_object: v8::Local<v8::Object>,
+      _data: v8::Local<v8::Value>,
+    ) -> bool {
+      false
+    }
+    let function_templ = v8::FunctionTemplate::new(scope, fortytwo_callback);
+    let instance_templ = function_templ.instance_template(scope);
+    instance_templ.set_access_check_callback(access_check);
+    let remote = function_templ.new_remote_instance(scope).unwrap();
+    assert!(remote.get_creation_context(scope).is_none());
+  }
+}
+
 #[test]
 fn test_object_get_property_names() {
   let _setup_guard = setup();